@@ -0,0 +1,110 @@
+//! Parsing into an owned [`Document`](markdown_ppp::ast::Document) versus
+//! parsing and then compacting into a
+//! [`BorrowedDocument`](markdown_ppp::ast::BorrowedDocument).
+//!
+//! Besides timing both, the benchmark prints what each one allocates in
+//! total while parsing and how many bytes the parsed tree keeps allocated,
+//! counted by a global allocator. Compacting runs after an owned parse, so
+//! its totals are never lower; only the retained bytes shrink.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use markdown_ppp::parser::{parse_markdown, parse_markdown_compact, MarkdownParserState};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts allocations and the bytes currently allocated
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        LIVE_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Allocations made while building a value, and the bytes it keeps
+struct Usage {
+    allocations: usize,
+    allocated_bytes: usize,
+    retained_bytes: usize,
+}
+
+fn corpus() -> String {
+    let section = "## A section\n\n\
+                   Most of a document is prose with some *emphasis*, `code` and\n\
+                   [links](https://example.com/docs) scattered through it.\n\n\
+                   - a list item with text\n\
+                   - another list item\n\n\
+                   > A quoted paragraph that goes on for a while.";
+    vec![section; 100].join("\n\n")
+}
+
+fn usage<T>(f: impl FnOnce() -> T) -> Usage {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+    let value = f();
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes,
+        retained_bytes: LIVE_BYTES.load(Ordering::Relaxed) - live_bytes,
+    };
+    drop(value);
+    usage
+}
+
+fn compact(c: &mut Criterion) {
+    let input = corpus();
+
+    for (name, usage) in [
+        (
+            "owned",
+            usage(|| parse_markdown(MarkdownParserState::default(), &input)),
+        ),
+        (
+            "compact",
+            usage(|| parse_markdown_compact(MarkdownParserState::default(), &input)),
+        ),
+    ] {
+        println!(
+            "{name}: {} allocations, {} bytes allocated, {} bytes retained",
+            usage.allocations, usage.allocated_bytes, usage.retained_bytes
+        );
+    }
+
+    let mut group = c.benchmark_group("compact");
+    group.bench_function("owned", |b| {
+        b.iter(|| parse_markdown(MarkdownParserState::default(), black_box(&input)).unwrap())
+    });
+    group.bench_function("compact", |b| {
+        b.iter(|| {
+            parse_markdown_compact(MarkdownParserState::default(), black_box(&input)).unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, compact);
+criterion_main!(benches);
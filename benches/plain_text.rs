@@ -0,0 +1,41 @@
+//! Parsing plain-text paragraphs, with and without the shortcut that skips
+//! the inline parsers when a paragraph contains no inline markup.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use markdown_ppp::parser::config::MarkdownParserConfig;
+use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+use nom::Parser;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn corpus() -> String {
+    let paragraph = "Most paragraphs in a large corpus are plain prose, with words,\n\
+                     numbers like 42 and punctuation, but no inline markup at all.\n\
+                     They still make up the bulk of the text the parser has to read.";
+    vec![paragraph; 200].join("\n\n")
+}
+
+fn plain_text(c: &mut Criterion) {
+    let input = corpus();
+    let mut group = c.benchmark_group("plain_text");
+
+    group.bench_function("shortcut", |b| {
+        b.iter(|| parse_markdown(MarkdownParserState::default(), black_box(&input)).unwrap())
+    });
+
+    // A custom inline parser rules out the shortcut, so every inline parser runs
+    group.bench_function("full_inline_parse", |b| {
+        b.iter(|| {
+            let config =
+                MarkdownParserConfig::default().with_custom_inline_parser(Rc::new(RefCell::new(
+                    Box::new(|input: &str| nom::combinator::fail::<_, Vec<_>, _>().parse(input)),
+                )));
+            parse_markdown(MarkdownParserState::with_config(config), black_box(&input)).unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, plain_text);
+criterion_main!(benches);
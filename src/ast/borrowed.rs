@@ -0,0 +1,500 @@
+//! Borrowed view of a parsed document
+//!
+//! [`BorrowedDocument`] mirrors the text-heavy part of the AST with
+//! [`Cow<'a, str>`] in place of `String`. Text that appears verbatim in the
+//! source borrows a slice of it instead of owning a copy, which keeps the
+//! retained tree small when many documents are parsed and only read.
+//!
+//! The parser does not borrow: it builds an owned [`Document`] as usual, and
+//! [`Document::into_compact`] then swaps its strings for source slices and
+//! frees the copies. This is a post-hoc compaction that lowers what is kept
+//! alive, not what parsing allocates. Blocks and inlines without a borrowed
+//! counterpart are kept owned in [`BorrowedBlock::Other`] and
+//! [`BorrowedInline::Other`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use markdown_ppp::ast::*;
+//! use std::borrow::Cow;
+//!
+//! let source = "Hello, world";
+//! let doc = Document {
+//!     blocks: vec![Block::Paragraph(vec![Inline::Text("Hello, world".to_string())])],
+//! };
+//! let compact = doc.clone().into_compact(source);
+//! assert!(matches!(
+//!     &compact.blocks[0],
+//!     BorrowedBlock::Paragraph(inlines)
+//!         if matches!(&inlines[0], BorrowedInline::Text(Cow::Borrowed(_)))
+//! ));
+//! assert_eq!(compact.into_owned(), doc);
+//! ```
+
+use super::{
+    Block, CodeBlock, CodeBlockKind, Document, Heading, HeadingKind, HtmlBlock, HtmlBlockKind,
+    Inline, Link, List, ListItem, ListKind, TaskState,
+};
+use std::borrow::Cow;
+
+/// How far past the previous match a string is looked for in the source.
+///
+/// The tree is visited in source order, so a string that appears verbatim
+/// starts shortly after the previous one; the bound keeps strings that do
+/// not appear (unescaped text, normalized labels) from scanning the whole
+/// remaining input.
+const LOOKAHEAD: usize = 4096;
+
+/// Root of a borrowed document
+#[derive(Debug, Clone, PartialEq)]
+pub struct BorrowedDocument<'a> {
+    /// Top-level blocks in source order.
+    pub blocks: Vec<BorrowedBlock<'a>>,
+}
+
+/// Block-level node of a borrowed document
+#[derive(Debug, Clone, PartialEq)]
+pub enum BorrowedBlock<'a> {
+    /// Paragraph.
+    Paragraph(Vec<BorrowedInline<'a>>),
+
+    /// ATX or setext heading.
+    Heading {
+        /// Heading style and level.
+        kind: HeadingKind,
+        /// Heading content.
+        content: Vec<BorrowedInline<'a>>,
+    },
+
+    /// Block quote.
+    BlockQuote(Vec<BorrowedBlock<'a>>),
+
+    /// Bullet or ordered list.
+    List {
+        /// List style.
+        kind: ListKind,
+        /// List items.
+        items: Vec<BorrowedListItem<'a>>,
+        /// Whether the list is tight.
+        tight: bool,
+    },
+
+    /// Indented or fenced code block.
+    CodeBlock {
+        /// Code block style; the info string stays owned.
+        kind: CodeBlockKind,
+        /// Code contents.
+        literal: Cow<'a, str>,
+    },
+
+    /// Raw HTML block.
+    HtmlBlock {
+        /// CommonMark kind, if the parser classified it.
+        kind: Option<HtmlBlockKind>,
+        /// Raw HTML source.
+        content: Cow<'a, str>,
+    },
+
+    /// Thematic break.
+    ThematicBreak,
+
+    /// Any other block, kept owned.
+    Other(Block),
+}
+
+/// Item of a borrowed list
+#[derive(Debug, Clone, PartialEq)]
+pub struct BorrowedListItem<'a> {
+    /// Task list state, if the item is a task.
+    pub task: Option<TaskState>,
+    /// Item contents.
+    pub blocks: Vec<BorrowedBlock<'a>>,
+}
+
+/// Inline-level node of a borrowed document
+#[derive(Debug, Clone, PartialEq)]
+pub enum BorrowedInline<'a> {
+    /// Plain text.
+    Text(Cow<'a, str>),
+
+    /// Hard line break.
+    LineBreak,
+
+    /// Soft line break.
+    SoftBreak,
+
+    /// Code span.
+    Code(Cow<'a, str>),
+
+    /// Raw inline HTML.
+    Html(Cow<'a, str>),
+
+    /// Inline link.
+    Link {
+        /// Link destination.
+        destination: Cow<'a, str>,
+        /// Optional link title.
+        title: Option<Cow<'a, str>>,
+        /// Link text.
+        children: Vec<BorrowedInline<'a>>,
+    },
+
+    /// Emphasis.
+    Emphasis(Vec<BorrowedInline<'a>>),
+
+    /// Strong emphasis.
+    Strong(Vec<BorrowedInline<'a>>),
+
+    /// Strikethrough.
+    Strikethrough(Vec<BorrowedInline<'a>>),
+
+    /// Any other inline, kept owned.
+    Other(Inline),
+}
+
+impl Document {
+    /// Compact into a [`BorrowedDocument`] whose text borrows from `source`
+    ///
+    /// `source` should be the input the document was parsed from. Every
+    /// string that appears there verbatim, in document order, is replaced by
+    /// a slice of it; the rest stays owned. Converting back with
+    /// [`BorrowedDocument::into_owned`] gives the original document.
+    pub fn into_compact(self, source: &str) -> BorrowedDocument<'_> {
+        let mut borrower = Borrower { source, pos: 0 };
+        BorrowedDocument {
+            blocks: borrower.blocks(self.blocks),
+        }
+    }
+}
+
+impl BorrowedDocument<'_> {
+    /// Convert back into an owned [`Document`]
+    pub fn into_owned(self) -> Document {
+        Document {
+            blocks: owned_blocks(self.blocks),
+        }
+    }
+
+    /// Number of strings that borrow from the source
+    pub fn borrowed_count(&self) -> usize {
+        self.blocks.iter().map(BorrowedBlock::borrowed_count).sum()
+    }
+}
+
+impl BorrowedBlock<'_> {
+    fn borrowed_count(&self) -> usize {
+        match self {
+            BorrowedBlock::Paragraph(inlines)
+            | BorrowedBlock::Heading {
+                content: inlines, ..
+            } => inlines.iter().map(BorrowedInline::borrowed_count).sum(),
+            BorrowedBlock::BlockQuote(blocks) => {
+                blocks.iter().map(BorrowedBlock::borrowed_count).sum()
+            }
+            BorrowedBlock::List { items, .. } => items
+                .iter()
+                .flat_map(|item| &item.blocks)
+                .map(BorrowedBlock::borrowed_count)
+                .sum(),
+            BorrowedBlock::CodeBlock { literal: s, .. }
+            | BorrowedBlock::HtmlBlock { content: s, .. } => is_borrowed(s),
+            BorrowedBlock::ThematicBreak | BorrowedBlock::Other(_) => 0,
+        }
+    }
+}
+
+impl BorrowedInline<'_> {
+    fn borrowed_count(&self) -> usize {
+        match self {
+            BorrowedInline::Text(s) | BorrowedInline::Code(s) | BorrowedInline::Html(s) => {
+                is_borrowed(s)
+            }
+            BorrowedInline::Link {
+                destination,
+                title,
+                children,
+            } => {
+                is_borrowed(destination)
+                    + title.as_ref().map_or(0, is_borrowed)
+                    + children
+                        .iter()
+                        .map(BorrowedInline::borrowed_count)
+                        .sum::<usize>()
+            }
+            BorrowedInline::Emphasis(children)
+            | BorrowedInline::Strong(children)
+            | BorrowedInline::Strikethrough(children) => {
+                children.iter().map(BorrowedInline::borrowed_count).sum()
+            }
+            BorrowedInline::LineBreak | BorrowedInline::SoftBreak | BorrowedInline::Other(_) => 0,
+        }
+    }
+}
+
+// Takes the `Cow` itself, since the variant is what is being checked
+#[allow(clippy::ptr_arg)]
+fn is_borrowed(s: &Cow<'_, str>) -> usize {
+    usize::from(matches!(s, Cow::Borrowed(_)))
+}
+
+/// Replaces strings with slices of the source, scanning it front to back
+struct Borrower<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Borrower<'a> {
+    fn borrow(&mut self, s: String) -> Cow<'a, str> {
+        if s.is_empty() {
+            return Cow::Owned(s);
+        }
+        let mut end = (self.pos + LOOKAHEAD + s.len()).min(self.source.len());
+        while !self.source.is_char_boundary(end) {
+            end -= 1;
+        }
+        match self.source[self.pos..end].find(s.as_str()) {
+            Some(offset) => {
+                let start = self.pos + offset;
+                self.pos = start + s.len();
+                Cow::Borrowed(&self.source[start..self.pos])
+            }
+            None => Cow::Owned(s),
+        }
+    }
+
+    fn blocks(&mut self, blocks: Vec<Block>) -> Vec<BorrowedBlock<'a>> {
+        blocks.into_iter().map(|block| self.block(block)).collect()
+    }
+
+    fn block(&mut self, block: Block) -> BorrowedBlock<'a> {
+        match block {
+            Block::Paragraph(inlines) => BorrowedBlock::Paragraph(self.inlines(inlines)),
+            Block::Heading(Heading { kind, content }) => BorrowedBlock::Heading {
+                kind,
+                content: self.inlines(content),
+            },
+            Block::BlockQuote(blocks) => BorrowedBlock::BlockQuote(self.blocks(blocks)),
+            Block::List(List { kind, items, tight }) => BorrowedBlock::List {
+                kind,
+                items: items
+                    .into_iter()
+                    .map(|item| BorrowedListItem {
+                        task: item.task,
+                        blocks: self.blocks(item.blocks),
+                    })
+                    .collect(),
+                tight,
+            },
+            Block::CodeBlock(CodeBlock { kind, literal }) => BorrowedBlock::CodeBlock {
+                kind,
+                literal: self.borrow(literal),
+            },
+            Block::HtmlBlock(HtmlBlock { kind, content }) => BorrowedBlock::HtmlBlock {
+                kind,
+                content: self.borrow(content),
+            },
+            Block::ThematicBreak => BorrowedBlock::ThematicBreak,
+            other => BorrowedBlock::Other(other),
+        }
+    }
+
+    fn inlines(&mut self, inlines: Vec<Inline>) -> Vec<BorrowedInline<'a>> {
+        inlines
+            .into_iter()
+            .map(|inline| self.inline(inline))
+            .collect()
+    }
+
+    fn inline(&mut self, inline: Inline) -> BorrowedInline<'a> {
+        match inline {
+            Inline::Text(text) => BorrowedInline::Text(self.borrow(text)),
+            Inline::LineBreak => BorrowedInline::LineBreak,
+            Inline::SoftBreak => BorrowedInline::SoftBreak,
+            Inline::Code(code) => BorrowedInline::Code(self.borrow(code)),
+            Inline::Html(html) => BorrowedInline::Html(self.borrow(html)),
+            Inline::Link(Link {
+                destination,
+                title,
+                children,
+            }) => {
+                // `[children](destination "title")`: borrow in source order
+                let children = self.inlines(children);
+                let destination = self.borrow(destination);
+                let title = title.map(|title| self.borrow(title));
+                BorrowedInline::Link {
+                    destination,
+                    title,
+                    children,
+                }
+            }
+            Inline::Emphasis(children) => BorrowedInline::Emphasis(self.inlines(children)),
+            Inline::Strong(children) => BorrowedInline::Strong(self.inlines(children)),
+            Inline::Strikethrough(children) => {
+                BorrowedInline::Strikethrough(self.inlines(children))
+            }
+            other => BorrowedInline::Other(other),
+        }
+    }
+}
+
+fn owned_blocks(blocks: Vec<BorrowedBlock<'_>>) -> Vec<Block> {
+    blocks.into_iter().map(owned_block).collect()
+}
+
+fn owned_block(block: BorrowedBlock<'_>) -> Block {
+    match block {
+        BorrowedBlock::Paragraph(inlines) => Block::Paragraph(owned_inlines(inlines)),
+        BorrowedBlock::Heading { kind, content } => Block::Heading(Heading {
+            kind,
+            content: owned_inlines(content),
+        }),
+        BorrowedBlock::BlockQuote(blocks) => Block::BlockQuote(owned_blocks(blocks)),
+        BorrowedBlock::List { kind, items, tight } => Block::List(List {
+            kind,
+            items: items
+                .into_iter()
+                .map(|item| ListItem {
+                    task: item.task,
+                    blocks: owned_blocks(item.blocks),
+                })
+                .collect(),
+            tight,
+        }),
+        BorrowedBlock::CodeBlock { kind, literal } => Block::CodeBlock(CodeBlock {
+            kind,
+            literal: literal.into_owned(),
+        }),
+        BorrowedBlock::HtmlBlock { kind, content } => Block::HtmlBlock(HtmlBlock {
+            kind,
+            content: content.into_owned(),
+        }),
+        BorrowedBlock::ThematicBreak => Block::ThematicBreak,
+        BorrowedBlock::Other(block) => block,
+    }
+}
+
+fn owned_inlines(inlines: Vec<BorrowedInline<'_>>) -> Vec<Inline> {
+    inlines.into_iter().map(owned_inline).collect()
+}
+
+fn owned_inline(inline: BorrowedInline<'_>) -> Inline {
+    match inline {
+        BorrowedInline::Text(text) => Inline::Text(text.into_owned()),
+        BorrowedInline::LineBreak => Inline::LineBreak,
+        BorrowedInline::SoftBreak => Inline::SoftBreak,
+        BorrowedInline::Code(code) => Inline::Code(code.into_owned()),
+        BorrowedInline::Html(html) => Inline::Html(html.into_owned()),
+        BorrowedInline::Link {
+            destination,
+            title,
+            children,
+        } => Inline::Link(Link {
+            destination: destination.into_owned(),
+            title: title.map(Cow::into_owned),
+            children: owned_inlines(children),
+        }),
+        BorrowedInline::Emphasis(children) => Inline::Emphasis(owned_inlines(children)),
+        BorrowedInline::Strong(children) => Inline::Strong(owned_inlines(children)),
+        BorrowedInline::Strikethrough(children) => Inline::Strikethrough(owned_inlines(children)),
+        BorrowedInline::Other(inline) => inline,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> Inline {
+        Inline::Text(s.to_string())
+    }
+
+    /// Whether `s` borrows from `source`
+    fn points_into(s: &str, source: &str) -> bool {
+        source.as_bytes().as_ptr_range().contains(&s.as_ptr())
+    }
+
+    #[test]
+    fn verbatim_text_borrows_from_source() {
+        let source = "Hello *world*, see [docs](https://example.com).";
+        let doc = Document {
+            blocks: vec![Block::Paragraph(vec![
+                text("Hello "),
+                Inline::Emphasis(vec![text("world")]),
+                text(", see "),
+                Inline::Link(Link {
+                    destination: "https://example.com".to_string(),
+                    title: None,
+                    children: vec![text("docs")],
+                }),
+                text("."),
+            ])],
+        };
+
+        let borrowed = doc.clone().into_compact(source);
+        assert_eq!(borrowed.borrowed_count(), 6);
+        let BorrowedBlock::Paragraph(inlines) = &borrowed.blocks[0] else {
+            panic!("expected a paragraph");
+        };
+        let BorrowedInline::Text(first) = &inlines[0] else {
+            panic!("expected text");
+        };
+        assert!(points_into(first, source));
+        assert_eq!(borrowed.into_owned(), doc);
+    }
+
+    #[test]
+    fn unescaped_text_stays_owned() {
+        let source = "a \\*b\\* c";
+        let doc = Document {
+            blocks: vec![Block::Paragraph(vec![text("a *b* c")])],
+        };
+
+        let borrowed = doc.clone().into_compact(source);
+        assert_eq!(
+            borrowed.blocks,
+            vec![BorrowedBlock::Paragraph(vec![BorrowedInline::Text(
+                Cow::Owned("a *b* c".to_string())
+            )])]
+        );
+        assert_eq!(borrowed.into_owned(), doc);
+    }
+
+    #[test]
+    fn repeated_text_borrows_in_order() {
+        let source = "same\n\nsame";
+        let doc = Document {
+            blocks: vec![
+                Block::Paragraph(vec![text("same")]),
+                Block::Paragraph(vec![text("same")]),
+            ],
+        };
+
+        let borrowed = doc.into_compact(source);
+        let starts: Vec<_> = borrowed
+            .blocks
+            .iter()
+            .map(|block| match block {
+                BorrowedBlock::Paragraph(inlines) => match &inlines[0] {
+                    BorrowedInline::Text(Cow::Borrowed(s)) => s.as_ptr() as usize,
+                    _ => panic!("expected borrowed text"),
+                },
+                _ => panic!("expected a paragraph"),
+            })
+            .collect();
+        let base = source.as_ptr() as usize;
+        assert_eq!(starts, vec![base, base + 6]);
+    }
+
+    #[cfg(feature = "parser")]
+    #[test]
+    fn parsed_document_round_trips() {
+        let input = "# Title\n\nSome *text* with `code` and [a link](/url \"Title\").\n\n\
+                     > quoted\n\n- one\n- [x] two\n\n```rust\nfn main() {}\n```\n\n\
+                     | a | b |\n|---|---|\n| 1 | 2 |\n";
+        let state = || crate::parser::MarkdownParserState::default();
+        let doc = crate::parser::parse_markdown(state(), input).unwrap();
+        let borrowed = crate::parser::parse_markdown_compact(state(), input).unwrap();
+        assert!(borrowed.borrowed_count() >= 10);
+        assert_eq!(borrowed.into_owned(), doc);
+    }
+}
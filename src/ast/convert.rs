@@ -90,8 +90,9 @@ impl<T: Default> WithData<T> for Block {
             },
             Block::List(list) => generic::Block::List(list.with_data(data)),
             Block::CodeBlock(code_block) => generic::Block::CodeBlock(code_block.with_data(data)),
-            Block::HtmlBlock(content) => generic::Block::HtmlBlock {
-                content,
+            Block::HtmlBlock(html) => generic::Block::HtmlBlock {
+                kind: html.kind,
+                content: html.content,
                 user_data: data,
             },
             Block::Definition(def) => generic::Block::Definition(def.with_data(data)),
@@ -138,6 +139,7 @@ impl<T: Default> WithData<T> for Inline {
                 user_data: data,
             },
             Inline::LineBreak => generic::Inline::LineBreak { user_data: data },
+            Inline::SoftBreak => generic::Inline::SoftBreak { user_data: data },
             Inline::Code(content) => generic::Inline::Code {
                 content,
                 user_data: data,
@@ -176,6 +178,32 @@ impl<T: Default> WithData<T> for Inline {
                     .collect(),
                 user_data: data,
             },
+            Inline::Highlight(content) => generic::Inline::Highlight {
+                content: content
+                    .into_iter()
+                    .map(|i| i.with_data(T::default()))
+                    .collect(),
+                user_data: data,
+            },
+            Inline::Subscript(content) => generic::Inline::Subscript {
+                content: content
+                    .into_iter()
+                    .map(|i| i.with_data(T::default()))
+                    .collect(),
+                user_data: data,
+            },
+            Inline::Superscript(content) => generic::Inline::Superscript {
+                content: content
+                    .into_iter()
+                    .map(|i| i.with_data(T::default()))
+                    .collect(),
+                user_data: data,
+            },
+            Inline::Delimited(delimited) => generic::Inline::Delimited {
+                delimiter: delimited.delimiter,
+                inner: Box::new(delimited.inner.with_data(T::default())),
+                user_data: data,
+            },
             Inline::Autolink(url) => generic::Inline::Autolink {
                 url,
                 user_data: data,
@@ -216,6 +244,7 @@ impl<T: Default> WithData<T> for List {
                 .into_iter()
                 .map(|i| i.with_data(T::default()))
                 .collect(),
+            tight: self.tight,
             user_data: data,
         }
     }
@@ -410,7 +439,9 @@ impl<T: Default> StripData<T> for generic::Block<T> {
             }
             generic::Block::List(list) => Block::List(list.strip_data()),
             generic::Block::CodeBlock(code_block) => Block::CodeBlock(code_block.strip_data()),
-            generic::Block::HtmlBlock { content, .. } => Block::HtmlBlock(content),
+            generic::Block::HtmlBlock { kind, content, .. } => {
+                Block::HtmlBlock(HtmlBlock { kind, content })
+            }
             generic::Block::Definition(def) => Block::Definition(def.strip_data()),
             generic::Block::Table(table) => Block::Table(table.strip_data()),
             generic::Block::FootnoteDefinition(footnote) => {
@@ -431,6 +462,7 @@ impl<T> StripData<T> for generic::Inline<T> {
         match self {
             generic::Inline::Text { content, .. } => Inline::Text(content),
             generic::Inline::LineBreak { .. } => Inline::LineBreak,
+            generic::Inline::SoftBreak { .. } => Inline::SoftBreak,
             generic::Inline::Code { content, .. } => Inline::Code(content),
             generic::Inline::Latex { content, .. } => Inline::Latex(content),
             generic::Inline::Html { content, .. } => Inline::Html(content),
@@ -448,6 +480,21 @@ impl<T> StripData<T> for generic::Inline<T> {
             generic::Inline::Strikethrough { content, .. } => {
                 Inline::Strikethrough(content.into_iter().map(|i| i.strip_data()).collect())
             }
+            generic::Inline::Highlight { content, .. } => {
+                Inline::Highlight(content.into_iter().map(|i| i.strip_data()).collect())
+            }
+            generic::Inline::Subscript { content, .. } => {
+                Inline::Subscript(content.into_iter().map(|i| i.strip_data()).collect())
+            }
+            generic::Inline::Superscript { content, .. } => {
+                Inline::Superscript(content.into_iter().map(|i| i.strip_data()).collect())
+            }
+            generic::Inline::Delimited {
+                delimiter, inner, ..
+            } => Inline::Delimited(Delimited {
+                delimiter,
+                inner: Box::new(inner.strip_data()),
+            }),
             generic::Inline::Autolink { url, .. } => Inline::Autolink(url),
             generic::Inline::FootnoteReference { label, .. } => Inline::FootnoteReference(label),
             generic::Inline::Empty { .. } => Inline::Empty,
@@ -473,6 +520,7 @@ impl<T: Default> StripData<T> for generic::List<T> {
         List {
             kind: self.kind.into(),
             items: self.items.into_iter().map(|i| i.strip_data()).collect(),
+            tight: self.tight,
         }
     }
 }
@@ -649,7 +697,8 @@ impl<T, U> MapData<T, U> for generic::Block<T> {
             },
             generic::Block::List(list) => generic::Block::List(list.map_data(f)),
             generic::Block::CodeBlock(code_block) => generic::Block::CodeBlock(code_block.map_data(f)),
-            generic::Block::HtmlBlock { content, user_data } => generic::Block::HtmlBlock {
+            generic::Block::HtmlBlock { kind, content, user_data } => generic::Block::HtmlBlock {
+                kind,
                 content,
                 user_data: f(user_data),
             },
@@ -675,6 +724,7 @@ impl<T, U> MapData<T, U> for generic::Inline<T> {
                 user_data: f(user_data),
             },
             generic::Inline::LineBreak { user_data } => generic::Inline::LineBreak { user_data: f(user_data) },
+            generic::Inline::SoftBreak { user_data } => generic::Inline::SoftBreak { user_data: f(user_data) },
             generic::Inline::Code { content, user_data } => generic::Inline::Code {
                 content,
                 user_data: f(user_data),
@@ -698,6 +748,27 @@ impl<T, U> MapData<T, U> for generic::Inline<T> {
                 content: content.into_iter().map(|i| i.map_data(&mut f)).collect(),
                 user_data: f(user_data),
             },
+            generic::Inline::Highlight { content, user_data } => generic::Inline::Highlight {
+                content: content.into_iter().map(|i| i.map_data(&mut f)).collect(),
+                user_data: f(user_data),
+            },
+            generic::Inline::Subscript { content, user_data } => generic::Inline::Subscript {
+                content: content.into_iter().map(|i| i.map_data(&mut f)).collect(),
+                user_data: f(user_data),
+            },
+            generic::Inline::Superscript { content, user_data } => generic::Inline::Superscript {
+                content: content.into_iter().map(|i| i.map_data(&mut f)).collect(),
+                user_data: f(user_data),
+            },
+            generic::Inline::Delimited {
+                delimiter,
+                inner,
+                user_data,
+            } => generic::Inline::Delimited {
+                delimiter,
+                inner: Box::new(inner.map_data(&mut f)),
+                user_data: f(user_data),
+            },
             generic::Inline::Autolink { url, user_data } => generic::Inline::Autolink {
                 url,
                 user_data: f(user_data),
@@ -739,6 +810,7 @@ impl<T, U> MapData<T, U> for generic::List<T> {
         generic::List {
             kind: self.kind,
             items: self.items.into_iter().map(|i| i.map_data(&mut f)).collect(),
+            tight: self.tight,
             user_data: f(self.user_data),
         }
     }
@@ -0,0 +1,188 @@
+//! Structural diff of two documents
+//!
+//! [`diff`] compares the top-level blocks of two documents with a longest
+//! common subsequence and reports what was inserted, removed or changed.
+
+use super::{Block, Document};
+
+/// One difference between two documents, see [`diff`].
+///
+/// Indices refer to `old.blocks` and `new.blocks` respectively.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffOp<'a> {
+    /// A block present only in the new document.
+    Inserted { new_index: usize, block: &'a Block },
+
+    /// A block present only in the old document.
+    Removed { old_index: usize, block: &'a Block },
+
+    /// A block that was replaced by a different one at the same position.
+    Changed {
+        old_index: usize,
+        new_index: usize,
+        old: &'a Block,
+        new: &'a Block,
+    },
+}
+
+/// Compare the top-level blocks of `old` and `new`.
+///
+/// Blocks are compared by equality; unchanged blocks are not reported. When
+/// blocks are removed and inserted between the same two unchanged blocks,
+/// they are paired up in order and reported as [`DiffOp::Changed`].
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::ast::*;
+///
+/// let para = |text: &str| Block::Paragraph(vec![Inline::Text(text.to_string())]);
+/// let old = Document { blocks: vec![para("a"), para("b"), para("c")] };
+/// let new = Document { blocks: vec![para("a"), para("b"), para("C")] };
+///
+/// assert_eq!(
+///     diff(&old, &new),
+///     vec![DiffOp::Changed {
+///         old_index: 2,
+///         new_index: 2,
+///         old: &old.blocks[2],
+///         new: &new.blocks[2],
+///     }]
+/// );
+/// ```
+pub fn diff<'a>(old: &'a Document, new: &'a Document) -> Vec<DiffOp<'a>> {
+    let (old, new) = (&old.blocks, &new.blocks);
+
+    // lcs[i][j] is the LCS length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut removed, mut inserted) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            flush(&mut ops, &mut removed, &mut inserted, old, new);
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            removed.push(i);
+            i += 1;
+        } else {
+            inserted.push(j);
+            j += 1;
+        }
+    }
+    flush(&mut ops, &mut removed, &mut inserted, old, new);
+    ops
+}
+
+/// Emit the removals and insertions collected between two unchanged blocks
+fn flush<'a>(
+    ops: &mut Vec<DiffOp<'a>>,
+    removed: &mut Vec<usize>,
+    inserted: &mut Vec<usize>,
+    old: &'a [Block],
+    new: &'a [Block],
+) {
+    let paired = removed.len().min(inserted.len());
+    for (&old_index, &new_index) in removed.iter().zip(inserted.iter()) {
+        ops.push(DiffOp::Changed {
+            old_index,
+            new_index,
+            old: &old[old_index],
+            new: &new[new_index],
+        });
+    }
+    for &old_index in &removed[paired..] {
+        ops.push(DiffOp::Removed {
+            old_index,
+            block: &old[old_index],
+        });
+    }
+    for &new_index in &inserted[paired..] {
+        ops.push(DiffOp::Inserted {
+            new_index,
+            block: &new[new_index],
+        });
+    }
+    removed.clear();
+    inserted.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Inline;
+
+    fn para(text: &str) -> Block {
+        Block::Paragraph(vec![Inline::Text(text.to_string())])
+    }
+
+    fn doc(texts: &[&str]) -> Document {
+        Document {
+            blocks: texts.iter().map(|text| para(text)).collect(),
+        }
+    }
+
+    #[test]
+    fn identical_documents() {
+        let old = doc(&["a", "b"]);
+        assert!(diff(&old, &old.clone()).is_empty());
+    }
+
+    #[test]
+    fn inserted_block() {
+        let old = doc(&["a", "c"]);
+        let new = doc(&["a", "b", "c"]);
+        assert_eq!(
+            diff(&old, &new),
+            vec![DiffOp::Inserted {
+                new_index: 1,
+                block: &new.blocks[1],
+            }]
+        );
+    }
+
+    #[test]
+    fn removed_block() {
+        let old = doc(&["a", "b", "c"]);
+        let new = doc(&["a", "c"]);
+        assert_eq!(
+            diff(&old, &new),
+            vec![DiffOp::Removed {
+                old_index: 1,
+                block: &old.blocks[1],
+            }]
+        );
+    }
+
+    #[test]
+    fn modified_paragraph() {
+        let old = doc(&["a", "b", "c", "d"]);
+        let new = doc(&["a", "b", "third", "d", "e"]);
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                DiffOp::Changed {
+                    old_index: 2,
+                    new_index: 2,
+                    old: &old.blocks[2],
+                    new: &new.blocks[2],
+                },
+                DiffOp::Inserted {
+                    new_index: 4,
+                    block: &new.blocks[4],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Human-readable tree dump of a [`Document`]
+//!
+//! The default `Debug` output is hard to read for deeply nested documents.
+//! [`dump_tree`] renders one node per line, indented by depth, which is handy
+//! when inspecting parser output or filing bug reports.
+
+use super::*;
+use std::fmt::Write;
+
+/// Maximum number of characters of literal text shown per node.
+const MAX_TEXT_LEN: usize = 40;
+
+/// Render a document as an indented tree, one node per line.
+///
+/// Literal text is quoted and truncated to keep lines short.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::ast::*;
+///
+/// let doc = Document {
+///     blocks: vec![Block::Paragraph(vec![
+///         Inline::Text("hello".to_string()),
+///         Inline::Emphasis(vec![Inline::Text("world".to_string())]),
+///     ])],
+/// };
+/// assert_eq!(
+///     dump_tree(&doc),
+///     "Document\n  Paragraph\n    Text \"hello\"\n    Emphasis\n      Text \"world\"\n"
+/// );
+/// ```
+pub fn dump_tree(doc: &Document) -> String {
+    let mut dumper = TreeDumper::default();
+    dumper.line(0, "Document");
+    for block in &doc.blocks {
+        dumper.block(1, block);
+    }
+    dumper.out
+}
+
+#[derive(Default)]
+struct TreeDumper {
+    out: String,
+}
+
+impl TreeDumper {
+    fn line(&mut self, depth: usize, label: impl AsRef<str>) {
+        let _ = writeln!(
+            self.out,
+            "{:indent$}{}",
+            "",
+            label.as_ref(),
+            indent = depth * 2
+        );
+    }
+
+    fn blocks(&mut self, depth: usize, blocks: &[Block]) {
+        for block in blocks {
+            self.block(depth, block);
+        }
+    }
+
+    fn inlines(&mut self, depth: usize, inlines: &[Inline]) {
+        for inline in inlines {
+            self.inline(depth, inline);
+        }
+    }
+
+    fn block(&mut self, depth: usize, block: &Block) {
+        match block {
+            Block::Paragraph(inlines) => {
+                self.line(depth, "Paragraph");
+                self.inlines(depth + 1, inlines);
+            }
+            Block::Heading(heading) => {
+                let level = heading.level();
+                self.line(depth, format!("Heading level={level}"));
+                self.inlines(depth + 1, &heading.content);
+            }
+            Block::ThematicBreak => self.line(depth, "ThematicBreak"),
+            Block::BlockQuote(blocks) => {
+                self.line(depth, "BlockQuote");
+                self.blocks(depth + 1, blocks);
+            }
+            Block::List(list) => {
+                let label = match &list.kind {
+                    ListKind::Ordered(options) if options.style == OrderedListStyle::Decimal => {
+                        format!("List ordered start={}", options.start)
+                    }
+                    ListKind::Ordered(options) => format!(
+                        "List ordered start={} style={:?}",
+                        options.start, options.style
+                    ),
+                    ListKind::Bullet(kind) => format!("List bullet={kind:?}"),
+                };
+                self.line(depth, label);
+                for item in &list.items {
+                    match item.task {
+                        Some(task) => self.line(depth + 1, format!("ListItem task={task:?}")),
+                        None => self.line(depth + 1, "ListItem"),
+                    }
+                    self.blocks(depth + 2, &item.blocks);
+                }
+            }
+            Block::CodeBlock(code) => {
+                let label = match &code.kind {
+                    CodeBlockKind::Indented => "CodeBlock indented".to_string(),
+                    CodeBlockKind::Fenced { info: Some(info) } => {
+                        format!("CodeBlock fenced info={}", quoted(info))
+                    }
+                    CodeBlockKind::Fenced { info: None } => "CodeBlock fenced".to_string(),
+                };
+                self.line(depth, format!("{label} {}", quoted(&code.literal)));
+            }
+            Block::HtmlBlock(html) => {
+                let label = match html.kind {
+                    Some(kind) => format!("HtmlBlock kind={kind:?}"),
+                    None => "HtmlBlock".to_string(),
+                };
+                self.line(depth, format!("{label} {}", quoted(&html.content)));
+            }
+            Block::Definition(definition) => {
+                self.line(
+                    depth,
+                    format!("Definition destination={}", quoted(&definition.destination)),
+                );
+                self.inlines(depth + 1, &definition.label);
+            }
+            Block::Table(table) => {
+                self.line(depth, format!("Table alignments={:?}", table.alignments));
+                for row in &table.rows {
+                    self.line(depth + 1, "TableRow");
+                    for cell in row {
+                        let mut label = "TableCell".to_string();
+                        if let Some(colspan) = cell.colspan {
+                            let _ = write!(label, " colspan={colspan}");
+                        }
+                        if let Some(rowspan) = cell.rowspan {
+                            let _ = write!(label, " rowspan={rowspan}");
+                        }
+                        if cell.removed_by_extended_table {
+                            label.push_str(" removed");
+                        }
+                        self.line(depth + 2, label);
+                        self.inlines(depth + 3, &cell.content);
+                    }
+                }
+            }
+            Block::FootnoteDefinition(footnote) => {
+                self.line(
+                    depth,
+                    format!("FootnoteDefinition label={}", quoted(&footnote.label)),
+                );
+                self.blocks(depth + 1, &footnote.blocks);
+            }
+            Block::GitHubAlert(alert) => {
+                self.line(depth, format!("GitHubAlert {:?}", alert.alert_type));
+                self.blocks(depth + 1, &alert.blocks);
+            }
+            Block::LatexBlock(latex) => self.line(depth, format!("LatexBlock {}", quoted(latex))),
+            Block::Empty => self.line(depth, "Empty"),
+            Block::Container(container) => {
+                self.line(depth, format!("Container kind={}", quoted(&container.kind)));
+                self.blocks(depth + 1, &container.blocks);
+            }
+            Block::MacroBlock(content) => {
+                self.line(depth, format!("MacroBlock {}", quoted(content)))
+            }
+        }
+    }
+
+    fn inline(&mut self, depth: usize, inline: &Inline) {
+        match inline {
+            Inline::Text(text) => self.line(depth, format!("Text {}", quoted(text))),
+            Inline::LineBreak => self.line(depth, "LineBreak"),
+            Inline::SoftBreak => self.line(depth, "SoftBreak"),
+            Inline::Code(code) => self.line(depth, format!("Code {}", quoted(code))),
+            Inline::Latex(latex) => self.line(depth, format!("Latex {}", quoted(latex))),
+            Inline::Html(html) => self.line(depth, format!("Html {}", quoted(html))),
+            Inline::Link(link) => {
+                self.line(
+                    depth,
+                    format!("Link destination={}", quoted(&link.destination)),
+                );
+                self.inlines(depth + 1, &link.children);
+            }
+            Inline::LinkReference(reference) => {
+                self.line(depth, "LinkReference");
+                self.inlines(depth + 1, &reference.text);
+            }
+            Inline::Image(image) => self.line(
+                depth,
+                format!(
+                    "Image destination={} alt={}",
+                    quoted(&image.destination),
+                    quoted(&image.alt)
+                ),
+            ),
+            Inline::Emphasis(children) => {
+                self.line(depth, "Emphasis");
+                self.inlines(depth + 1, children);
+            }
+            Inline::Strong(children) => {
+                self.line(depth, "Strong");
+                self.inlines(depth + 1, children);
+            }
+            Inline::Strikethrough(children) => {
+                self.line(depth, "Strikethrough");
+                self.inlines(depth + 1, children);
+            }
+            Inline::Highlight(children) => {
+                self.line(depth, "Highlight");
+                self.inlines(depth + 1, children);
+            }
+            Inline::Subscript(children) => {
+                self.line(depth, "Subscript");
+                self.inlines(depth + 1, children);
+            }
+            Inline::Superscript(children) => {
+                self.line(depth, "Superscript");
+                self.inlines(depth + 1, children);
+            }
+            Inline::Delimited(delimited) => {
+                self.line(depth, format!("Delimited {}", quoted(&delimited.delimiter)));
+                self.inline(depth + 1, &delimited.inner);
+            }
+            Inline::Autolink(url) => self.line(depth, format!("Autolink {}", quoted(url))),
+            Inline::FootnoteReference(label) => {
+                self.line(depth, format!("FootnoteReference {}", quoted(label)))
+            }
+            Inline::Empty => self.line(depth, "Empty"),
+        }
+    }
+}
+
+/// Quote text with `Debug` escaping, truncating long values with `…`.
+fn quoted(text: &str) -> String {
+    if text.chars().count() > MAX_TEXT_LEN {
+        let truncated: String = text.chars().take(MAX_TEXT_LEN).collect();
+        format!("{truncated:?}…")
+    } else {
+        format!("{text:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dump_nested_document() {
+        let doc = Document {
+            blocks: vec![
+                Block::Heading(Heading {
+                    kind: HeadingKind::Atx(1),
+                    content: vec![Inline::Text("Title".to_string())],
+                }),
+                Block::List(List {
+                    kind: ListKind::Bullet(ListBulletKind::Dash),
+                    items: vec![ListItem {
+                        task: Some(TaskState::Complete),
+                        blocks: vec![Block::Paragraph(vec![
+                            Inline::Text("see ".to_string()),
+                            Inline::Link(Link {
+                                destination: "/url".to_string(),
+                                title: None,
+                                children: vec![Inline::Strong(vec![Inline::Text(
+                                    "here".to_string(),
+                                )])],
+                            }),
+                        ])],
+                    }],
+                    tight: true,
+                }),
+                Block::Paragraph(vec![Inline::Text("x".repeat(50))]),
+            ],
+        };
+
+        let expected = format!(
+            "Document
+  Heading level=1
+    Text \"Title\"
+  List bullet=Dash
+    ListItem task=Complete
+      Paragraph
+        Text \"see \"
+        Link destination=\"/url\"
+          Strong
+            Text \"here\"
+  Paragraph
+    Text \"{}\"…
+",
+            "x".repeat(40)
+        );
+        assert_eq!(dump_tree(&doc), expected);
+    }
+}
@@ -36,8 +36,8 @@
 
 // Re-export types from parent module that don't need generics
 pub use super::{
-    Alignment, CodeBlockKind, GitHubAlert, GitHubAlertType, HeadingKind, ListBulletKind,
-    ListOrderedKindOptions, SetextHeading, TaskState,
+    Alignment, CodeBlockKind, GitHubAlert, GitHubAlertType, HeadingKind, HtmlBlockKind,
+    ListBulletKind, ListOrderedKindOptions, OrderedListStyle, SetextHeading, TaskState,
 };
 
 // ——————————————————————————————————————————————————————————————————————————
@@ -101,6 +101,8 @@ where
 
     /// Raw HTML block
     HtmlBlock {
+        #[cfg_attr(feature = "ast-serde", serde(default))]
+        kind: Option<HtmlBlockKind>,
         content: String,
         #[cfg_attr(feature = "ast-serde", serde(default))]
         user_data: T,
@@ -189,6 +191,10 @@ where
     /// List items in source order.
     pub items: Vec<ListItem<T>>,
 
+    /// `true` for tight lists, `false` for loose lists whose items are
+    /// separated by blank lines.
+    pub tight: bool,
+
     /// User-defined data associated with this list
     #[cfg_attr(feature = "ast-serde", serde(default))]
     pub user_data: T,
@@ -368,6 +374,12 @@ pub enum Inline<T = ()> {
         user_data: T,
     },
 
+    /// Soft line break
+    SoftBreak {
+        #[cfg_attr(feature = "ast-serde", serde(default))]
+        user_data: T,
+    },
+
     /// Inline code span
     Code {
         content: String,
@@ -419,6 +431,35 @@ pub enum Inline<T = ()> {
         user_data: T,
     },
 
+    /// Highlighted text (`==`)
+    Highlight {
+        content: Vec<Inline<T>>,
+        #[cfg_attr(feature = "ast-serde", serde(default))]
+        user_data: T,
+    },
+
+    /// Subscript (`~`)
+    Subscript {
+        content: Vec<Inline<T>>,
+        #[cfg_attr(feature = "ast-serde", serde(default))]
+        user_data: T,
+    },
+
+    /// Superscript (`^`)
+    Superscript {
+        content: Vec<Inline<T>>,
+        #[cfg_attr(feature = "ast-serde", serde(default))]
+        user_data: T,
+    },
+
+    /// Emphasis or strong emphasis with its source delimiter run
+    Delimited {
+        delimiter: String,
+        inner: Box<Inline<T>>,
+        #[cfg_attr(feature = "ast-serde", serde(default))]
+        user_data: T,
+    },
+
     /// Autolink (`<https://>` or `<mailto:…>`)
     Autolink {
         url: String,
@@ -533,6 +574,7 @@ impl<T: Default> Default for List<T> {
         Self {
             kind: ListKind::Bullet(ListBulletKind::Dash),
             items: Vec::new(),
+            tight: true,
             user_data: T::default(),
         }
     }
@@ -0,0 +1,374 @@
+//! JSON Schema for the serialized AST
+//!
+//! The schema mirrors the serde representation produced by the `ast-serde`
+//! feature: structs are JSON objects, unit enum variants are plain strings and
+//! all other enum variants are externally tagged (`{"Variant": value}`).
+
+use serde_json::{json, Value};
+
+/// Build a JSON Schema (draft 2020-12) describing a serialized [`Document`](crate::ast::Document).
+///
+/// Tools written in other languages can use the schema to validate ASTs they
+/// produce before handing them to this crate for deserialization.
+///
+/// # Examples
+///
+/// ```rust
+/// let schema = markdown_ppp::ast::json_schema();
+/// assert_eq!(schema["$ref"], "#/$defs/Document");
+/// assert!(schema["$defs"]["Block"].is_object());
+/// ```
+pub fn json_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "markdown-ppp Document",
+        "$ref": "#/$defs/Document",
+        "$defs": {
+            "Document": object(
+                json!({ "blocks": array_of(reference("Block")) }),
+                &["blocks"],
+            ),
+            "Block": one_of(vec![
+                newtype_variant("Paragraph", array_of(reference("Inline"))),
+                newtype_variant("Heading", reference("Heading")),
+                unit_variant("ThematicBreak"),
+                newtype_variant("BlockQuote", array_of(reference("Block"))),
+                newtype_variant("List", reference("List")),
+                newtype_variant("CodeBlock", reference("CodeBlock")),
+                newtype_variant("HtmlBlock", reference("HtmlBlock")),
+                newtype_variant("Definition", reference("LinkDefinition")),
+                newtype_variant("Table", reference("Table")),
+                newtype_variant("FootnoteDefinition", reference("FootnoteDefinition")),
+                newtype_variant("GitHubAlert", reference("GitHubAlert")),
+                newtype_variant("LatexBlock", string()),
+                unit_variant("Empty"),
+                newtype_variant("Container", reference("Container")),
+                newtype_variant("MacroBlock", string()),
+            ]),
+            "Container": object(
+                json!({
+                    "kind": string(),
+                    "params": array_of(json!({
+                        "type": "array",
+                        "prefixItems": [string(), string()],
+                        "items": false,
+                        "minItems": 2,
+                    })),
+                    "blocks": array_of(reference("Block")),
+                }),
+                &["kind", "params", "blocks"],
+            ),
+            "Heading": object(
+                json!({
+                    "kind": reference("HeadingKind"),
+                    "content": array_of(reference("Inline")),
+                }),
+                &["kind", "content"],
+            ),
+            "HeadingKind": one_of(vec![
+                newtype_variant("Atx", json!({ "type": "integer", "minimum": 0, "maximum": 255 })),
+                newtype_variant("Setext", reference("SetextHeading")),
+            ]),
+            "SetextHeading": string_enum(&["Level1", "Level2"]),
+            "List": object(
+                json!({
+                    "kind": reference("ListKind"),
+                    "items": array_of(reference("ListItem")),
+                    "tight": { "type": "boolean" },
+                }),
+                &["kind", "items"],
+            ),
+            "ListKind": one_of(vec![
+                newtype_variant("Ordered", reference("ListOrderedKindOptions")),
+                newtype_variant("Bullet", reference("ListBulletKind")),
+            ]),
+            "ListOrderedKindOptions": object(
+                json!({
+                    "start": unsigned(),
+                    "style": reference("OrderedListStyle"),
+                }),
+                &["start"],
+            ),
+            "OrderedListStyle": string_enum(&[
+                "Decimal",
+                "LowerAlpha",
+                "UpperAlpha",
+                "LowerRoman",
+                "UpperRoman",
+            ]),
+            "ListBulletKind": string_enum(&["Dash", "Star", "Plus"]),
+            "ListItem": object(
+                json!({
+                    "task": nullable(reference("TaskState")),
+                    "blocks": array_of(reference("Block")),
+                }),
+                &["blocks"],
+            ),
+            "TaskState": string_enum(&["Incomplete", "Complete"]),
+            "CodeBlock": object(
+                json!({
+                    "kind": reference("CodeBlockKind"),
+                    "literal": string(),
+                }),
+                &["kind", "literal"],
+            ),
+            "CodeBlockKind": one_of(vec![
+                unit_variant("Indented"),
+                newtype_variant(
+                    "Fenced",
+                    object(json!({ "info": nullable(string()) }), &[]),
+                ),
+            ]),
+            "HtmlBlock": object(
+                json!({
+                    "kind": nullable(reference("HtmlBlockKind")),
+                    "content": string(),
+                }),
+                &["content"],
+            ),
+            "HtmlBlockKind": string_enum(&[
+                "Raw",
+                "Comment",
+                "ProcessingInstruction",
+                "Declaration",
+                "CData",
+                "BlockTag",
+                "OtherTag",
+            ]),
+            "LinkDefinition": object(
+                json!({
+                    "label": array_of(reference("Inline")),
+                    "destination": string(),
+                    "title": nullable(string()),
+                }),
+                &["label", "destination"],
+            ),
+            "Table": object(
+                json!({
+                    "rows": array_of(array_of(reference("TableCell"))),
+                    "alignments": array_of(reference("Alignment")),
+                }),
+                &["rows", "alignments"],
+            ),
+            "TableCell": object(
+                json!({
+                    "content": array_of(reference("Inline")),
+                    "colspan": nullable(unsigned()),
+                    "rowspan": nullable(unsigned()),
+                    "removed_by_extended_table": { "type": "boolean" },
+                }),
+                &["content", "removed_by_extended_table"],
+            ),
+            "Alignment": string_enum(&["None", "Left", "Center", "Right"]),
+            "FootnoteDefinition": object(
+                json!({
+                    "label": string(),
+                    "blocks": array_of(reference("Block")),
+                }),
+                &["label", "blocks"],
+            ),
+            "GitHubAlert": object(
+                json!({
+                    "alert_type": reference("GitHubAlertType"),
+                    "blocks": array_of(reference("Block")),
+                }),
+                &["alert_type", "blocks"],
+            ),
+            "GitHubAlertType": one_of(vec![
+                string_enum(&["Note", "Tip", "Important", "Warning", "Caution"]),
+                newtype_variant("Custom", string()),
+            ]),
+            "Inline": one_of(vec![
+                newtype_variant("Text", string()),
+                unit_variant("LineBreak"),
+                unit_variant("SoftBreak"),
+                newtype_variant("Code", string()),
+                newtype_variant("Latex", string()),
+                newtype_variant("Html", string()),
+                newtype_variant("Link", reference("Link")),
+                newtype_variant("LinkReference", reference("LinkReference")),
+                newtype_variant("Image", reference("Image")),
+                newtype_variant("Emphasis", array_of(reference("Inline"))),
+                newtype_variant("Strong", array_of(reference("Inline"))),
+                newtype_variant("Strikethrough", array_of(reference("Inline"))),
+                newtype_variant("Highlight", array_of(reference("Inline"))),
+                newtype_variant("Subscript", array_of(reference("Inline"))),
+                newtype_variant("Superscript", array_of(reference("Inline"))),
+                newtype_variant("Delimited", reference("Delimited")),
+                newtype_variant("Autolink", string()),
+                newtype_variant("FootnoteReference", string()),
+                unit_variant("Empty"),
+            ]),
+            "Delimited": object(
+                json!({
+                    "delimiter": string(),
+                    "inner": reference("Inline"),
+                }),
+                &["delimiter", "inner"],
+            ),
+            "Link": object(
+                json!({
+                    "destination": string(),
+                    "title": nullable(string()),
+                    "children": array_of(reference("Inline")),
+                }),
+                &["destination", "children"],
+            ),
+            "Image": object(
+                json!({
+                    "destination": string(),
+                    "title": nullable(string()),
+                    "alt": string(),
+                    "attr": nullable(reference("ImageAttributes")),
+                }),
+                &["destination", "alt"],
+            ),
+            "ImageAttributes": object(
+                json!({
+                    "width": nullable(string()),
+                    "height": nullable(string()),
+                }),
+                &[],
+            ),
+            "LinkReference": object(
+                json!({
+                    "label": array_of(reference("Inline")),
+                    "text": array_of(reference("Inline")),
+                }),
+                &["label", "text"],
+            ),
+        },
+    })
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{name}") })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn unsigned() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+fn array_of(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+/// `Option<T>` fields serialize as `null` and may be omitted on input.
+fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [{ "type": "null" }, schema] })
+}
+
+fn object(properties: Value, required: &[&str]) -> Value {
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn one_of(variants: Vec<Value>) -> Value {
+    json!({ "oneOf": variants })
+}
+
+fn string_enum(variants: &[&str]) -> Value {
+    json!({ "type": "string", "enum": variants })
+}
+
+/// Unit variants are serialized as their name.
+fn unit_variant(name: &str) -> Value {
+    json!({ "const": name })
+}
+
+/// Newtype and struct variants are serialized as `{"Variant": value}`.
+fn newtype_variant(name: &str, schema: Value) -> Value {
+    object(json!({ name: schema }), &[name])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::json_schema;
+
+    fn validator() -> jsonschema::Validator {
+        jsonschema::validator_for(&json_schema()).unwrap()
+    }
+
+    #[cfg(feature = "parser")]
+    #[test]
+    fn schema_validates_serialized_document() {
+        let input = r#"# Title
+
+Some *emphasis*, **strong**, ~~strike~~, `code`, <https://example.com>
+and a [link](/url "title") with ![image](/img.png){width=10pt}.
+
+> [!WARNING]
+> Careful
+
+> quote
+
+- [x] done
+- todo
+
+3. three
+
+```rust
+fn main() {}
+```
+
+| a | b |
+|:--|--:|
+| 1 | 2 |
+
+[ref]: /target "Title"
+
+Reference [ref] and footnote[^1].
+
+[^1]: Footnote text.
+
+:::note {title="Hi"}
+inside
+:::
+
+---
+"#;
+        let doc =
+            crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+                .unwrap();
+        let value = serde_json::to_value(&doc).unwrap();
+
+        let validator = validator();
+        let errors: Vec<_> = validator
+            .iter_errors(&value)
+            .map(|e| format!("{e} at {}", e.instance_path))
+            .collect();
+        assert!(errors.is_empty(), "{errors:#?}");
+    }
+
+    #[test]
+    fn schema_rejects_unknown_variant() {
+        let value = serde_json::json!({
+            "blocks": [{ "Paragraph": [{ "Bogus": "text" }] }]
+        });
+        assert!(!validator().is_valid(&value));
+    }
+
+    #[test]
+    fn list_tight_defaults_to_true() {
+        let value = serde_json::json!({
+            "blocks": [{ "List": { "kind": { "Bullet": "Dash" }, "items": [] } }]
+        });
+        assert!(validator().is_valid(&value));
+        let doc: crate::ast::Document = serde_json::from_value(value).unwrap();
+        assert!(matches!(
+            &doc.blocks[..],
+            [crate::ast::Block::List(crate::ast::List {
+                tight: true,
+                ..
+            })]
+        ));
+    }
+}
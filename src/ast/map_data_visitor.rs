@@ -43,7 +43,12 @@ pub trait MapDataVisitor<T: Default, U: Default> {
             generic::Block::CodeBlock(code_block) => {
                 generic::Block::CodeBlock(self.visit_code_block(code_block))
             }
-            generic::Block::HtmlBlock { content, user_data } => generic::Block::HtmlBlock {
+            generic::Block::HtmlBlock {
+                kind,
+                content,
+                user_data,
+            } => generic::Block::HtmlBlock {
+                kind,
                 content,
                 user_data: self.map_data(user_data),
             },
@@ -71,10 +76,7 @@ pub trait MapDataVisitor<T: Default, U: Default> {
     }
 
     /// Transform a container
-    fn visit_container(
-        &mut self,
-        container: generic::Container<T>,
-    ) -> generic::Container<U> {
+    fn visit_container(&mut self, container: generic::Container<T>) -> generic::Container<U> {
         generic::Container {
             kind: container.kind,
             params: container.params,
@@ -97,6 +99,9 @@ pub trait MapDataVisitor<T: Default, U: Default> {
             generic::Inline::LineBreak { user_data } => generic::Inline::LineBreak {
                 user_data: self.map_data(user_data),
             },
+            generic::Inline::SoftBreak { user_data } => generic::Inline::SoftBreak {
+                user_data: self.map_data(user_data),
+            },
             generic::Inline::Code { content, user_data } => generic::Inline::Code {
                 content,
                 user_data: self.map_data(user_data),
@@ -124,6 +129,27 @@ pub trait MapDataVisitor<T: Default, U: Default> {
                     user_data: self.map_data(user_data),
                 }
             }
+            generic::Inline::Highlight { content, user_data } => generic::Inline::Highlight {
+                content: content.into_iter().map(|i| self.visit_inline(i)).collect(),
+                user_data: self.map_data(user_data),
+            },
+            generic::Inline::Subscript { content, user_data } => generic::Inline::Subscript {
+                content: content.into_iter().map(|i| self.visit_inline(i)).collect(),
+                user_data: self.map_data(user_data),
+            },
+            generic::Inline::Superscript { content, user_data } => generic::Inline::Superscript {
+                content: content.into_iter().map(|i| self.visit_inline(i)).collect(),
+                user_data: self.map_data(user_data),
+            },
+            generic::Inline::Delimited {
+                delimiter,
+                inner,
+                user_data,
+            } => generic::Inline::Delimited {
+                delimiter,
+                inner: Box::new(self.visit_inline(*inner)),
+                user_data: self.map_data(user_data),
+            },
             generic::Inline::Autolink { url, user_data } => generic::Inline::Autolink {
                 url,
                 user_data: self.map_data(user_data),
@@ -166,6 +192,7 @@ pub trait MapDataVisitor<T: Default, U: Default> {
                 .into_iter()
                 .map(|i| self.visit_list_item(i))
                 .collect(),
+            tight: list.tight,
             user_data: self.map_data(list.user_data),
         }
     }
@@ -424,6 +451,7 @@ mod tests {
                         }],
                         user_data: 7u32,
                     }],
+                    tight: true,
                     user_data: 8u32,
                 }),
             ],
@@ -28,9 +28,38 @@ pub mod generic;
 /// Visitor-based MapData implementation to avoid recursion limits
 pub mod map_data_visitor;
 
+mod borrowed;
+pub use borrowed::{BorrowedBlock, BorrowedDocument, BorrowedInline, BorrowedListItem};
+mod diff;
+pub use diff::{diff, DiffOp};
+
+mod dump;
+pub use dump::dump_tree;
+
 mod github_alerts;
 pub use github_alerts::{GitHubAlert, GitHubAlertType};
 
+mod outline;
+pub use outline::OutlineNode;
+
+mod slug;
+#[cfg(any(feature = "typst-printer", feature = "ast-transform"))]
+pub(crate) use slug::inline_text;
+pub(crate) use slug::label_key;
+pub use slug::{slugify, SlugOptions};
+
+#[cfg(feature = "ast-serde")]
+mod json_schema;
+#[cfg(feature = "ast-serde")]
+pub use json_schema::json_schema;
+
+mod validate;
+#[cfg(feature = "parser")]
+pub(crate) use validate::DefinitionCollector;
+pub use validate::{validate, ValidationIssue, ValidationIssueKind};
+
+pub(crate) mod visitor;
+
 // ——————————————————————————————————————————————————————————————————————————
 // Document root
 // ——————————————————————————————————————————————————————————————————————————
@@ -70,7 +99,7 @@ pub enum Block {
     CodeBlock(CodeBlock),
 
     /// Raw HTML block
-    HtmlBlock(String),
+    HtmlBlock(HtmlBlock),
 
     /// Link reference definition.  Preserved for round‑tripping.
     Definition(LinkDefinition),
@@ -111,6 +140,50 @@ pub struct Container {
     pub blocks: Vec<Block>,
 }
 
+/// Raw HTML block.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ast-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HtmlBlock {
+    /// CommonMark kind of the block, set when the parser classifies HTML
+    /// blocks.
+    #[cfg_attr(feature = "ast-serde", serde(default))]
+    pub kind: Option<HtmlBlockKind>,
+
+    /// Raw HTML source of the block.
+    pub content: String,
+}
+
+/// The seven kinds of HTML blocks defined by CommonMark
+///
+/// The kind is decided by the block's first line and determines how the
+/// block ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ast-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HtmlBlockKind {
+    /// Type 1: `<script>`, `<pre>` or `<style>`, up to the matching end tag.
+    Raw,
+
+    /// Type 2: an HTML comment `<!-- ... -->`.
+    Comment,
+
+    /// Type 3: a processing instruction `<? ... ?>`.
+    ProcessingInstruction,
+
+    /// Type 4: a declaration such as `<!DOCTYPE html>`.
+    Declaration,
+
+    /// Type 5: a `<![CDATA[ ... ]]>` section.
+    CData,
+
+    /// Type 6: a known block-level tag such as `<div>` or `<table>`, up to a
+    /// blank line.
+    BlockTag,
+
+    /// Type 7: any other complete open or closing tag on a line of its own,
+    /// up to a blank line.
+    OtherTag,
+}
+
 /// Heading with level 1–6 and inline content.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "ast-serde", derive(serde::Serialize, serde::Deserialize))]
@@ -144,6 +217,29 @@ pub enum SetextHeading {
     Level2,
 }
 
+impl Heading {
+    /// Level of the heading, 1 for `#` or `===` headings and so on
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::{Heading, HeadingKind, SetextHeading};
+    ///
+    /// let heading = Heading {
+    ///     kind: HeadingKind::Setext(SetextHeading::Level2),
+    ///     content: vec![],
+    /// };
+    /// assert_eq!(heading.level(), 2);
+    /// ```
+    pub fn level(&self) -> u8 {
+        match self.kind {
+            HeadingKind::Atx(level) => level,
+            HeadingKind::Setext(SetextHeading::Level1) => 1,
+            HeadingKind::Setext(SetextHeading::Level2) => 2,
+        }
+    }
+}
+
 // ——————————————————————————————————————————————————————————————————————————
 // Lists
 // ——————————————————————————————————————————————————————————————————————————
@@ -158,6 +254,16 @@ pub struct List {
 
     /// List items in source order.
     pub items: Vec<ListItem>,
+
+    /// `true` for tight lists, `false` for loose lists whose items are
+    /// separated by blank lines.
+    #[cfg_attr(feature = "ast-serde", serde(default = "default_tight"))]
+    pub tight: bool,
+}
+
+#[cfg(feature = "ast-serde")]
+fn default_tight() -> bool {
+    true
 }
 
 /// Specifies *what kind* of list we have.
@@ -177,6 +283,104 @@ pub enum ListKind {
 pub struct ListOrderedKindOptions {
     /// Start index (1, 2, …) for ordered lists.
     pub start: u64,
+
+    /// How item numbers are written.
+    #[cfg_attr(feature = "ast-serde", serde(default))]
+    pub style: OrderedListStyle,
+}
+
+/// Numbering style of an ordered list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "ast-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OrderedListStyle {
+    /// `1.`, `2.`, `3.`
+    #[default]
+    Decimal,
+
+    /// `a.`, `b.`, `c.`
+    LowerAlpha,
+
+    /// `A.`, `B.`, `C.`
+    UpperAlpha,
+
+    /// `i.`, `ii.`, `iii.`
+    LowerRoman,
+
+    /// `I.`, `II.`, `III.`
+    UpperRoman,
+}
+
+impl OrderedListStyle {
+    /// Write `number` in this style, without the trailing delimiter
+    ///
+    /// Alphabetic numbering continues with `aa`, `ab`, … after `z`; numbers
+    /// that have no letter or roman form (0, or past 3999 for roman
+    /// numerals) are written in decimal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::OrderedListStyle;
+    ///
+    /// assert_eq!(OrderedListStyle::LowerAlpha.format(28), "ab");
+    /// assert_eq!(OrderedListStyle::UpperRoman.format(14), "XIV");
+    /// ```
+    pub fn format(self, number: u64) -> String {
+        match self {
+            OrderedListStyle::Decimal => number.to_string(),
+            OrderedListStyle::LowerAlpha => to_alpha(number).unwrap_or(number.to_string()),
+            OrderedListStyle::UpperAlpha => to_alpha(number)
+                .map(|s| s.to_ascii_uppercase())
+                .unwrap_or(number.to_string()),
+            OrderedListStyle::LowerRoman => to_roman(number).unwrap_or(number.to_string()),
+            OrderedListStyle::UpperRoman => to_roman(number)
+                .map(|s| s.to_ascii_uppercase())
+                .unwrap_or(number.to_string()),
+        }
+    }
+}
+
+fn to_alpha(mut number: u64) -> Option<String> {
+    if number == 0 {
+        return None;
+    }
+    let mut letters = Vec::new();
+    while number > 0 {
+        number -= 1;
+        letters.push(b'a' + (number % 26) as u8);
+        number /= 26;
+    }
+    letters.reverse();
+    String::from_utf8(letters).ok()
+}
+
+fn to_roman(mut number: u64) -> Option<String> {
+    const NUMERALS: [(u64, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    if number == 0 || number > 3999 {
+        return None;
+    }
+    let mut out = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            out.push_str(numeral);
+            number -= value;
+        }
+    }
+    Some(out)
 }
 
 /// Concrete bullet character used for a bullet list.
@@ -227,6 +431,12 @@ pub struct CodeBlock {
     pub kind: CodeBlockKind,
 
     /// Literal text inside the code block **without** final newline trimming.
+    ///
+    /// The parser keeps the content lines verbatim, including trailing
+    /// whitespace, and joins them with `\n`. Only the indentation required
+    /// by the block syntax is removed, and there is no newline after the last
+    /// line. Fenced blocks also keep trailing blank lines; an indented block
+    /// keeps blank lines between code lines but ends at its last code line.
     pub literal: String,
 }
 
@@ -338,6 +548,12 @@ pub enum Inline {
     /// Hard line break
     LineBreak,
 
+    /// Soft line break, a plain newline within a paragraph
+    ///
+    /// Only produced when the parser is configured to preserve soft breaks;
+    /// otherwise the newline stays part of the surrounding text.
+    SoftBreak,
+
     /// Inline code span
     Code(String),
 
@@ -362,6 +578,19 @@ pub enum Inline {
     Strong(Vec<Inline>),
     /// Strikethrough (`~~`)
     Strikethrough(Vec<Inline>),
+    /// Highlighted text (`==`), parsed only when enabled with
+    /// `MarkdownParserConfig::with_inline_highlight_behavior`
+    Highlight(Vec<Inline>),
+    /// Subscript (`~`), parsed only when enabled with
+    /// `MarkdownParserConfig::with_inline_subscript_behavior`
+    Subscript(Vec<Inline>),
+    /// Superscript (`^`), parsed only when enabled with
+    /// `MarkdownParserConfig::with_inline_superscript_behavior`
+    Superscript(Vec<Inline>),
+    /// Emphasis or strong emphasis together with the delimiters it was
+    /// written with, produced only when enabled with
+    /// `MarkdownParserConfig::with_preserve_emphasis_delimiters`
+    Delimited(Delimited),
 
     /// Autolink (`<https://>` or `<mailto:…>`)
     Autolink(String),
@@ -415,6 +644,19 @@ pub struct Image {
     pub attr: Option<ImageAttributes>,
 }
 
+/// Emphasis node with its source delimiter run (e.g., `_x_` or `__x__`).
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "ast-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Delimited {
+    /// Opening delimiter run, such as `_`, `**` or `___`. The closing run is
+    /// the same.
+    pub delimiter: String,
+
+    /// The delimited node: `Emphasis` for a run of one, `Strong` for two, and
+    /// `Strong` around `Emphasis` for three.
+    pub inner: Box<Inline>,
+}
+
 /// Reference-style link (e.g., `[text][label]` or `[label][]`).
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
 #[cfg_attr(feature = "ast-serde", derive(serde::Serialize, serde::Deserialize))]
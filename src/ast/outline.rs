@@ -0,0 +1,160 @@
+//! Heading outline of a document
+//!
+//! [`Document::outline`] nests the top-level headings of a document into a
+//! tree, e.g. for rendering collapsible navigation.
+
+use super::{Block, Document, Inline};
+
+/// A node of a document outline
+///
+/// The root returned by [`Document::outline`] has level 0 and no content;
+/// every other node is a heading.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineNode {
+    /// Heading level (1–6), or 0 for the root.
+    pub level: u8,
+
+    /// Heading content.
+    pub content: Vec<Inline>,
+
+    /// Deeper headings that follow this one up to the next heading of the
+    /// same or a higher level.
+    pub children: Vec<OutlineNode>,
+}
+
+impl Document {
+    /// Build the heading outline of the document
+    ///
+    /// Only top-level headings are considered. A heading becomes a child of
+    /// the nearest preceding heading with a lower level, so skipped levels
+    /// (an H3 directly after an H1) nest under that heading.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    ///
+    /// let heading = |level, text: &str| {
+    ///     Block::Heading(Heading {
+    ///         kind: HeadingKind::Atx(level),
+    ///         content: vec![Inline::Text(text.to_string())],
+    ///     })
+    /// };
+    /// let doc = Document {
+    ///     blocks: vec![heading(1, "Guide"), heading(2, "Install"), heading(2, "Usage")],
+    /// };
+    /// let outline = doc.outline();
+    /// assert_eq!(outline.children.len(), 1);
+    /// assert_eq!(outline.children[0].children.len(), 2);
+    /// ```
+    pub fn outline(&self) -> OutlineNode {
+        // Path from the root to the most recent heading
+        let mut stack = vec![OutlineNode {
+            level: 0,
+            content: Vec::new(),
+            children: Vec::new(),
+        }];
+
+        for block in &self.blocks {
+            let Block::Heading(heading) = block else {
+                continue;
+            };
+            let level = heading.level();
+            while stack.last().is_some_and(|node| node.level >= level) {
+                close(&mut stack);
+            }
+            stack.push(OutlineNode {
+                level,
+                content: heading.content.clone(),
+                children: Vec::new(),
+            });
+        }
+
+        while stack.len() > 1 {
+            close(&mut stack);
+        }
+        stack.pop().expect("root is never popped")
+    }
+}
+
+/// Move the innermost open node into its parent's children
+fn close(stack: &mut Vec<OutlineNode>) {
+    let node = stack.pop().expect("stack is not empty");
+    stack
+        .last_mut()
+        .expect("root stays on the stack")
+        .children
+        .push(node);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Heading, HeadingKind, SetextHeading};
+
+    fn heading(kind: HeadingKind, text: &str) -> Block {
+        Block::Heading(Heading {
+            kind,
+            content: vec![Inline::Text(text.to_string())],
+        })
+    }
+
+    /// Render an outline as `title(children...)` for compact assertions
+    fn shape(node: &OutlineNode) -> String {
+        let title = match node.content.as_slice() {
+            [Inline::Text(text)] => text.clone(),
+            _ => String::new(),
+        };
+        if node.children.is_empty() {
+            return title;
+        }
+        let children: Vec<_> = node.children.iter().map(shape).collect();
+        format!("{title}({})", children.join(" "))
+    }
+
+    #[test]
+    fn nests_realistic_hierarchy() {
+        let doc = Document {
+            blocks: vec![
+                Block::Paragraph(vec![Inline::Text("Intro".to_string())]),
+                heading(HeadingKind::Setext(SetextHeading::Level1), "Guide"),
+                heading(HeadingKind::Atx(2), "Install"),
+                heading(HeadingKind::Atx(3), "Linux"),
+                heading(HeadingKind::Atx(3), "macOS"),
+                heading(HeadingKind::Setext(SetextHeading::Level2), "Usage"),
+                Block::BlockQuote(vec![heading(HeadingKind::Atx(2), "Quoted")]),
+                heading(HeadingKind::Atx(1), "Reference"),
+                heading(HeadingKind::Atx(2), "API"),
+            ],
+        };
+
+        let outline = doc.outline();
+        assert_eq!(outline.level, 0);
+        assert_eq!(
+            shape(&outline),
+            "(Guide(Install(Linux macOS) Usage) Reference(API))"
+        );
+        assert_eq!(outline.children[0].children[0].level, 2);
+    }
+
+    #[test]
+    fn skipped_levels() {
+        let doc = Document {
+            blocks: vec![
+                heading(HeadingKind::Atx(3), "Preface"),
+                heading(HeadingKind::Atx(1), "One"),
+                heading(HeadingKind::Atx(3), "Deep"),
+                heading(HeadingKind::Atx(2), "Two"),
+                heading(HeadingKind::Atx(4), "Deeper"),
+            ],
+        };
+
+        assert_eq!(shape(&doc.outline()), "(Preface One(Deep Two(Deeper)))");
+    }
+
+    #[test]
+    fn empty_document() {
+        let outline = Document { blocks: vec![] }.outline();
+        assert!(outline.children.is_empty());
+    }
+}
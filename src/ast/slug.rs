@@ -0,0 +1,169 @@
+//! Heading slugs for anchors
+//!
+//! [`slugify`] turns heading text into an anchor id following GitHub's rules:
+//! lowercase, spaces become `-`, and everything except letters, digits, `-`
+//! and `_` is removed. Non-ASCII letters are kept unless transliteration is
+//! requested.
+
+/// Options for [`slugify`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SlugOptions {
+    /// Replace accented Latin letters with their ASCII base letters
+    /// (`"Café"` → `"cafe"`). Letters without an ASCII equivalent, such as
+    /// CJK characters, are kept as they are.
+    pub transliterate: bool,
+}
+
+impl SlugOptions {
+    /// Enable transliteration of accented letters to ASCII.
+    pub fn with_transliterate(self, transliterate: bool) -> Self {
+        Self { transliterate }
+    }
+}
+
+/// Build an anchor slug from heading text.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::ast::{slugify, SlugOptions};
+///
+/// assert_eq!(slugify("Hello, World!", SlugOptions::default()), "hello-world");
+/// assert_eq!(slugify("Café Menu", SlugOptions::default()), "café-menu");
+/// assert_eq!(
+///     slugify("Café Menu", SlugOptions::default().with_transliterate(true)),
+///     "cafe-menu"
+/// );
+/// ```
+pub fn slugify(text: &str, options: SlugOptions) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.trim().chars().flat_map(char::to_lowercase) {
+        if c == ' ' {
+            slug.push('-');
+        } else if c == '-' || c == '_' || c.is_alphanumeric() {
+            match transliterate(c).filter(|_| options.transliterate) {
+                Some(ascii) => slug.push_str(ascii),
+                None => slug.push(c),
+            }
+        }
+    }
+    slug
+}
+
+/// Plain text of inline content, as used for heading slugs.
+///
+/// Formatting is dropped, code spans and autolinks contribute their text and
+/// images their alt text.
+pub(crate) fn inline_text(inlines: &[super::Inline]) -> String {
+    use super::Inline;
+
+    fn collect(inlines: &[Inline], out: &mut String) {
+        for inline in inlines {
+            match inline {
+                Inline::Text(text) | Inline::Code(text) | Inline::Autolink(text) => {
+                    out.push_str(text)
+                }
+                Inline::Emphasis(children)
+                | Inline::Strong(children)
+                | Inline::Strikethrough(children)
+                | Inline::Highlight(children)
+                | Inline::Subscript(children)
+                | Inline::Superscript(children) => collect(children, out),
+                Inline::Delimited(delimited) => {
+                    collect(std::slice::from_ref(delimited.inner.as_ref()), out)
+                }
+                Inline::Link(link) => collect(&link.children, out),
+                Inline::LinkReference(link_ref) => collect(&link_ref.text, out),
+                Inline::Image(image) => out.push_str(&image.alt),
+                Inline::LineBreak | Inline::SoftBreak => out.push(' '),
+                _ => {}
+            }
+        }
+    }
+
+    let mut text = String::new();
+    collect(inlines, &mut text);
+    text
+}
+
+/// Normalize a link label for matching: plain text, case-folded, whitespace collapsed.
+pub(crate) fn label_key(label: &[super::Inline]) -> String {
+    inline_text(label)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// ASCII replacement for lowercase accented Latin letters.
+fn transliterate(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accented_characters() {
+        assert_eq!(
+            slugify("Crème Brûlée à la carte", SlugOptions::default()),
+            "crème-brûlée-à-la-carte"
+        );
+        assert_eq!(
+            slugify(
+                "Crème Brûlée à la carte",
+                SlugOptions::default().with_transliterate(true)
+            ),
+            "creme-brulee-a-la-carte"
+        );
+        assert_eq!(
+            slugify("Straße", SlugOptions::default().with_transliterate(true)),
+            "strasse"
+        );
+    }
+
+    #[test]
+    fn cjk_characters() {
+        let options = SlugOptions::default().with_transliterate(true);
+        assert_eq!(slugify("日本語 Guide", options), "日本語-guide");
+    }
+
+    #[test]
+    fn emoji_and_punctuation() {
+        assert_eq!(
+            slugify("🚀 Launch: v2.0 (beta)", SlugOptions::default()),
+            "-launch-v20-beta"
+        );
+        assert_eq!(
+            slugify("snake_case and-kebab", SlugOptions::default()),
+            "snake_case-and-kebab"
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! Structural validation of documents
+//!
+//! [`validate`] walks a document and reports problems that parsers never
+//! produce but hand-built or transformed ASTs may contain, such as ragged
+//! tables or references without a matching definition.
+
+use super::visitor::{VisitWith, Visitor};
+use super::*;
+use std::collections::HashSet;
+
+/// Category of a [`ValidationIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationIssueKind {
+    /// A table row has a different number of cells than the table has alignments.
+    RaggedTable,
+
+    /// A footnote reference has no matching footnote definition.
+    UndefinedFootnote,
+
+    /// A reference link has no matching link definition.
+    UndefinedLinkReference,
+
+    /// A heading has no content.
+    EmptyHeading,
+}
+
+/// A single problem found by [`validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// Category of the problem.
+    pub kind: ValidationIssueKind,
+
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Check a document for structural inconsistencies.
+///
+/// Returns an empty vector if no problems were found. Issues are reported in
+/// document order.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::ast::*;
+///
+/// let doc = Document {
+///     blocks: vec![Block::Paragraph(vec![Inline::FootnoteReference("1".to_string())])],
+/// };
+/// let issues = validate(&doc);
+/// assert_eq!(issues.len(), 1);
+/// assert_eq!(issues[0].kind, ValidationIssueKind::UndefinedFootnote);
+/// ```
+pub fn validate(doc: &Document) -> Vec<ValidationIssue> {
+    let mut definitions = DefinitionCollector::default();
+    doc.visit_with(&mut definitions);
+
+    let mut validator = Validator {
+        definitions,
+        issues: Vec::new(),
+    };
+    doc.visit_with(&mut validator);
+    validator.issues
+}
+
+/// Footnote labels and normalized link labels defined in a document
+#[derive(Default)]
+pub(crate) struct DefinitionCollector {
+    pub(crate) footnotes: HashSet<String>,
+    pub(crate) links: HashSet<String>,
+}
+
+impl DefinitionCollector {
+    /// Issue for `inline` if it refers to a label that is not defined,
+    /// together with that label
+    pub(crate) fn undefined_reference(&self, inline: &Inline) -> Option<(ValidationIssue, String)> {
+        match inline {
+            Inline::FootnoteReference(label) if !self.footnotes.contains(label) => Some((
+                ValidationIssue {
+                    kind: ValidationIssueKind::UndefinedFootnote,
+                    message: format!("footnote [^{label}] is not defined"),
+                },
+                label.clone(),
+            )),
+            Inline::LinkReference(link_ref) => {
+                let key = label_key(&link_ref.label);
+                (!self.links.contains(&key)).then(|| {
+                    (
+                        ValidationIssue {
+                            kind: ValidationIssueKind::UndefinedLinkReference,
+                            message: format!("link reference [{key}] is not defined"),
+                        },
+                        key,
+                    )
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Visitor for DefinitionCollector {
+    fn visit_block(&mut self, block: &Block) {
+        match block {
+            Block::FootnoteDefinition(footnote) => {
+                self.footnotes.insert(footnote.label.clone());
+            }
+            Block::Definition(definition) => {
+                self.links.insert(label_key(&definition.label));
+            }
+            _ => {}
+        }
+        self.walk_block(block);
+    }
+}
+
+struct Validator {
+    definitions: DefinitionCollector,
+    issues: Vec<ValidationIssue>,
+}
+
+impl Validator {
+    fn report(&mut self, kind: ValidationIssueKind, message: String) {
+        self.issues.push(ValidationIssue { kind, message });
+    }
+}
+
+impl Visitor for Validator {
+    fn visit_block(&mut self, block: &Block) {
+        match block {
+            Block::Table(table) => {
+                let columns = table.alignments.len();
+                for (index, row) in table.rows.iter().enumerate() {
+                    if row.len() != columns {
+                        self.report(
+                            ValidationIssueKind::RaggedTable,
+                            format!(
+                                "table row {index} has {} cells, expected {columns}",
+                                row.len()
+                            ),
+                        );
+                    }
+                }
+            }
+            Block::Heading(heading) if heading.content.is_empty() => {
+                self.report(
+                    ValidationIssueKind::EmptyHeading,
+                    "heading has no content".to_string(),
+                );
+            }
+            _ => {}
+        }
+        self.walk_block(block);
+    }
+
+    fn visit_inline(&mut self, inline: &Inline) {
+        if let Some((issue, _)) = self.definitions.undefined_reference(inline) {
+            self.issues.push(issue);
+        }
+        self.walk_inline(inline);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(text: &str) -> TableCell {
+        TableCell {
+            content: vec![Inline::Text(text.to_string())],
+            colspan: None,
+            rowspan: None,
+            removed_by_extended_table: false,
+        }
+    }
+
+    #[test]
+    fn ragged_table() {
+        let doc = Document {
+            blocks: vec![Block::Table(Table {
+                rows: vec![vec![cell("a"), cell("b")], vec![cell("c")]],
+                alignments: vec![Alignment::None, Alignment::None],
+            })],
+        };
+
+        assert_eq!(
+            validate(&doc),
+            vec![ValidationIssue {
+                kind: ValidationIssueKind::RaggedTable,
+                message: "table row 1 has 1 cells, expected 2".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn dangling_references() {
+        let doc = Document {
+            blocks: vec![
+                Block::Paragraph(vec![
+                    Inline::FootnoteReference("defined".to_string()),
+                    Inline::FootnoteReference("missing".to_string()),
+                    Inline::LinkReference(LinkReference {
+                        label: vec![Inline::Text("Docs".to_string())],
+                        text: vec![Inline::Text("docs".to_string())],
+                    }),
+                    Inline::LinkReference(LinkReference {
+                        label: vec![Inline::Text("nowhere".to_string())],
+                        text: vec![Inline::Text("nowhere".to_string())],
+                    }),
+                ]),
+                Block::FootnoteDefinition(FootnoteDefinition {
+                    label: "defined".to_string(),
+                    blocks: vec![],
+                }),
+                Block::Definition(LinkDefinition {
+                    label: vec![Inline::Text("docs".to_string())],
+                    destination: "/docs".to_string(),
+                    title: None,
+                }),
+                Block::Heading(Heading {
+                    kind: HeadingKind::Atx(2),
+                    content: vec![],
+                }),
+            ],
+        };
+
+        let kinds: Vec<_> = validate(&doc).into_iter().map(|issue| issue.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ValidationIssueKind::UndefinedFootnote,
+                ValidationIssueKind::UndefinedLinkReference,
+                ValidationIssueKind::EmptyHeading,
+            ]
+        );
+    }
+
+    #[cfg(feature = "parser")]
+    #[test]
+    fn parsed_document_is_valid() {
+        let doc = crate::parser::parse_markdown(
+            crate::parser::MarkdownParserState::default(),
+            "# Title\n\nSee [docs] and[^1].\n\n| a | b |\n|---|---|\n| 1 |\n\n[docs]: /docs\n\n[^1]: Note.\n",
+        )
+        .unwrap();
+        assert_eq!(validate(&doc), vec![]);
+    }
+}
@@ -21,12 +21,22 @@
 //! ```
 
 use super::transformer::Transformer;
+use super::visitor::{VisitWith, Visitor};
 use crate::ast::*;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+static BARE_URL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(?:https?://|www\.)[^\s<>]+").unwrap());
 
 /// High-level transformation methods for common use cases
 pub trait Transform {
     /// Transform all text elements with a function
     ///
+    /// Only [`Inline::Text`] is passed to `f`; code spans, code blocks, raw
+    /// HTML and LaTeX keep their literal content.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -99,254 +109,2451 @@ pub trait Transform {
     where
         F: Fn(String) -> String;
 
+    /// Give fenced code blocks without an info string a default language
+    ///
+    /// Shorthand for
+    /// [`default_code_language_with`](Transform::default_code_language_with)
+    /// that leaves indented code blocks alone.
+    fn default_code_language(self, lang: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.default_code_language_with(lang, false)
+    }
+
+    /// Give code blocks without a language the info string `lang`
+    ///
+    /// Fenced code blocks whose info string is missing or blank get `lang`;
+    /// others are left as they are. With `fence_indented`, indented code
+    /// blocks become fenced blocks with `lang` too.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::CodeBlock(CodeBlock {
+    ///         kind: CodeBlockKind::Indented,
+    ///         literal: "echo hi".to_string(),
+    ///     })],
+    /// };
+    /// let doc = doc.default_code_language_with("text", true);
+    /// assert_eq!(
+    ///     doc.blocks[0],
+    ///     Block::CodeBlock(CodeBlock {
+    ///         kind: CodeBlockKind::Fenced { info: Some("text".to_string()) },
+    ///         literal: "echo hi".to_string(),
+    ///     })
+    /// );
+    /// ```
+    fn default_code_language_with(self, lang: &str, fence_indented: bool) -> Self;
+
     /// Transform all HTML content with a function
     fn transform_html<F>(self, f: F) -> Self
     where
         F: Fn(String) -> String;
 
-    /// Apply a custom transformer
-    fn transform_with<T: Transformer>(self, transformer: T) -> Self;
+    /// Apply a custom transformer
+    fn transform_with<T: Transformer>(self, transformer: T) -> Self;
+
+    /// Transform conditionally based on a document predicate
+    fn transform_if_doc<P, F>(self, predicate: P, transform: F) -> Self
+    where
+        P: Fn(&Self) -> bool,
+        F: FnOnce(Self) -> Self,
+        Self: Sized;
+
+    /// Shorten the document to an excerpt
+    ///
+    /// If `more_marker` is set and a top-level HTML block with that content is
+    /// found, everything from the marker onwards is dropped, regardless of
+    /// `max_blocks`. Otherwise the document is truncated to the first
+    /// `max_blocks` top-level blocks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::{ExcerptOptions, Transform};
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![
+    ///         Block::Paragraph(vec![Inline::Text("intro".to_string())]),
+    ///         Block::HtmlBlock(HtmlBlock {
+    ///             kind: None,
+    ///             content: "<!-- more -->".to_string(),
+    ///         }),
+    ///         Block::Paragraph(vec![Inline::Text("rest".to_string())]),
+    ///     ],
+    /// };
+    /// let result = doc.excerpt(ExcerptOptions {
+    ///     max_blocks: None,
+    ///     more_marker: Some("<!-- more -->".to_string()),
+    /// });
+    /// assert_eq!(result.blocks.len(), 1);
+    /// ```
+    fn excerpt(self, opts: ExcerptOptions) -> Self;
+
+    /// Resolve relative link, image and link definition URLs against a base URL
+    ///
+    /// Absolute URLs and fragment-only links (`#section`) are left untouched.
+    /// If `base` is not a valid absolute URL the document is returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![Inline::Link(Link {
+    ///         destination: "../guide?page=2#intro".to_string(),
+    ///         title: None,
+    ///         children: vec![Inline::Text("guide".to_string())],
+    ///     })])],
+    /// };
+    /// let result = doc.rebase_urls("https://example.com/docs/api/");
+    /// if let Block::Paragraph(inlines) = &result.blocks[0] {
+    ///     if let Inline::Link(link) = &inlines[0] {
+    ///         assert_eq!(link.destination, "https://example.com/docs/guide?page=2#intro");
+    ///     }
+    /// }
+    /// ```
+    fn rebase_urls(self, base: &str) -> Self;
+
+    /// Replace straight quotes, dashes and ellipses with typographic ones
+    ///
+    /// Only [`Inline::Text`] is touched, so code spans and code blocks keep
+    /// their literal content. `--` becomes an en dash, `---` an em dash and
+    /// `...` an ellipsis. Quote state is tracked across the inlines of a block,
+    /// and apostrophes inside words become right single quotes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![Inline::Text(
+    ///         "\"don't\" -- wait...".to_string(),
+    ///     )])],
+    /// };
+    /// let result = doc.smart_punctuation();
+    /// assert_eq!(
+    ///     result.blocks[0],
+    ///     Block::Paragraph(vec![Inline::Text("\u{201C}don\u{2019}t\u{201D} \u{2013} wait\u{2026}".to_string())])
+    /// );
+    /// ```
+    fn smart_punctuation(self) -> Self;
+
+    /// Remove duplicate and unreferenced link definitions
+    ///
+    /// Labels are compared case-insensitively with whitespace collapsed, as in
+    /// CommonMark. Of several definitions with the same label the first one is
+    /// kept, and definitions not used by any reference link are dropped. Use
+    /// [`Transform::try_dedupe_definitions`] to reject duplicates that point to
+    /// different destinations instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let definition = |label: &str, destination: &str| {
+    ///     Block::Definition(LinkDefinition {
+    ///         label: vec![Inline::Text(label.to_string())],
+    ///         destination: destination.to_string(),
+    ///         title: None,
+    ///     })
+    /// };
+    /// let doc = Document {
+    ///     blocks: vec![
+    ///         Block::Paragraph(vec![Inline::LinkReference(LinkReference {
+    ///             label: vec![Inline::Text("docs".to_string())],
+    ///             text: vec![Inline::Text("docs".to_string())],
+    ///         })]),
+    ///         definition("docs", "/docs"),
+    ///         definition("Docs", "/docs"),
+    ///         definition("unused", "/unused"),
+    ///     ],
+    /// };
+    /// let result = doc.dedupe_definitions();
+    /// assert_eq!(result.blocks.len(), 2);
+    /// ```
+    fn dedupe_definitions(self) -> Self;
+
+    /// Like [`Transform::dedupe_definitions`], but fail if two definitions
+    /// share a label and have different destinations
+    fn try_dedupe_definitions(self) -> Result<Self, DefinitionConflict>
+    where
+        Self: Sized;
+
+    /// Remove backslash escapes from text
+    ///
+    /// A backslash before ASCII punctuation (`\*`, `\[`, `\\`, ...) is
+    /// dropped, as a CommonMark parser would. Other backslashes are kept, and
+    /// code spans and code blocks are left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![Inline::Text(r"\*not emphasis\* C:\dir".to_string())])],
+    /// };
+    /// let doc = doc.normalize_backslash_escapes();
+    /// assert_eq!(
+    ///     doc.blocks[0],
+    ///     Block::Paragraph(vec![Inline::Text(r"*not emphasis* C:\dir".to_string())])
+    /// );
+    /// ```
+    fn normalize_backslash_escapes(self) -> Self
+    where
+        Self: Sized,
+    {
+        self.transform_text(|text| unescape_backslashes(&text))
+    }
+
+    /// Backslash-escape characters in text that Markdown would interpret
+    ///
+    /// The inverse of [`normalize_backslash_escapes`](Transform::normalize_backslash_escapes):
+    /// ``\ ` * _ [ ] < > # | ~`` get a leading backslash so the text prints
+    /// literally. Code spans and code blocks are left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![Inline::Text("2 * 3".to_string())])],
+    /// };
+    /// let doc = doc.add_backslash_escapes();
+    /// assert_eq!(
+    ///     doc.blocks[0],
+    ///     Block::Paragraph(vec![Inline::Text(r"2 \* 3".to_string())])
+    /// );
+    /// ```
+    fn add_backslash_escapes(self) -> Self
+    where
+        Self: Sized,
+    {
+        self.transform_text(|text| escape_backslashes(&text))
+    }
+
+    /// Move all footnote definitions to the end of the document
+    ///
+    /// Shorthand for
+    /// [`collect_footnotes_to_end_with`](Transform::collect_footnotes_to_end_with)
+    /// with [`UnreferencedFootnotes::Keep`].
+    fn collect_footnotes_to_end(self) -> Self
+    where
+        Self: Sized,
+    {
+        self.collect_footnotes_to_end_with(UnreferencedFootnotes::Keep)
+    }
+
+    /// Move all footnote definitions to the end of the document
+    ///
+    /// Definitions are removed from wherever they appear, including nested
+    /// blocks, and appended in the order their footnotes are first
+    /// referenced. References inside a footnote count after the references
+    /// of the footnote itself. Labels are compared case-insensitively.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::{Transform, UnreferencedFootnotes};
+    ///
+    /// let footnote = |label: &str| {
+    ///     Block::FootnoteDefinition(FootnoteDefinition {
+    ///         label: label.to_string(),
+    ///         blocks: vec![Block::Paragraph(vec![Inline::Text(label.to_string())])],
+    ///     })
+    /// };
+    /// let doc = Document {
+    ///     blocks: vec![
+    ///         footnote("b"),
+    ///         footnote("unused"),
+    ///         Block::Paragraph(vec![
+    ///             Inline::FootnoteReference("a".to_string()),
+    ///             Inline::FootnoteReference("b".to_string()),
+    ///         ]),
+    ///         footnote("a"),
+    ///     ],
+    /// };
+    /// let doc = doc.collect_footnotes_to_end_with(UnreferencedFootnotes::Drop);
+    /// assert_eq!(doc.blocks[1..], [footnote("a"), footnote("b")]);
+    /// ```
+    fn collect_footnotes_to_end_with(self, unreferenced: UnreferencedFootnotes) -> Self;
+
+    /// Embed images as `data:` URIs
+    ///
+    /// The `loader` is called with each image destination and returns the MIME
+    /// type and bytes of the image, or `None` to leave the image untouched.
+    /// The crate takes care of base64 encoding; reading files or fetching URLs is
+    /// up to the caller.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![Inline::Image(Image {
+    ///         destination: "dot.gif".to_string(),
+    ///         title: None,
+    ///         alt: "dot".to_string(),
+    ///         attr: None,
+    ///     })])],
+    /// };
+    /// let result = doc.inline_images(|_| Some(("image/gif".to_string(), b"GIF".to_vec())));
+    /// if let Block::Paragraph(inlines) = &result.blocks[0] {
+    ///     if let Inline::Image(image) = &inlines[0] {
+    ///         assert_eq!(image.destination, "data:image/gif;base64,R0lG");
+    ///     }
+    /// }
+    /// ```
+    fn inline_images<F>(self, loader: F) -> Self
+    where
+        F: FnMut(&str) -> Option<(String, Vec<u8>)>;
+
+    /// Split the document into sections at top-level headings of `level`
+    ///
+    /// Each section starts with such a heading and runs up to the next one.
+    /// Content before the first heading becomes a leading section of its own;
+    /// it is omitted if there is none.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let heading = |text: &str| {
+    ///     Block::Heading(Heading {
+    ///         kind: HeadingKind::Atx(1),
+    ///         content: vec![Inline::Text(text.to_string())],
+    ///     })
+    /// };
+    /// let doc = Document {
+    ///     blocks: vec![heading("One"), heading("Two")],
+    /// };
+    /// assert_eq!(doc.split_by_heading(1).len(), 2);
+    /// ```
+    fn split_by_heading(self, level: u8) -> Vec<Self>
+    where
+        Self: Sized;
+
+    /// Rewrite every heading to use the `target` style
+    ///
+    /// Setext only exists for levels 1 and 2, so deeper headings stay ATX
+    /// when converting to [`HeadingStyle::Setext`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::{HeadingStyle, Transform};
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Heading(Heading {
+    ///         kind: HeadingKind::Setext(SetextHeading::Level2),
+    ///         content: vec![Inline::Text("Title".to_string())],
+    ///     })],
+    /// };
+    /// let doc = doc.normalize_heading_kinds(HeadingStyle::Atx);
+    /// assert!(matches!(
+    ///     &doc.blocks[0],
+    ///     Block::Heading(Heading { kind: HeadingKind::Atx(2), .. })
+    /// ));
+    /// ```
+    fn normalize_heading_kinds(self, target: HeadingStyle) -> Self;
+
+    /// Replace regex matches in text with inline nodes built by `f`
+    ///
+    /// Each match is replaced by the inlines `f` returns for its captures;
+    /// text between matches stays as [`Inline::Text`]. Link texts, images and
+    /// autolinks are left untouched so a match never produces nested links,
+    /// and code spans and code blocks are never matched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    /// use regex::Regex;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![Inline::Text("a TODO b".to_string())])],
+    /// };
+    /// let doc = doc.replace_regex(Regex::new("TODO").unwrap(), |caps| {
+    ///     vec![Inline::Strong(vec![Inline::Text(caps[0].to_string())])]
+    /// });
+    /// assert_eq!(
+    ///     doc.blocks[0],
+    ///     Block::Paragraph(vec![
+    ///         Inline::Text("a ".to_string()),
+    ///         Inline::Strong(vec![Inline::Text("TODO".to_string())]),
+    ///         Inline::Text(" b".to_string()),
+    ///     ])
+    /// );
+    /// ```
+    fn replace_regex<F>(self, re: regex::Regex, f: F) -> Self
+    where
+        F: FnMut(&regex::Captures) -> Vec<Inline>;
+
+    /// Turn bare `http://`, `https://` and `www.` URLs in text into links
+    ///
+    /// Trailing punctuation such as `.` or `,` and unbalanced closing
+    /// parentheses are left out of the URL, following GFM's extended
+    /// autolinks. `http(s)` URLs become [`Inline::Autolink`]; `www.` URLs
+    /// become an [`Inline::Link`] to `http://www.…` that keeps the original
+    /// text. Existing links, images and autolinks are left untouched, as are
+    /// URLs inside code spans and code blocks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![Inline::Text(
+    ///         "See https://example.com.".to_string(),
+    ///     )])],
+    /// };
+    /// let doc = doc.autolink_bare_urls();
+    /// assert_eq!(
+    ///     doc.blocks[0],
+    ///     Block::Paragraph(vec![
+    ///         Inline::Text("See ".to_string()),
+    ///         Inline::Autolink("https://example.com".to_string()),
+    ///         Inline::Text(".".to_string()),
+    ///     ])
+    /// );
+    /// ```
+    fn autolink_bare_urls(self) -> Self;
+
+    /// Normalize absolute link, image, autolink and link definition URLs
+    ///
+    /// Scheme and host are lowercased and default ports (`:80` for `http`,
+    /// `:443` for `https`, …) are removed; path and query values keep their
+    /// case. Relative URLs are left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::{Transform, UrlNormOptions};
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![Inline::Autolink(
+    ///         "HTTP://Example.COM:80/Path?utm_source=x&id=Q".to_string(),
+    ///     )])],
+    /// };
+    /// let opts = UrlNormOptions {
+    ///     strip_tracking_params: true,
+    /// };
+    /// let doc = doc.normalize_urls(opts);
+    /// assert_eq!(
+    ///     doc.blocks[0],
+    ///     Block::Paragraph(vec![Inline::Autolink("http://example.com/Path?id=Q".to_string())])
+    /// );
+    /// ```
+    fn normalize_urls(self, opts: UrlNormOptions) -> Self;
+
+    /// Collapse directly nested identical emphasis wrappers
+    ///
+    /// `Strong([Strong([...])])` becomes `Strong([...])`, and likewise for
+    /// emphasis and strikethrough. Only a wrapper whose sole child is the same
+    /// kind of wrapper is collapsed; mixed nesting such as
+    /// `Strong([Emphasis([...])])` is kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![Inline::Strong(vec![Inline::Strong(
+    ///         vec![Inline::Text("bold".to_string())],
+    ///     )])])],
+    /// };
+    /// assert_eq!(
+    ///     doc.flatten_emphasis().blocks[0],
+    ///     Block::Paragraph(vec![Inline::Strong(vec![Inline::Text("bold".to_string())])])
+    /// );
+    /// ```
+    fn flatten_emphasis(self) -> Self;
+
+    /// Turn GitHub alerts into plain blockquotes
+    ///
+    /// For renderers without alert support. The blockquote starts with a
+    /// paragraph holding the alert title in bold, followed by the alert's
+    /// blocks. Custom alerts use their name as the title.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::GitHubAlert(GitHubAlert {
+    ///         alert_type: GitHubAlertType::Tip,
+    ///         blocks: vec![Block::Paragraph(vec![Inline::Text("Hint".to_string())])],
+    ///     })],
+    /// };
+    /// assert_eq!(
+    ///     doc.alerts_to_blockquotes().blocks[0],
+    ///     Block::BlockQuote(vec![
+    ///         Block::Paragraph(vec![Inline::Strong(vec![Inline::Text("Tip".to_string())])]),
+    ///         Block::Paragraph(vec![Inline::Text("Hint".to_string())]),
+    ///     ])
+    /// );
+    /// ```
+    fn alerts_to_blockquotes(self) -> Self;
+
+    /// Number figures and tables and resolve references to them
+    ///
+    /// `figure` containers are numbered in document order. A figure whose
+    /// only content is a table counts as a table, as do bare tables, and
+    /// tables have their own sequence. A figure's `caption` parameter gets a
+    /// `Figure N: ` or `Table N: ` prefix, and its `id` parameter becomes a
+    /// label: `[@id]` anywhere in the text is replaced by the number. Unknown
+    /// labels are left as they are.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![
+    ///         Block::Paragraph(vec![Inline::Text("See figure [@fig:plot].".to_string())]),
+    ///         Block::Container(Container {
+    ///             kind: "figure".to_string(),
+    ///             params: vec![
+    ///                 ("id".to_string(), "fig:plot".to_string()),
+    ///                 ("caption".to_string(), "Results".to_string()),
+    ///             ],
+    ///             blocks: vec![],
+    ///         }),
+    ///     ],
+    /// };
+    /// let doc = doc.number_figures();
+    /// assert_eq!(
+    ///     doc.blocks[0],
+    ///     Block::Paragraph(vec![Inline::Text("See figure 1.".to_string())])
+    /// );
+    /// let Block::Container(figure) = &doc.blocks[1] else { unreachable!() };
+    /// assert_eq!(figure.params[1].1, "Figure 1: Results");
+    /// ```
+    fn number_figures(self) -> Self;
+
+    /// Replace tabs in code with spaces up to the next tab stop
+    ///
+    /// Tab stops are every `width` columns, counted from the start of each
+    /// line of a code block or code span. Other text is left as it is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::CodeBlock(CodeBlock {
+    ///         kind: CodeBlockKind::Fenced { info: None },
+    ///         literal: "a\tb\n\tc".to_string(),
+    ///     })],
+    /// };
+    /// let Block::CodeBlock(code) = &doc.expand_tabs_in_code(4).blocks[0] else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(code.literal, "a   b\n    c");
+    /// ```
+    fn expand_tabs_in_code(self, width: usize) -> Self;
+
+    /// Replace the content of every heading with its plain text
+    ///
+    /// Emphasis, links and other formatting are dropped while their text is
+    /// kept, as is the text of code spans. The result is a single text node,
+    /// which keeps anchors generated from the heading free of markup.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Heading(Heading {
+    ///         kind: HeadingKind::Atx(2),
+    ///         content: vec![
+    ///             Inline::Text("The ".to_string()),
+    ///             Inline::Emphasis(vec![Inline::Text("new".to_string())]),
+    ///             Inline::Text(" ".to_string()),
+    ///             Inline::Code("api".to_string()),
+    ///         ],
+    ///     })],
+    /// };
+    /// let Block::Heading(heading) = &doc.flatten_heading_inlines().blocks[0] else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(heading.content, vec![Inline::Text("The new api".to_string())]);
+    /// ```
+    fn flatten_heading_inlines(self) -> Self;
+
+    /// Transform every heading with a function that also receives its level
+    ///
+    /// The level is normalized to 1–6 for both ATX and setext headings.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Heading(Heading {
+    ///         kind: HeadingKind::Setext(SetextHeading::Level2),
+    ///         content: vec![Inline::Text("Usage".to_string())],
+    ///     })],
+    /// };
+    /// let doc = doc.map_headings(|level, mut heading| {
+    ///     heading.content.insert(0, Inline::Text(format!("{level}. ")));
+    ///     heading
+    /// });
+    /// let Block::Heading(heading) = &doc.blocks[0] else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(heading.content[0], Inline::Text("2. ".to_string()));
+    /// ```
+    fn map_headings<F>(self, f: F) -> Self
+    where
+        F: FnMut(u8, Heading) -> Heading;
+
+    /// Demote headings deeper than `max` to level `max`
+    ///
+    /// Shorthand for [`clamp_heading_levels_with`](Transform::clamp_heading_levels_with)
+    /// with [`HeadingOverflow::Clamp`].
+    fn clamp_heading_levels(self, max: u8) -> Self
+    where
+        Self: Sized,
+    {
+        self.clamp_heading_levels_with(max, HeadingOverflow::Clamp)
+    }
+
+    /// Limit headings to at most level `max`
+    ///
+    /// Headings deeper than `max` are handled according to `overflow`;
+    /// shallower headings are left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::{HeadingOverflow, Transform};
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Heading(Heading {
+    ///         kind: HeadingKind::Atx(5),
+    ///         content: vec![Inline::Text("Details".to_string())],
+    ///     })],
+    /// };
+    /// let doc = doc.clamp_heading_levels_with(3, HeadingOverflow::BoldParagraph);
+    /// assert_eq!(
+    ///     doc.blocks[0],
+    ///     Block::Paragraph(vec![Inline::Strong(vec![Inline::Text("Details".to_string())])])
+    /// );
+    /// ```
+    fn clamp_heading_levels_with(self, max: u8, overflow: HeadingOverflow) -> Self;
+
+    /// Collapse repeated whitespace in text and trim paragraph edges
+    ///
+    /// Runs of spaces and tabs inside text become a single space, and runs
+    /// that span a line break become just the line break. The first text
+    /// node of each paragraph, heading and table cell loses its leading
+    /// whitespace and the last one its trailing whitespace; spacing between
+    /// adjacent inlines is left alone. Non-breaking spaces are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![
+    ///         Inline::Text("  Hello  ".to_string()),
+    ///         Inline::Strong(vec![Inline::Text("big".to_string())]),
+    ///         Inline::Text("   world ".to_string()),
+    ///     ])],
+    /// };
+    /// assert_eq!(
+    ///     doc.tidy_whitespace().blocks[0],
+    ///     Block::Paragraph(vec![
+    ///         Inline::Text("Hello ".to_string()),
+    ///         Inline::Strong(vec![Inline::Text("big".to_string())]),
+    ///         Inline::Text(" world".to_string()),
+    ///     ])
+    /// );
+    /// ```
+    fn tidy_whitespace(self) -> Self;
+
+    /// Turn simple inline HTML spans into the matching Markdown inlines
+    ///
+    /// `<b>`/`<strong>` become [`Inline::Strong`], `<i>`/`<em>`
+    /// [`Inline::Emphasis`], `<s>`/`<del>` [`Inline::Strikethrough`],
+    /// `<code>` [`Inline::Code`] and `<a href="...">` [`Inline::Link`]. A span
+    /// is converted when it is a single [`Inline::Html`] node such as
+    /// `<b>x</b>` holding only text, or an opening and a closing tag node in
+    /// the same inline list with only converted spans between them. Tags
+    /// with other attributes, unmatched tags and other HTML are left
+    /// untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![
+    ///         Inline::Html("<em>".to_string()),
+    ///         Inline::Text("really".to_string()),
+    ///         Inline::Html("</em>".to_string()),
+    ///     ])],
+    /// };
+    /// assert_eq!(
+    ///     doc.htmlize_inlines().blocks[0],
+    ///     Block::Paragraph(vec![Inline::Emphasis(vec![Inline::Text("really".to_string())])])
+    /// );
+    /// ```
+    fn htmlize_inlines(self) -> Self;
+
+    /// Use the same marker for every list
+    ///
+    /// Bullet lists get `bullet` as their marker. Ordered lists get the
+    /// `ordered` numbering style if one is given and are left alone
+    /// otherwise; their start numbers are kept. The AST does not record
+    /// whether an ordered list used `.` or `)`, the printer always writes
+    /// `.`.
+    ///
+    /// Adjacent lists that only differed in their marker are read back as a
+    /// single list once printed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::List(List {
+    ///         kind: ListKind::Bullet(ListBulletKind::Star),
+    ///         items: vec![],
+    ///         tight: true,
+    ///     })],
+    /// };
+    /// let doc = doc.normalize_list_markers(ListBulletKind::Dash, None);
+    /// assert!(matches!(
+    ///     &doc.blocks[0],
+    ///     Block::List(List { kind: ListKind::Bullet(ListBulletKind::Dash), .. })
+    /// ));
+    /// ```
+    fn normalize_list_markers(
+        self,
+        bullet: ListBulletKind,
+        ordered: Option<OrderedListStyle>,
+    ) -> Self;
+
+    /// Replace bullet lists holding a single one-paragraph item with that
+    /// paragraph
+    ///
+    /// Shorthand for
+    /// [`collapse_single_item_lists_with`](Transform::collapse_single_item_lists_with)
+    /// that leaves ordered lists alone.
+    fn collapse_single_item_lists(self) -> Self
+    where
+        Self: Sized,
+    {
+        self.collapse_single_item_lists_with(false)
+    }
+
+    /// Replace lists holding a single one-paragraph item with that paragraph
+    ///
+    /// A list is collapsed when it has exactly one item, the item is not a
+    /// task and its only block is a paragraph. Bullet lists are always
+    /// collapsed; with `ordered`, ordered lists are too, losing their
+    /// number. Lists with more items are left as they are, but their items
+    /// are searched for nested lists.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let paragraph = Block::Paragraph(vec![Inline::Text("Only item".to_string())]);
+    /// let doc = Document {
+    ///     blocks: vec![Block::List(List {
+    ///         kind: ListKind::Bullet(ListBulletKind::Dash),
+    ///         items: vec![ListItem {
+    ///             task: None,
+    ///             blocks: vec![paragraph.clone()],
+    ///         }],
+    ///         tight: true,
+    ///     })],
+    /// };
+    /// assert_eq!(doc.collapse_single_item_lists().blocks, vec![paragraph]);
+    /// ```
+    fn collapse_single_item_lists_with(self, ordered: bool) -> Self;
+
+    /// Merge directly adjacent block quotes and indented code blocks
+    ///
+    /// Consecutive [`Block::BlockQuote`]s become one quote holding the
+    /// blocks of all of them, and consecutive indented code blocks become
+    /// one whose lines are joined with a newline. This happens in every
+    /// block sequence, including inside quotes and list items. Fenced code
+    /// blocks, and quotes or code blocks with another block between them,
+    /// are left alone.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::Transform;
+    ///
+    /// let code = |literal: &str| {
+    ///     Block::CodeBlock(CodeBlock {
+    ///         kind: CodeBlockKind::Indented,
+    ///         literal: literal.to_string(),
+    ///     })
+    /// };
+    /// let doc = Document {
+    ///     blocks: vec![code("let a = 1;"), code("let b = 2;")],
+    /// };
+    /// assert_eq!(
+    ///     doc.merge_adjacent_blocks().blocks,
+    ///     vec![code("let a = 1;\nlet b = 2;")]
+    /// );
+    /// ```
+    fn merge_adjacent_blocks(self) -> Self;
+}
+
+/// What [`Transform::collect_footnotes_to_end_with`] does with footnote
+/// definitions that are never referenced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnreferencedFootnotes {
+    /// Append them after the referenced ones, in source order.
+    #[default]
+    Keep,
+
+    /// Remove them.
+    Drop,
+}
+
+/// What [`Transform::clamp_heading_levels_with`] does with too deep headings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeadingOverflow {
+    /// Keep the heading, demoted to the maximum level.
+    #[default]
+    Clamp,
+
+    /// Replace the heading with a paragraph of bold text.
+    BoldParagraph,
+}
+
+/// Heading syntax targeted by [`Transform::normalize_heading_kinds`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadingStyle {
+    /// `#`-prefixed headings.
+    Atx,
+
+    /// Underlined headings, used for levels 1 and 2 only.
+    Setext,
+}
+
+/// Two link definitions with the same label but different destinations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefinitionConflict {
+    /// Normalized label shared by both definitions.
+    pub label: String,
+
+    /// Destination of the first definition.
+    pub first: String,
+
+    /// Destination of the conflicting later definition.
+    pub second: String,
+}
+
+impl std::fmt::Display for DefinitionConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "link definition [{}] points to both {} and {}",
+            self.label, self.first, self.second
+        )
+    }
+}
+
+impl std::error::Error for DefinitionConflict {}
+
+/// Options for [`Transform::normalize_urls`]
+#[derive(Debug, Clone, Default)]
+pub struct UrlNormOptions {
+    /// Remove `utm_*` tracking parameters from the query string.
+    pub strip_tracking_params: bool,
+}
+
+/// Options for [`Transform::excerpt`]
+#[derive(Debug, Clone, Default)]
+pub struct ExcerptOptions {
+    /// Maximum number of top-level blocks to keep.
+    pub max_blocks: Option<usize>,
+
+    /// Content of an HTML block (e.g. `<!-- more -->`) marking the end of the excerpt.
+    pub more_marker: Option<String>,
+}
+
+impl Transform for Document {
+    fn transform_text<F>(self, f: F) -> Self
+    where
+        F: Fn(String) -> String,
+    {
+        let mut transformer = TextTransformer::new(f);
+        transformer.transform_document(self)
+    }
+
+    fn transform_image_urls<F>(self, f: F) -> Self
+    where
+        F: Fn(String) -> String,
+    {
+        let mut transformer = ImageUrlTransformer::new(f);
+        transformer.transform_document(self)
+    }
+
+    fn transform_link_urls<F>(self, f: F) -> Self
+    where
+        F: Fn(String) -> String,
+    {
+        let mut transformer = LinkUrlTransformer::new(f);
+        transformer.transform_document(self)
+    }
+
+    fn transform_autolink_urls<F>(self, f: F) -> Self
+    where
+        F: Fn(String) -> String,
+    {
+        let mut transformer = AutolinkTransformer::new(f);
+        transformer.transform_document(self)
+    }
+
+    fn transform_code<F>(self, f: F) -> Self
+    where
+        F: Fn(String) -> String,
+    {
+        let mut transformer = CodeTransformer::new(f);
+        transformer.transform_document(self)
+    }
+
+    fn default_code_language_with(self, lang: &str, fence_indented: bool) -> Self {
+        let mut transformer = CodeLanguageDefaulter {
+            lang,
+            fence_indented,
+        };
+        transformer.transform_document(self)
+    }
+
+    fn transform_html<F>(self, f: F) -> Self
+    where
+        F: Fn(String) -> String,
+    {
+        let mut transformer = HtmlTransformer::new(f);
+        transformer.transform_document(self)
+    }
+
+    fn transform_with<T: Transformer>(self, mut transformer: T) -> Self {
+        transformer.transform_document(self)
+    }
+
+    fn transform_if_doc<P, F>(self, predicate: P, transform: F) -> Self
+    where
+        P: Fn(&Self) -> bool,
+        F: FnOnce(Self) -> Self,
+    {
+        if predicate(&self) {
+            transform(self)
+        } else {
+            self
+        }
+    }
+
+    fn excerpt(mut self, opts: ExcerptOptions) -> Self {
+        let marker_position = opts.more_marker.as_deref().and_then(|marker| {
+            self.blocks.iter().position(
+                |block| matches!(block, Block::HtmlBlock(html) if html.content.trim() == marker.trim()),
+            )
+        });
+
+        if let Some(position) = marker_position {
+            self.blocks.truncate(position);
+        } else if let Some(max_blocks) = opts.max_blocks {
+            self.blocks.truncate(max_blocks);
+        }
+        self
+    }
+
+    fn rebase_urls(self, base: &str) -> Self {
+        match url::Url::parse(base) {
+            Ok(base) => UrlRebaser { base }.transform_document(self),
+            Err(_) => self,
+        }
+    }
+
+    fn smart_punctuation(self) -> Self {
+        let mut transformer = SmartPunctuation::default();
+        transformer.transform_document(self)
+    }
+
+    fn dedupe_definitions(self) -> Self {
+        let mut deduper = DefinitionDeduper::new(&self, false);
+        deduper.expand_document(self).remove(0)
+    }
+
+    fn try_dedupe_definitions(self) -> Result<Self, DefinitionConflict> {
+        let mut deduper = DefinitionDeduper::new(&self, true);
+        let doc = deduper.expand_document(self).remove(0);
+        match deduper.conflict {
+            Some(conflict) => Err(conflict),
+            None => Ok(doc),
+        }
+    }
+
+    fn collect_footnotes_to_end_with(self, unreferenced: UnreferencedFootnotes) -> Self {
+        let mut collector = FootnoteCollector::default();
+        let mut doc = collector.expand_document(self).remove(0);
+
+        let mut references = FootnoteReferences::default();
+        doc.visit_with(&mut references);
+        let mut definitions = collector.definitions;
+        let mut i = 0;
+        while i < references.labels.len() {
+            let key = references.labels[i].to_lowercase();
+            if let Some(pos) = definitions
+                .iter()
+                .position(|def| def.label.to_lowercase() == key)
+            {
+                let def = definitions.remove(pos);
+                for block in &def.blocks {
+                    block.visit_with(&mut references);
+                }
+                doc.blocks.push(Block::FootnoteDefinition(def));
+            }
+            i += 1;
+        }
+
+        if unreferenced == UnreferencedFootnotes::Keep {
+            doc.blocks
+                .extend(definitions.into_iter().map(Block::FootnoteDefinition));
+        }
+        doc
+    }
+
+    fn inline_images<F>(self, loader: F) -> Self
+    where
+        F: FnMut(&str) -> Option<(String, Vec<u8>)>,
+    {
+        let mut transformer = ImageInliner { loader };
+        transformer.transform_document(self)
+    }
+
+    fn split_by_heading(self, level: u8) -> Vec<Self> {
+        let mut sections = Vec::new();
+        let mut current = Vec::new();
+        for block in self.blocks {
+            let starts_section =
+                matches!(&block, Block::Heading(heading) if heading.level() == level);
+            if starts_section && !current.is_empty() {
+                sections.push(Document {
+                    blocks: std::mem::take(&mut current),
+                });
+            }
+            current.push(block);
+        }
+        if !current.is_empty() {
+            sections.push(Document { blocks: current });
+        }
+        sections
+    }
+
+    fn normalize_heading_kinds(self, target: HeadingStyle) -> Self {
+        let mut transformer = HeadingKindNormalizer { target };
+        transformer.transform_document(self)
+    }
+
+    fn replace_regex<F>(self, re: regex::Regex, f: F) -> Self
+    where
+        F: FnMut(&regex::Captures) -> Vec<Inline>,
+    {
+        let mut replacer = RegexReplacer { re, f };
+        replacer.expand_document(self).remove(0)
+    }
+
+    fn autolink_bare_urls(self) -> Self {
+        BareUrlLinker.expand_document(self).remove(0)
+    }
+
+    fn normalize_urls(self, opts: UrlNormOptions) -> Self {
+        let mut transformer = UrlNormalizer { opts };
+        transformer.transform_document(self)
+    }
+
+    fn flatten_emphasis(self) -> Self {
+        EmphasisFlattener.transform_document(self)
+    }
+
+    fn number_figures(self) -> Self {
+        let mut numberer = FigureNumberer::default();
+        let doc = numberer.transform_document(self);
+        let mut resolver = FigureReferences {
+            labels: numberer.labels,
+        };
+        resolver.transform_document(doc)
+    }
+
+    fn alerts_to_blockquotes(self) -> Self {
+        AlertDegrader.transform_document(self)
+    }
+
+    fn expand_tabs_in_code(self, width: usize) -> Self {
+        let mut transformer = TabExpander {
+            width: width.max(1),
+        };
+        transformer.transform_document(self)
+    }
+
+    fn flatten_heading_inlines(self) -> Self {
+        HeadingFlattener.transform_document(self)
+    }
+
+    fn map_headings<F>(self, f: F) -> Self
+    where
+        F: FnMut(u8, Heading) -> Heading,
+    {
+        let mut transformer = HeadingMapper { func: f };
+        transformer.transform_document(self)
+    }
+
+    fn clamp_heading_levels_with(self, max: u8, overflow: HeadingOverflow) -> Self {
+        let mut transformer = HeadingClamper {
+            max: max.max(1),
+            overflow,
+        };
+        transformer.transform_document(self)
+    }
+
+    fn tidy_whitespace(self) -> Self {
+        WhitespaceTidier.transform_document(self)
+    }
+
+    fn htmlize_inlines(self) -> Self {
+        HtmlInliner.transform_document(self)
+    }
+
+    fn normalize_list_markers(
+        self,
+        bullet: ListBulletKind,
+        ordered: Option<OrderedListStyle>,
+    ) -> Self {
+        let mut transformer = ListMarkerNormalizer { bullet, ordered };
+        transformer.transform_document(self)
+    }
+
+    fn collapse_single_item_lists_with(self, ordered: bool) -> Self {
+        let mut transformer = SingleItemListCollapser { ordered };
+        transformer.transform_document(self)
+    }
+
+    fn merge_adjacent_blocks(self) -> Self {
+        AdjacentBlockMerger.transform_document(self)
+    }
+}
+
+/// Whitespace that may be collapsed or trimmed; non-breaking spaces are
+/// content and are kept
+fn is_collapsible_space(c: char) -> bool {
+    c.is_whitespace() && !matches!(c, '\u{a0}' | '\u{2007}' | '\u{202f}')
+}
+
+fn unescape_backslashes(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(&next) = chars.peek() {
+                if next.is_ascii_punctuation() {
+                    out.push(next);
+                    chars.next();
+                    continue;
+                }
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn escape_backslashes(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+// Internal transformer implementations
+
+struct TextTransformer<F> {
+    func: F,
+}
+
+impl<F> TextTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn new(func: F) -> Self {
+        Self { func }
+    }
+}
+
+impl<F> Transformer for TextTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        match inline {
+            Inline::Text(text) => Inline::Text((self.func)(text)),
+            other => self.walk_transform_inline(other),
+        }
+    }
+}
+
+struct ImageUrlTransformer<F> {
+    func: F,
+}
+
+impl<F> ImageUrlTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn new(func: F) -> Self {
+        Self { func }
+    }
+}
+
+impl<F> Transformer for ImageUrlTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        match inline {
+            Inline::Image(mut image) => {
+                image.destination = (self.func)(image.destination);
+                Inline::Image(image)
+            }
+            other => self.walk_transform_inline(other),
+        }
+    }
+}
+
+struct LinkUrlTransformer<F> {
+    func: F,
+}
+
+impl<F> LinkUrlTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn new(func: F) -> Self {
+        Self { func }
+    }
+}
+
+impl<F> Transformer for LinkUrlTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        match inline {
+            Inline::Link(mut link) => {
+                link.destination = (self.func)(link.destination);
+                link.children = link
+                    .children
+                    .into_iter()
+                    .map(|child| self.transform_inline(child))
+                    .collect();
+                Inline::Link(link)
+            }
+            other => self.walk_transform_inline(other),
+        }
+    }
+}
+
+struct AutolinkTransformer<F> {
+    func: F,
+}
+
+impl<F> AutolinkTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn new(func: F) -> Self {
+        Self { func }
+    }
+}
+
+impl<F> Transformer for AutolinkTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        match inline {
+            Inline::Autolink(url) => Inline::Autolink((self.func)(url)),
+            other => self.walk_transform_inline(other),
+        }
+    }
+}
+
+struct CodeTransformer<F> {
+    func: F,
+}
+
+impl<F> CodeTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn new(func: F) -> Self {
+        Self { func }
+    }
+}
+
+impl<F> Transformer for CodeTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        match inline {
+            Inline::Code(code) => Inline::Code((self.func)(code)),
+            other => self.walk_transform_inline(other),
+        }
+    }
+}
+
+struct CodeLanguageDefaulter<'a> {
+    lang: &'a str,
+    fence_indented: bool,
+}
+
+impl Transformer for CodeLanguageDefaulter<'_> {
+    fn transform_code_block(&mut self, mut code_block: CodeBlock) -> CodeBlock {
+        match &code_block.kind {
+            CodeBlockKind::Fenced { info }
+                if info.as_deref().is_some_and(|info| !info.trim().is_empty()) => {}
+            CodeBlockKind::Indented if !self.fence_indented => {}
+            _ => {
+                code_block.kind = CodeBlockKind::Fenced {
+                    info: Some(self.lang.to_string()),
+                }
+            }
+        }
+        code_block
+    }
+}
+
+struct HtmlTransformer<F> {
+    func: F,
+}
+
+impl<F> HtmlTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn new(func: F) -> Self {
+        Self { func }
+    }
+}
+
+impl<F> Transformer for HtmlTransformer<F>
+where
+    F: Fn(String) -> String,
+{
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        match inline {
+            Inline::Html(html) => Inline::Html((self.func)(html)),
+            other => self.walk_transform_inline(other),
+        }
+    }
+
+    fn transform_block(&mut self, block: Block) -> Block {
+        match block {
+            Block::HtmlBlock(html) => Block::HtmlBlock(HtmlBlock {
+                content: (self.func)(html.content),
+                ..html
+            }),
+            other => self.walk_transform_block(other),
+        }
+    }
+}
+
+struct UrlRebaser {
+    base: url::Url,
+}
+
+impl UrlRebaser {
+    fn rebase(&self, destination: String) -> String {
+        if destination.starts_with('#') || url::Url::parse(&destination).is_ok() {
+            return destination;
+        }
+        match self.base.join(&destination) {
+            Ok(url) => url.into(),
+            Err(_) => destination,
+        }
+    }
+}
+
+impl Transformer for UrlRebaser {
+    fn transform_link(&mut self, mut link: Link) -> Link {
+        link.destination = self.rebase(link.destination);
+        self.walk_transform_link(link)
+    }
+
+    fn transform_image(&mut self, mut image: Image) -> Image {
+        image.destination = self.rebase(image.destination);
+        image
+    }
+
+    fn transform_block(&mut self, block: Block) -> Block {
+        match block {
+            Block::Definition(mut def) => {
+                def.destination = self.rebase(def.destination);
+                self.walk_transform_block(Block::Definition(def))
+            }
+            other => self.walk_transform_block(other),
+        }
+    }
+}
+
+struct UrlNormalizer {
+    opts: UrlNormOptions,
+}
+
+impl UrlNormalizer {
+    fn normalize(&self, destination: String) -> String {
+        // Parsing lowercases the scheme and host and drops default ports
+        let Ok(mut url) = url::Url::parse(&destination) else {
+            return destination;
+        };
+        if self.opts.strip_tracking_params {
+            if let Some(query) = url.query() {
+                let kept: Vec<&str> = query
+                    .split('&')
+                    .filter(|pair| !pair.starts_with("utm_"))
+                    .collect();
+                let kept = kept.join("&");
+                url.set_query((!kept.is_empty()).then_some(kept.as_str()));
+            }
+        }
+        url.into()
+    }
+}
+
+impl Transformer for UrlNormalizer {
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        match inline {
+            Inline::Autolink(url) => Inline::Autolink(self.normalize(url)),
+            other => self.walk_transform_inline(other),
+        }
+    }
+
+    fn transform_link(&mut self, mut link: Link) -> Link {
+        link.destination = self.normalize(link.destination);
+        self.walk_transform_link(link)
+    }
+
+    fn transform_image(&mut self, mut image: Image) -> Image {
+        image.destination = self.normalize(image.destination);
+        image
+    }
+
+    fn transform_block(&mut self, block: Block) -> Block {
+        match block {
+            Block::Definition(mut def) => {
+                def.destination = self.normalize(def.destination);
+                self.walk_transform_block(Block::Definition(def))
+            }
+            other => self.walk_transform_block(other),
+        }
+    }
+}
+
+struct HeadingMapper<F> {
+    func: F,
+}
+
+impl<F> Transformer for HeadingMapper<F>
+where
+    F: FnMut(u8, Heading) -> Heading,
+{
+    fn transform_heading(&mut self, heading: Heading) -> Heading {
+        let heading = self.walk_transform_heading(heading);
+        (self.func)(heading.level(), heading)
+    }
+}
+
+struct HeadingClamper {
+    max: u8,
+    overflow: HeadingOverflow,
+}
+
+impl Transformer for HeadingClamper {
+    fn transform_block(&mut self, block: Block) -> Block {
+        match self.walk_transform_block(block) {
+            Block::Heading(mut heading) if heading.level() > self.max => match self.overflow {
+                HeadingOverflow::Clamp => {
+                    heading.kind = match heading.kind {
+                        HeadingKind::Setext(_) => HeadingKind::Setext(SetextHeading::Level1),
+                        HeadingKind::Atx(_) => HeadingKind::Atx(self.max),
+                    };
+                    Block::Heading(heading)
+                }
+                HeadingOverflow::BoldParagraph => {
+                    Block::Paragraph(vec![Inline::Strong(heading.content)])
+                }
+            },
+            other => other,
+        }
+    }
+}
+
+#[derive(Default)]
+struct FigureNumberer {
+    figures: usize,
+    tables: usize,
+    labels: HashMap<String, usize>,
+}
+
+impl Transformer for FigureNumberer {
+    fn transform_block(&mut self, block: Block) -> Block {
+        match block {
+            Block::Container(mut container) if container.kind == "figure" => {
+                let is_table = matches!(container.blocks.as_slice(), [Block::Table(_)]);
+                let (name, number) = if is_table {
+                    self.tables += 1;
+                    ("Table", self.tables)
+                } else {
+                    self.figures += 1;
+                    ("Figure", self.figures)
+                };
+                for (key, value) in &mut container.params {
+                    match key.as_str() {
+                        "id" => {
+                            self.labels.insert(value.clone(), number);
+                        }
+                        "caption" => *value = format!("{name} {number}: {value}"),
+                        _ => {}
+                    }
+                }
+                // The table was counted as the figure itself
+                if !is_table {
+                    container.blocks = container
+                        .blocks
+                        .into_iter()
+                        .map(|block| self.transform_block(block))
+                        .collect();
+                }
+                Block::Container(container)
+            }
+            Block::Table(table) => {
+                self.tables += 1;
+                Block::Table(table)
+            }
+            other => self.walk_transform_block(other),
+        }
+    }
+}
+
+struct FigureReferences {
+    labels: HashMap<String, usize>,
+}
+
+impl Transformer for FigureReferences {
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        // The parser reads `[@label]` as a shortcut reference
+        if let Inline::LinkReference(link_ref) = &inline {
+            if let [Inline::Text(text)] = link_ref.label.as_slice() {
+                let number = text
+                    .strip_prefix('@')
+                    .and_then(|label| self.labels.get(label));
+                if let Some(number) = number.filter(|_| link_ref.text == link_ref.label) {
+                    return Inline::Text(number.to_string());
+                }
+            }
+        }
+        self.walk_transform_inline(inline)
+    }
+
+    fn transform_text(&mut self, text: String) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+        while let Some(start) = rest.find("[@") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            match after
+                .find(']')
+                .and_then(|end| Some((end, self.labels.get(&after[..end])?)))
+            {
+                Some((end, number)) => {
+                    out.push_str(&number.to_string());
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    out.push_str("[@");
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+struct AlertDegrader;
+
+impl Transformer for AlertDegrader {
+    fn transform_block(&mut self, block: Block) -> Block {
+        match self.walk_transform_block(block) {
+            Block::GitHubAlert(alert) => {
+                let title = match alert.alert_type {
+                    GitHubAlertType::Note => "Note".to_string(),
+                    GitHubAlertType::Tip => "Tip".to_string(),
+                    GitHubAlertType::Important => "Important".to_string(),
+                    GitHubAlertType::Warning => "Warning".to_string(),
+                    GitHubAlertType::Caution => "Caution".to_string(),
+                    GitHubAlertType::Custom(name) => name,
+                };
+                let mut blocks = vec![Block::Paragraph(vec![Inline::Strong(vec![Inline::Text(
+                    title,
+                )])])];
+                blocks.extend(alert.blocks);
+                Block::BlockQuote(blocks)
+            }
+            other => other,
+        }
+    }
+}
+
+struct TabExpander {
+    width: usize,
+}
+
+impl TabExpander {
+    fn expand(&self, code: &str) -> String {
+        let mut out = String::with_capacity(code.len());
+        let mut column = 0;
+        for c in code.chars() {
+            match c {
+                '\t' => {
+                    let spaces = self.width - column % self.width;
+                    out.extend(std::iter::repeat_n(' ', spaces));
+                    column += spaces;
+                }
+                '\n' => {
+                    out.push(c);
+                    column = 0;
+                }
+                _ => {
+                    out.push(c);
+                    column += 1;
+                }
+            }
+        }
+        out
+    }
+}
+
+impl Transformer for TabExpander {
+    fn transform_code_block(&mut self, mut code_block: CodeBlock) -> CodeBlock {
+        code_block.literal = self.expand(&code_block.literal);
+        code_block
+    }
+
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        match inline {
+            Inline::Code(code) => Inline::Code(self.expand(&code)),
+            other => self.walk_transform_inline(other),
+        }
+    }
+}
+
+struct HtmlInliner;
+
+/// Opening tag of an HTML span [`Transform::htmlize_inlines`] understands
+struct HtmlSpanTag {
+    name: String,
+    href: Option<String>,
+    title: Option<String>,
+}
+
+impl HtmlSpanTag {
+    /// Parse `<name attr="value" ...>` at the start of `html`, returning the
+    /// tag and the rest of the string
+    fn parse(html: &str) -> Option<(Self, &str)> {
+        let rest = html.strip_prefix('<')?;
+        let name_end = rest
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        let (attrs, rest) = rest[name_end..].split_once('>')?;
+        let mut tag = HtmlSpanTag {
+            name,
+            href: None,
+            title: None,
+        };
+        let mut attrs = attrs.trim_start();
+        while !attrs.is_empty() {
+            let (key, value) = attrs.split_once('=')?;
+            let value = value.trim_start();
+            let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let (value, after) = value[1..].split_once(quote)?;
+            match key.trim().to_ascii_lowercase().as_str() {
+                "href" if tag.name == "a" => tag.href = Some(value.to_string()),
+                "title" if tag.name == "a" => tag.title = Some(value.to_string()),
+                _ => return None,
+            }
+            attrs = after.trim_start();
+        }
+        let known = matches!(
+            tag.name.as_str(),
+            "b" | "strong" | "i" | "em" | "s" | "del" | "code"
+        ) || (tag.name == "a" && tag.href.is_some());
+        known.then_some((tag, rest))
+    }
+
+    fn closing(&self) -> String {
+        format!("</{}>", self.name)
+    }
+
+    /// Inline for this tag around `children`, or the children back if they
+    /// do not fit
+    fn into_inline(self, children: Vec<Inline>) -> Result<Inline, Vec<Inline>> {
+        Ok(match self.name.as_str() {
+            "b" | "strong" => Inline::Strong(children),
+            "i" | "em" => Inline::Emphasis(children),
+            "s" | "del" => Inline::Strikethrough(children),
+            "code" => {
+                if !children
+                    .iter()
+                    .all(|child| matches!(child, Inline::Text(_)))
+                {
+                    return Err(children);
+                }
+                Inline::Code(inline_text(&children))
+            }
+            _ => match self.href {
+                Some(destination) => Inline::Link(Link {
+                    destination,
+                    title: self.title,
+                    children,
+                }),
+                None => return Err(children),
+            },
+        })
+    }
+}
+
+impl HtmlInliner {
+    fn convert(inlines: Vec<Inline>) -> Vec<Inline> {
+        let mut result = Vec::with_capacity(inlines.len());
+        // Opening tag nodes not closed yet, with their index in `result`
+        let mut open: Vec<(usize, HtmlSpanTag)> = Vec::new();
+        for inline in inlines {
+            let Inline::Html(html) = &inline else {
+                result.push(inline);
+                continue;
+            };
+
+            if let Some((tag, after)) = HtmlSpanTag::parse(html) {
+                if after.is_empty() {
+                    open.push((result.len(), tag));
+                    result.push(inline);
+                    continue;
+                }
+                // The whole span in one node: `<b>text</b>`
+                let closing = tag.closing();
+                let converted = after
+                    .strip_suffix(closing.as_str())
+                    .filter(|text| !text.contains('<'))
+                    .map(|text| vec![Inline::Text(text.to_string())])
+                    .and_then(|children| tag.into_inline(children).ok());
+                result.push(converted.unwrap_or(inline));
+                continue;
+            }
+
+            let closes_last = open
+                .last()
+                .is_some_and(|(_, tag)| html.eq_ignore_ascii_case(&tag.closing()));
+            if !closes_last {
+                // Other HTML in between: nothing open so far can be converted
+                open.clear();
+                result.push(inline);
+                continue;
+            }
+            let (start, tag) = open.pop().expect("checked above");
+            let children = result.split_off(start + 1);
+            match tag.into_inline(children) {
+                Ok(converted) => {
+                    result.pop();
+                    result.push(converted);
+                }
+                Err(children) => {
+                    open.clear();
+                    result.extend(children);
+                    result.push(inline);
+                }
+            }
+        }
+        result
+    }
+}
+
+impl Transformer for HtmlInliner {
+    fn transform_block(&mut self, block: Block) -> Block {
+        match self.walk_transform_block(block) {
+            Block::Paragraph(inlines) => Block::Paragraph(Self::convert(inlines)),
+            other => other,
+        }
+    }
+
+    fn transform_heading(&mut self, heading: Heading) -> Heading {
+        let mut heading = self.walk_transform_heading(heading);
+        heading.content = Self::convert(heading.content);
+        heading
+    }
+
+    fn transform_table_cell(&mut self, cell: TableCell) -> TableCell {
+        let mut cell = self.walk_transform_table_cell(cell);
+        cell.content = Self::convert(cell.content);
+        cell
+    }
+
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        match self.walk_transform_inline(inline) {
+            Inline::Emphasis(children) => Inline::Emphasis(Self::convert(children)),
+            Inline::Strong(children) => Inline::Strong(Self::convert(children)),
+            Inline::Strikethrough(children) => Inline::Strikethrough(Self::convert(children)),
+            Inline::Highlight(children) => Inline::Highlight(Self::convert(children)),
+            Inline::Subscript(children) => Inline::Subscript(Self::convert(children)),
+            Inline::Superscript(children) => Inline::Superscript(Self::convert(children)),
+            Inline::Link(mut link) => {
+                link.children = Self::convert(link.children);
+                Inline::Link(link)
+            }
+            other => other,
+        }
+    }
+}
+
+struct WhitespaceTidier;
+
+impl WhitespaceTidier {
+    fn trim_edges(inlines: &mut Vec<Inline>) {
+        if let Some(Inline::Text(text)) = inlines.first_mut() {
+            *text = text.trim_start_matches(is_collapsible_space).to_string();
+            if text.is_empty() {
+                inlines.remove(0);
+            }
+        }
+        if let Some(Inline::Text(text)) = inlines.last_mut() {
+            text.truncate(text.trim_end_matches(is_collapsible_space).len());
+            if text.is_empty() {
+                inlines.pop();
+            }
+        }
+    }
+}
+
+impl Transformer for WhitespaceTidier {
+    fn transform_block(&mut self, block: Block) -> Block {
+        match self.walk_transform_block(block) {
+            Block::Paragraph(mut inlines) => {
+                Self::trim_edges(&mut inlines);
+                Block::Paragraph(inlines)
+            }
+            other => other,
+        }
+    }
+
+    fn transform_heading(&mut self, heading: Heading) -> Heading {
+        let mut heading = self.walk_transform_heading(heading);
+        Self::trim_edges(&mut heading.content);
+        heading
+    }
 
-    /// Transform conditionally based on a document predicate
-    fn transform_if_doc<P, F>(self, predicate: P, transform: F) -> Self
-    where
-        P: Fn(&Self) -> bool,
-        F: FnOnce(Self) -> Self,
-        Self: Sized;
+    fn transform_table_cell(&mut self, cell: TableCell) -> TableCell {
+        let mut cell = self.walk_transform_table_cell(cell);
+        Self::trim_edges(&mut cell.content);
+        cell
+    }
+
+    fn transform_text(&mut self, text: String) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut run = None;
+        for c in text.chars() {
+            if is_collapsible_space(c) {
+                // A run keeps a line break if it has one, otherwise a space
+                if c == '\n' || run.is_none() {
+                    run = Some(if c == '\n' { '\n' } else { ' ' });
+                }
+                continue;
+            }
+            out.extend(run.take());
+            out.push(c);
+        }
+        out.extend(run);
+        out
+    }
 }
 
-impl Transform for Document {
-    fn transform_text<F>(self, f: F) -> Self
-    where
-        F: Fn(String) -> String,
-    {
-        let mut transformer = TextTransformer::new(f);
-        transformer.transform_document(self)
+struct HeadingFlattener;
+
+impl Transformer for HeadingFlattener {
+    fn transform_heading(&mut self, mut heading: Heading) -> Heading {
+        let text = inline_text(&heading.content);
+        heading.content = if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![Inline::Text(text)]
+        };
+        heading
     }
+}
 
-    fn transform_image_urls<F>(self, f: F) -> Self
-    where
-        F: Fn(String) -> String,
-    {
-        let mut transformer = ImageUrlTransformer::new(f);
-        transformer.transform_document(self)
+struct EmphasisFlattener;
+
+impl Transformer for EmphasisFlattener {
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        // Children first, so deeper nesting is already collapsed
+        match self.walk_transform_inline(inline) {
+            Inline::Emphasis(mut children) => {
+                if let [Inline::Emphasis(inner)] = children.as_mut_slice() {
+                    children = std::mem::take(inner);
+                }
+                Inline::Emphasis(children)
+            }
+            Inline::Strong(mut children) => {
+                if let [Inline::Strong(inner)] = children.as_mut_slice() {
+                    children = std::mem::take(inner);
+                }
+                Inline::Strong(children)
+            }
+            Inline::Strikethrough(mut children) => {
+                if let [Inline::Strikethrough(inner)] = children.as_mut_slice() {
+                    children = std::mem::take(inner);
+                }
+                Inline::Strikethrough(children)
+            }
+            other => other,
+        }
     }
+}
 
-    fn transform_link_urls<F>(self, f: F) -> Self
-    where
-        F: Fn(String) -> String,
-    {
-        let mut transformer = LinkUrlTransformer::new(f);
-        transformer.transform_document(self)
+/// Quote and previous-character state shared by all text nodes of a block.
+#[derive(Default)]
+struct SmartPunctuation {
+    prev: Option<char>,
+    double_open: bool,
+}
+
+impl SmartPunctuation {
+    fn reset(&mut self) {
+        *self = Self::default();
     }
 
-    fn transform_autolink_urls<F>(self, f: F) -> Self
-    where
-        F: Fn(String) -> String,
-    {
-        let mut transformer = AutolinkTransformer::new(f);
-        transformer.transform_document(self)
+    fn is_boundary(c: Option<char>) -> bool {
+        match c {
+            None => true,
+            Some(c) => c.is_whitespace() || matches!(c, '(' | '[' | '{' | '\u{2013}' | '\u{2014}'),
+        }
     }
 
-    fn transform_code<F>(self, f: F) -> Self
-    where
-        F: Fn(String) -> String,
-    {
-        let mut transformer = CodeTransformer::new(f);
-        transformer.transform_document(self)
+    fn convert(&mut self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            let next = chars.peek().copied();
+            let replacement = match c {
+                '"' => {
+                    let open = Self::is_boundary(self.prev)
+                        || (!self.double_open && next.is_some_and(char::is_alphanumeric));
+                    self.double_open = open;
+                    if open {
+                        '\u{201C}'
+                    } else {
+                        '\u{201D}'
+                    }
+                }
+                '\'' => {
+                    let in_word = self.prev.is_some_and(char::is_alphanumeric)
+                        && next.is_some_and(char::is_alphanumeric);
+                    if !in_word && Self::is_boundary(self.prev) {
+                        '\u{2018}'
+                    } else {
+                        '\u{2019}'
+                    }
+                }
+                '-' if next == Some('-') => {
+                    chars.next();
+                    if chars.peek() == Some(&'-') {
+                        chars.next();
+                        '\u{2014}'
+                    } else {
+                        '\u{2013}'
+                    }
+                }
+                '.' if chars.clone().take(2).eq(['.', '.']) => {
+                    chars.next();
+                    chars.next();
+                    '\u{2026}'
+                }
+                other => other,
+            };
+            out.push(replacement);
+            self.prev = Some(replacement);
+        }
+        out
     }
+}
 
-    fn transform_html<F>(self, f: F) -> Self
-    where
-        F: Fn(String) -> String,
-    {
-        let mut transformer = HtmlTransformer::new(f);
-        transformer.transform_document(self)
+impl Transformer for SmartPunctuation {
+    fn transform_block(&mut self, block: Block) -> Block {
+        self.reset();
+        self.walk_transform_block(block)
     }
 
-    fn transform_with<T: Transformer>(self, mut transformer: T) -> Self {
-        transformer.transform_document(self)
+    fn transform_table_cell(&mut self, cell: TableCell) -> TableCell {
+        self.reset();
+        self.walk_transform_table_cell(cell)
     }
 
-    fn transform_if_doc<P, F>(self, predicate: P, transform: F) -> Self
-    where
-        P: Fn(&Self) -> bool,
-        F: FnOnce(Self) -> Self,
-    {
-        if predicate(&self) {
-            transform(self)
-        } else {
-            self
+    fn transform_inline(&mut self, inline: Inline) -> Inline {
+        match &inline {
+            Inline::LineBreak | Inline::SoftBreak => self.prev = Some('\n'),
+            // Code is left alone but still precedes the next quote
+            Inline::Code(code) => self.prev = code.chars().last().or(self.prev),
+            _ => {}
         }
+        self.walk_transform_inline(inline)
+    }
+
+    fn transform_text(&mut self, text: String) -> String {
+        self.convert(&text)
     }
 }
 
-// Internal transformer implementations
+struct HeadingKindNormalizer {
+    target: HeadingStyle,
+}
 
-struct TextTransformer<F> {
-    func: F,
+impl Transformer for HeadingKindNormalizer {
+    fn transform_heading(&mut self, heading: Heading) -> Heading {
+        let mut heading = self.walk_transform_heading(heading);
+        let level = heading.level();
+        heading.kind = match (self.target, level) {
+            (HeadingStyle::Setext, 1) => HeadingKind::Setext(SetextHeading::Level1),
+            (HeadingStyle::Setext, 2) => HeadingKind::Setext(SetextHeading::Level2),
+            _ => HeadingKind::Atx(level),
+        };
+        heading
+    }
 }
 
-impl<F> TextTransformer<F>
-where
-    F: Fn(String) -> String,
-{
-    fn new(func: F) -> Self {
-        Self { func }
+struct ListMarkerNormalizer {
+    bullet: ListBulletKind,
+    ordered: Option<OrderedListStyle>,
+}
+
+impl Transformer for ListMarkerNormalizer {
+    fn transform_block(&mut self, block: Block) -> Block {
+        match self.walk_transform_block(block) {
+            Block::List(mut list) => {
+                match &mut list.kind {
+                    ListKind::Bullet(kind) => *kind = self.bullet,
+                    ListKind::Ordered(options) => {
+                        if let Some(style) = self.ordered {
+                            options.style = style;
+                        }
+                    }
+                }
+                Block::List(list)
+            }
+            other => other,
+        }
     }
 }
 
-impl<F> Transformer for TextTransformer<F>
-where
-    F: Fn(String) -> String,
-{
-    fn transform_inline(&mut self, inline: Inline) -> Inline {
-        match inline {
-            Inline::Text(text) => Inline::Text((self.func)(text)),
-            other => self.walk_transform_inline(other),
+struct SingleItemListCollapser {
+    ordered: bool,
+}
+
+impl Transformer for SingleItemListCollapser {
+    fn transform_block(&mut self, block: Block) -> Block {
+        match self.walk_transform_block(block) {
+            Block::List(mut list)
+                if (self.ordered || matches!(list.kind, ListKind::Bullet(_)))
+                    && matches!(
+                        list.items.as_slice(),
+                        [ListItem { task: None, blocks }]
+                            if matches!(blocks.as_slice(), [Block::Paragraph(_)])
+                    ) =>
+            {
+                list.items.remove(0).blocks.remove(0)
+            }
+            other => other,
         }
     }
 }
 
-struct ImageUrlTransformer<F> {
-    func: F,
+struct AdjacentBlockMerger;
+
+impl AdjacentBlockMerger {
+    fn merge(blocks: Vec<Block>) -> Vec<Block> {
+        let mut result: Vec<Block> = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            match (result.last_mut(), block) {
+                (Some(Block::BlockQuote(prev)), Block::BlockQuote(blocks)) => {
+                    prev.extend(blocks);
+                    // The end of one quote may now touch the start of the next
+                    *prev = Self::merge(std::mem::take(prev));
+                }
+                (
+                    Some(Block::CodeBlock(CodeBlock {
+                        kind: CodeBlockKind::Indented,
+                        literal: prev,
+                    })),
+                    Block::CodeBlock(CodeBlock {
+                        kind: CodeBlockKind::Indented,
+                        literal,
+                    }),
+                ) => {
+                    prev.push('\n');
+                    prev.push_str(&literal);
+                }
+                (_, block) => result.push(block),
+            }
+        }
+        result
+    }
 }
 
-impl<F> ImageUrlTransformer<F>
-where
-    F: Fn(String) -> String,
-{
-    fn new(func: F) -> Self {
-        Self { func }
+impl Transformer for AdjacentBlockMerger {
+    fn transform_document(&mut self, doc: Document) -> Document {
+        let mut doc = self.walk_transform_document(doc);
+        doc.blocks = Self::merge(doc.blocks);
+        doc
+    }
+
+    fn transform_block(&mut self, block: Block) -> Block {
+        match self.walk_transform_block(block) {
+            Block::BlockQuote(blocks) => Block::BlockQuote(Self::merge(blocks)),
+            Block::Container(mut container) => {
+                container.blocks = Self::merge(container.blocks);
+                Block::Container(container)
+            }
+            other => other,
+        }
+    }
+
+    fn transform_list_item(&mut self, item: ListItem) -> ListItem {
+        let mut item = self.walk_transform_list_item(item);
+        item.blocks = Self::merge(item.blocks);
+        item
+    }
+
+    fn transform_footnote_definition(
+        &mut self,
+        footnote: FootnoteDefinition,
+    ) -> FootnoteDefinition {
+        let mut footnote = self.walk_transform_footnote_definition(footnote);
+        footnote.blocks = Self::merge(footnote.blocks);
+        footnote
+    }
+
+    fn transform_github_alert(&mut self, alert: GitHubAlert) -> GitHubAlert {
+        let mut alert = self.walk_transform_github_alert(alert);
+        alert.blocks = Self::merge(alert.blocks);
+        alert
     }
 }
 
-impl<F> Transformer for ImageUrlTransformer<F>
+struct RegexReplacer<F> {
+    re: regex::Regex,
+    f: F,
+}
+
+impl<F> Transformer for RegexReplacer<F>
 where
-    F: Fn(String) -> String,
+    F: FnMut(&regex::Captures) -> Vec<Inline>,
 {
-    fn transform_inline(&mut self, inline: Inline) -> Inline {
+    fn expand_inline(&mut self, inline: Inline) -> Vec<Inline> {
         match inline {
-            Inline::Image(mut image) => {
-                image.destination = (self.func)(image.destination);
-                Inline::Image(image)
+            Inline::Text(text) => {
+                let mut result = Vec::new();
+                let mut last = 0;
+                for caps in self.re.captures_iter(&text) {
+                    let whole = caps.get(0).expect("group 0 always matches");
+                    if whole.start() > last {
+                        result.push(Inline::Text(text[last..whole.start()].to_string()));
+                    }
+                    result.extend((self.f)(&caps));
+                    last = whole.end();
+                }
+                if last < text.len() || result.is_empty() {
+                    result.push(Inline::Text(text[last..].to_string()));
+                }
+                result
             }
-            other => self.walk_transform_inline(other),
+            Inline::Link(_) | Inline::LinkReference(_) | Inline::Image(_) | Inline::Autolink(_) => {
+                vec![inline]
+            }
+            other => self.walk_expand_inline(other),
         }
     }
 }
 
-struct LinkUrlTransformer<F> {
-    func: F,
-}
+struct BareUrlLinker;
 
-impl<F> LinkUrlTransformer<F>
-where
-    F: Fn(String) -> String,
-{
-    fn new(func: F) -> Self {
-        Self { func }
+impl BareUrlLinker {
+    /// Length of `url` once trailing punctuation is dropped
+    fn trimmed_len(url: &str) -> usize {
+        let mut end = url.len();
+        loop {
+            let trimmed = &url[..end];
+            match trimmed.chars().last() {
+                Some('?' | '!' | '.' | ',' | ':' | ';' | '*' | '_' | '~' | '\'' | '"') => {
+                    end -= 1;
+                }
+                Some(')') if trimmed.matches(')').count() > trimmed.matches('(').count() => {
+                    end -= 1;
+                }
+                _ => return end,
+            }
+        }
+    }
+
+    fn link(url: &str) -> Inline {
+        if url.len() >= 4 && url[..4].eq_ignore_ascii_case("www.") {
+            Inline::Link(Link {
+                destination: format!("http://{url}"),
+                title: None,
+                children: vec![Inline::Text(url.to_string())],
+            })
+        } else {
+            Inline::Autolink(url.to_string())
+        }
     }
 }
 
-impl<F> Transformer for LinkUrlTransformer<F>
-where
-    F: Fn(String) -> String,
-{
-    fn transform_inline(&mut self, inline: Inline) -> Inline {
+impl Transformer for BareUrlLinker {
+    fn expand_inline(&mut self, inline: Inline) -> Vec<Inline> {
         match inline {
-            Inline::Link(mut link) => {
-                link.destination = (self.func)(link.destination);
-                link.children = link
-                    .children
-                    .into_iter()
-                    .map(|child| self.transform_inline(child))
-                    .collect();
-                Inline::Link(link)
+            Inline::Text(text) => {
+                let mut result = Vec::new();
+                let mut last = 0;
+                for m in BARE_URL_REGEX.find_iter(&text) {
+                    let url = &m.as_str()[..Self::trimmed_len(m.as_str())];
+                    let prefix_len = url.find("://").map_or(4, |i| i + 3);
+                    if url.len() <= prefix_len {
+                        continue;
+                    }
+                    if m.start() > last {
+                        result.push(Inline::Text(text[last..m.start()].to_string()));
+                    }
+                    result.push(Self::link(url));
+                    last = m.start() + url.len();
+                }
+                if last < text.len() || result.is_empty() {
+                    result.push(Inline::Text(text[last..].to_string()));
+                }
+                result
             }
-            other => self.walk_transform_inline(other),
+            Inline::Link(_) | Inline::LinkReference(_) | Inline::Image(_) | Inline::Autolink(_) => {
+                vec![inline]
+            }
+            other => self.walk_expand_inline(other),
         }
     }
 }
 
-struct AutolinkTransformer<F> {
-    func: F,
+struct ImageInliner<F> {
+    loader: F,
 }
 
-impl<F> AutolinkTransformer<F>
+impl<F> Transformer for ImageInliner<F>
 where
-    F: Fn(String) -> String,
+    F: FnMut(&str) -> Option<(String, Vec<u8>)>,
 {
-    fn new(func: F) -> Self {
-        Self { func }
+    fn transform_image(&mut self, mut image: Image) -> Image {
+        if let Some((mime, bytes)) = (self.loader)(&image.destination) {
+            image.destination = format!("data:{mime};base64,{}", base64_encode(&bytes));
+        }
+        image
     }
 }
 
-impl<F> Transformer for AutolinkTransformer<F>
-where
-    F: Fn(String) -> String,
-{
-    fn transform_inline(&mut self, inline: Inline) -> Inline {
-        match inline {
-            Inline::Autolink(url) => Inline::Autolink((self.func)(url)),
-            other => self.walk_transform_inline(other),
+/// Standard base64 encoding with padding (RFC 4648).
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
         }
     }
+    out
 }
 
-struct CodeTransformer<F> {
-    func: F,
+#[derive(Default)]
+struct ReferencedLabels {
+    labels: HashSet<String>,
 }
 
-impl<F> CodeTransformer<F>
-where
-    F: Fn(String) -> String,
-{
-    fn new(func: F) -> Self {
-        Self { func }
+impl Visitor for ReferencedLabels {
+    fn visit_inline(&mut self, inline: &Inline) {
+        if let Inline::LinkReference(link_ref) = inline {
+            self.labels.insert(label_key(&link_ref.label));
+        }
+        self.walk_inline(inline);
     }
 }
 
-impl<F> Transformer for CodeTransformer<F>
-where
-    F: Fn(String) -> String,
-{
-    fn transform_inline(&mut self, inline: Inline) -> Inline {
-        match inline {
-            Inline::Code(code) => Inline::Code((self.func)(code)),
-            other => self.walk_transform_inline(other),
+#[derive(Default)]
+struct FootnoteCollector {
+    definitions: Vec<FootnoteDefinition>,
+}
+
+impl Transformer for FootnoteCollector {
+    fn expand_block(&mut self, block: Block) -> Vec<Block> {
+        match block {
+            Block::FootnoteDefinition(mut def) => {
+                def.blocks = def
+                    .blocks
+                    .into_iter()
+                    .flat_map(|block| self.expand_block(block))
+                    .collect();
+                self.definitions.push(def);
+                vec![]
+            }
+            other => self.walk_expand_block(other),
         }
     }
 }
 
-struct HtmlTransformer<F> {
-    func: F,
+/// Footnote labels in order of first reference
+#[derive(Default)]
+struct FootnoteReferences {
+    labels: Vec<String>,
+    seen: HashSet<String>,
 }
 
-impl<F> HtmlTransformer<F>
-where
-    F: Fn(String) -> String,
-{
-    fn new(func: F) -> Self {
-        Self { func }
+impl Visitor for FootnoteReferences {
+    fn visit_inline(&mut self, inline: &Inline) {
+        if let Inline::FootnoteReference(label) = inline {
+            if self.seen.insert(label.to_lowercase()) {
+                self.labels.push(label.clone());
+            }
+        }
+        self.walk_inline(inline);
     }
 }
 
-impl<F> Transformer for HtmlTransformer<F>
-where
-    F: Fn(String) -> String,
-{
-    fn transform_inline(&mut self, inline: Inline) -> Inline {
-        match inline {
-            Inline::Html(html) => Inline::Html((self.func)(html)),
-            other => self.walk_transform_inline(other),
+struct DefinitionDeduper {
+    referenced: HashSet<String>,
+    seen: HashMap<String, String>,
+    strict: bool,
+    conflict: Option<DefinitionConflict>,
+}
+
+impl DefinitionDeduper {
+    fn new(doc: &Document, strict: bool) -> Self {
+        let mut references = ReferencedLabels::default();
+        doc.visit_with(&mut references);
+        Self {
+            referenced: references.labels,
+            seen: HashMap::new(),
+            strict,
+            conflict: None,
         }
     }
+}
 
-    fn transform_block(&mut self, block: Block) -> Block {
+impl Transformer for DefinitionDeduper {
+    fn expand_block(&mut self, block: Block) -> Vec<Block> {
         match block {
-            Block::HtmlBlock(html) => Block::HtmlBlock((self.func)(html)),
-            other => self.walk_transform_block(other),
+            Block::Definition(def) => {
+                let key = label_key(&def.label);
+                if !self.referenced.contains(&key) {
+                    return vec![];
+                }
+                match self.seen.get(&key) {
+                    Some(first) => {
+                        if self.strict && *first != def.destination && self.conflict.is_none() {
+                            self.conflict = Some(DefinitionConflict {
+                                label: key,
+                                first: first.clone(),
+                                second: def.destination,
+                            });
+                        }
+                        vec![]
+                    }
+                    None => {
+                        self.seen.insert(key, def.destination.clone());
+                        vec![Block::Definition(def)]
+                    }
+                }
+            }
+            other => self.walk_expand_block(other),
         }
     }
 }
@@ -359,13 +2566,35 @@ pub trait FilterTransform {
     /// Remove empty text elements
     fn remove_empty_text(self) -> Self;
 
-    /// Normalize whitespace in text elements
+    /// Normalize whitespace in text elements, keeping non-breaking spaces
     fn normalize_whitespace(self) -> Self;
 
     /// Remove specific block types
     fn filter_blocks<F>(self, predicate: F) -> Self
     where
         F: Fn(&Block) -> bool;
+
+    /// Remove empty blockquotes, containers, GitHub alerts and list items
+    ///
+    /// Works bottom-up, so a blockquote that only held an empty container is
+    /// removed as well, and lists left without items are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::ast_transform::FilterTransform;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![
+    ///         Block::BlockQuote(vec![]),
+    ///         Block::Paragraph(vec![Inline::Text("kept".to_string())]),
+    ///     ],
+    /// };
+    /// let doc = doc.prune_empty();
+    /// assert_eq!(doc.blocks.len(), 1);
+    /// ```
+    fn prune_empty(self) -> Self;
 }
 
 impl FilterTransform for Document {
@@ -381,7 +2610,12 @@ impl FilterTransform for Document {
     }
 
     fn normalize_whitespace(self) -> Self {
-        self.transform_text(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+        self.transform_text(|text| {
+            text.split(is_collapsible_space)
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
     }
 
     fn filter_blocks<F>(mut self, predicate: F) -> Self
@@ -391,6 +2625,11 @@ impl FilterTransform for Document {
         self.blocks.retain(|block| predicate(block));
         self
     }
+
+    fn prune_empty(self) -> Self {
+        let mut transformer = EmptyContainerPruner;
+        transformer.expand_document(self).remove(0)
+    }
 }
 
 struct EmptyTextRemover;
@@ -398,8 +2637,34 @@ struct EmptyTextRemover;
 impl Transformer for EmptyTextRemover {
     fn transform_inline(&mut self, inline: Inline) -> Inline {
         match inline {
-            Inline::Text(text) if text.trim().is_empty() => Inline::Empty,
+            Inline::Text(text) if text.trim_matches(is_collapsible_space).is_empty() => {
+                Inline::Empty
+            }
             other => self.walk_transform_inline(other),
         }
     }
 }
+
+struct EmptyContainerPruner;
+
+impl Transformer for EmptyContainerPruner {
+    fn expand_block(&mut self, block: Block) -> Vec<Block> {
+        self.walk_expand_block(block)
+            .into_iter()
+            .filter(|block| match block {
+                Block::BlockQuote(blocks) => !blocks.is_empty(),
+                Block::Container(container) => !container.blocks.is_empty(),
+                Block::GitHubAlert(alert) => !alert.blocks.is_empty(),
+                Block::List(list) => !list.items.is_empty(),
+                _ => true,
+            })
+            .collect()
+    }
+
+    fn expand_list_item(&mut self, item: ListItem) -> Vec<ListItem> {
+        self.walk_expand_list_item(item)
+            .into_iter()
+            .filter(|item| !item.blocks.is_empty())
+            .collect()
+    }
+}
@@ -42,7 +42,10 @@
 //! }
 //! ```
 
+use crate::ast::convert::StripData;
 use crate::ast::generic::*;
+use crate::ast::{inline_text, slugify, SlugOptions};
+use std::collections::HashMap;
 
 /// Generic transformer trait for AST nodes with user data
 ///
@@ -255,6 +258,36 @@ pub trait GenericTransformer<T: Default> {
                     .collect(),
                 user_data,
             },
+            Inline::Highlight { content, user_data } => Inline::Highlight {
+                content: content
+                    .into_iter()
+                    .map(|inline| self.transform_inline(inline))
+                    .collect(),
+                user_data,
+            },
+            Inline::Subscript { content, user_data } => Inline::Subscript {
+                content: content
+                    .into_iter()
+                    .map(|inline| self.transform_inline(inline))
+                    .collect(),
+                user_data,
+            },
+            Inline::Superscript { content, user_data } => Inline::Superscript {
+                content: content
+                    .into_iter()
+                    .map(|inline| self.transform_inline(inline))
+                    .collect(),
+                user_data,
+            },
+            Inline::Delimited {
+                delimiter,
+                inner,
+                user_data,
+            } => Inline::Delimited {
+                delimiter,
+                inner: Box::new(self.transform_inline(*inner)),
+                user_data,
+            },
             Inline::Link(link) => Inline::Link(self.transform_link(link)),
             Inline::LinkReference(mut link_ref) => {
                 link_ref.label = link_ref
@@ -481,6 +514,43 @@ pub trait GenericTransformer<T: Default> {
                     .collect(),
                 user_data,
             },
+            Inline::Highlight { content, user_data } => Inline::Highlight {
+                content: content
+                    .into_iter()
+                    .flat_map(|inline| self.walk_expand_inline(inline))
+                    .collect(),
+                user_data,
+            },
+            Inline::Subscript { content, user_data } => Inline::Subscript {
+                content: content
+                    .into_iter()
+                    .flat_map(|inline| self.walk_expand_inline(inline))
+                    .collect(),
+                user_data,
+            },
+            Inline::Superscript { content, user_data } => Inline::Superscript {
+                content: content
+                    .into_iter()
+                    .flat_map(|inline| self.walk_expand_inline(inline))
+                    .collect(),
+                user_data,
+            },
+            Inline::Delimited {
+                delimiter,
+                inner,
+                user_data,
+            } => {
+                let mut inlines = self.walk_expand_inline(*inner);
+                // The delimiters only describe a single node
+                if inlines.len() != 1 {
+                    return inlines;
+                }
+                Inline::Delimited {
+                    delimiter,
+                    inner: Box::new(inlines.remove(0)),
+                    user_data,
+                }
+            }
             Inline::Link(link) => {
                 let expanded_links = self.expand_link(link);
                 return expanded_links.into_iter().map(Inline::Link).collect();
@@ -580,3 +650,105 @@ impl<T: Default> GenericExpandWith<T> for Inline<T> {
         transformer.walk_expand_inline(self)
     }
 }
+
+/// User data that can hold a heading slug, see
+/// [`AssignHeadingSlugs::assign_heading_slugs`]
+pub trait HeadingSlugData {
+    /// Store the slug computed for the heading this data belongs to
+    fn set_heading_slug(&mut self, slug: String);
+}
+
+impl HeadingSlugData for String {
+    fn set_heading_slug(&mut self, slug: String) {
+        *self = slug;
+    }
+}
+
+impl HeadingSlugData for Option<String> {
+    fn set_heading_slug(&mut self, slug: String) {
+        *self = Some(slug);
+    }
+}
+
+/// Extension trait storing heading slugs in the user data of a generic
+/// document
+pub trait AssignHeadingSlugs {
+    /// Store a slug for every heading in its `user_data`
+    ///
+    /// Slugs are built with [`slugify`] from the heading text, in document
+    /// order. A slug that was already used gets a `-1`, `-2`, ... suffix,
+    /// as GitHub does. Headings without any text to build a slug from keep
+    /// their data unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::generic::*;
+    /// use markdown_ppp::ast::HeadingKind;
+    /// use markdown_ppp::ast_transform::AssignHeadingSlugs;
+    ///
+    /// let heading = || {
+    ///     Block::Heading(Heading {
+    ///         kind: HeadingKind::Atx(2),
+    ///         content: vec![Inline::Text {
+    ///             content: "Usage".to_string(),
+    ///             user_data: None,
+    ///         }],
+    ///         user_data: None,
+    ///     })
+    /// };
+    /// let doc: Document<Option<String>> = Document {
+    ///     blocks: vec![heading(), heading()],
+    ///     user_data: None,
+    /// };
+    /// let slugs: Vec<_> = doc
+    ///     .assign_heading_slugs()
+    ///     .blocks
+    ///     .into_iter()
+    ///     .filter_map(|block| match block {
+    ///         Block::Heading(heading) => heading.user_data,
+    ///         _ => None,
+    ///     })
+    ///     .collect();
+    /// assert_eq!(slugs, vec!["usage", "usage-1"]);
+    /// ```
+    fn assign_heading_slugs(self) -> Self;
+}
+
+impl<T: Default + Clone + HeadingSlugData> AssignHeadingSlugs for Document<T> {
+    fn assign_heading_slugs(self) -> Self {
+        let mut assigner = HeadingSlugAssigner {
+            used: HashMap::new(),
+        };
+        assigner.transform_document(self)
+    }
+}
+
+struct HeadingSlugAssigner {
+    /// How many times each slug has been used so far
+    used: HashMap<String, usize>,
+}
+
+impl<T: Default + Clone + HeadingSlugData> GenericTransformer<T> for HeadingSlugAssigner {
+    fn transform_heading(&mut self, heading: Heading<T>) -> Heading<T> {
+        let mut heading = self.walk_transform_heading(heading);
+        let content: Vec<_> = heading
+            .content
+            .iter()
+            .cloned()
+            .map(StripData::strip_data)
+            .collect();
+        let slug = slugify(&inline_text(&content), SlugOptions::default());
+        if slug.is_empty() {
+            return heading;
+        }
+        let count = self.used.entry(slug.clone()).or_insert(0);
+        let slug = match *count {
+            0 => slug,
+            n => format!("{slug}-{n}"),
+        };
+        *count += 1;
+        heading.user_data.set_heading_slug(slug);
+        heading
+    }
+}
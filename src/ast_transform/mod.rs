@@ -40,6 +40,7 @@
 pub mod convenience;
 pub mod generic_transformer;
 pub mod macro_expansion;
+pub mod path_visitor;
 pub mod pipeline;
 pub mod query;
 pub mod transformer;
@@ -50,6 +51,7 @@ mod tests;
 
 pub use convenience::*;
 pub use generic_transformer::*;
+pub use path_visitor::*;
 pub use pipeline::*;
 pub use query::*;
 pub use transformer::*;
@@ -0,0 +1,254 @@
+//! Visitor that tracks the structural path to each node
+//!
+//! [`PathVisitor`] works like [`Visitor`](super::Visitor), but every callback
+//! also receives the [`NodePath`] from the document root to the node, such as
+//! `blocks[2].items[0].blocks[1]`. This gives tooling precise locations for
+//! diagnostics when no byte spans are available.
+//!
+//! # Example
+//!
+//! ```rust
+//! use markdown_ppp::ast::*;
+//! use markdown_ppp::ast_transform::{NodePath, PathVisitor, VisitWithPath};
+//!
+//! struct TextLocator {
+//!     found: Vec<String>,
+//! }
+//!
+//! impl PathVisitor for TextLocator {
+//!     fn visit_inline(&mut self, inline: &Inline, path: &mut NodePath) {
+//!         if let Inline::Text(_) = inline {
+//!             self.found.push(path.to_string());
+//!         }
+//!         self.walk_inline(inline, path);
+//!     }
+//! }
+//!
+//! let doc = Document {
+//!     blocks: vec![Block::Paragraph(vec![Inline::Text("hello".to_string())])],
+//! };
+//!
+//! let mut locator = TextLocator { found: Vec::new() };
+//! doc.visit_with_path(&mut locator);
+//! assert_eq!(locator.found, vec!["blocks[0].inlines[0]"]);
+//! ```
+
+use crate::ast::*;
+use std::fmt;
+
+/// One step of a [`NodePath`]: a child collection and an index into it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PathSegment {
+    /// Name of the child collection, e.g. `blocks`, `items` or `inlines`.
+    pub field: &'static str,
+
+    /// Index of the child within the collection.
+    pub index: usize,
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]", self.field, self.index)
+    }
+}
+
+/// Path from the document root to a node
+///
+/// Displayed as its segments joined by `.`, e.g. `blocks[2].items[0]`. The
+/// path of the document itself is empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct NodePath {
+    segments: Vec<PathSegment>,
+}
+
+impl NodePath {
+    /// Create an empty path pointing at the document root
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Segments from the root to the node
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Descend into child `index` of collection `field`
+    pub fn push(&mut self, field: &'static str, index: usize) {
+        self.segments.push(PathSegment { field, index });
+    }
+
+    /// Return to the parent node
+    pub fn pop(&mut self) -> Option<PathSegment> {
+        self.segments.pop()
+    }
+}
+
+impl fmt::Display for NodePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{segment}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Visitor trait for read-only traversal with node paths
+///
+/// The default `walk_*` methods push a segment before visiting each child
+/// and pop it afterwards, so `path` always points at the node being
+/// visited. Overrides must leave the path as they found it.
+pub trait PathVisitor {
+    /// Visit a document node
+    fn visit_document(&mut self, doc: &Document, path: &mut NodePath) {
+        self.walk_document(doc, path);
+    }
+
+    /// Visit a block node
+    fn visit_block(&mut self, block: &Block, path: &mut NodePath) {
+        self.walk_block(block, path);
+    }
+
+    /// Visit an inline node
+    fn visit_inline(&mut self, inline: &Inline, path: &mut NodePath) {
+        self.walk_inline(inline, path);
+    }
+
+    /// Visit a list item
+    fn visit_list_item(&mut self, item: &ListItem, path: &mut NodePath) {
+        self.walk_list_item(item, path);
+    }
+
+    /// Visit a table row
+    fn visit_table_row(&mut self, row: &TableRow, path: &mut NodePath) {
+        self.walk_table_row(row, path);
+    }
+
+    /// Visit a table cell
+    fn visit_table_cell(&mut self, cell: &TableCell, path: &mut NodePath) {
+        self.walk_table_cell(cell, path);
+    }
+
+    /// Visit each block of `blocks` under the `field` segment
+    fn visit_blocks(&mut self, field: &'static str, blocks: &[Block], path: &mut NodePath) {
+        for (index, block) in blocks.iter().enumerate() {
+            path.push(field, index);
+            self.visit_block(block, path);
+            path.pop();
+        }
+    }
+
+    /// Visit each inline of `inlines` under the `field` segment
+    fn visit_inlines(&mut self, field: &'static str, inlines: &[Inline], path: &mut NodePath) {
+        for (index, inline) in inlines.iter().enumerate() {
+            path.push(field, index);
+            self.visit_inline(inline, path);
+            path.pop();
+        }
+    }
+
+    /// Default traversal for document
+    fn walk_document(&mut self, doc: &Document, path: &mut NodePath) {
+        self.visit_blocks("blocks", &doc.blocks, path);
+    }
+
+    /// Default traversal for block nodes
+    fn walk_block(&mut self, block: &Block, path: &mut NodePath) {
+        match block {
+            Block::Paragraph(inlines) => self.visit_inlines("inlines", inlines, path),
+            Block::Heading(heading) => self.visit_inlines("content", &heading.content, path),
+            Block::BlockQuote(blocks) => self.visit_blocks("blocks", blocks, path),
+            Block::List(list) => {
+                for (index, item) in list.items.iter().enumerate() {
+                    path.push("items", index);
+                    self.visit_list_item(item, path);
+                    path.pop();
+                }
+            }
+            Block::Table(table) => {
+                for (index, row) in table.rows.iter().enumerate() {
+                    path.push("rows", index);
+                    self.visit_table_row(row, path);
+                    path.pop();
+                }
+            }
+            Block::FootnoteDefinition(footnote) => {
+                self.visit_blocks("blocks", &footnote.blocks, path)
+            }
+            Block::GitHubAlert(alert) => self.visit_blocks("blocks", &alert.blocks, path),
+            Block::Definition(def) => self.visit_inlines("label", &def.label, path),
+            Block::Container(container) => self.visit_blocks("blocks", &container.blocks, path),
+            // Terminal nodes - no traversal needed
+            Block::CodeBlock(_)
+            | Block::ThematicBreak
+            | Block::HtmlBlock(_)
+            | Block::Empty
+            | Block::LatexBlock(_)
+            | Block::MacroBlock(_) => {}
+        }
+    }
+
+    /// Default traversal for inline nodes
+    fn walk_inline(&mut self, inline: &Inline, path: &mut NodePath) {
+        match inline {
+            Inline::Emphasis(inlines)
+            | Inline::Strong(inlines)
+            | Inline::Strikethrough(inlines)
+            | Inline::Highlight(inlines)
+            | Inline::Subscript(inlines)
+            | Inline::Superscript(inlines) => self.visit_inlines("children", inlines, path),
+            Inline::Delimited(delimited) => {
+                self.visit_inlines("inner", std::slice::from_ref(&delimited.inner), path)
+            }
+            Inline::Link(link) => self.visit_inlines("children", &link.children, path),
+            Inline::LinkReference(link_ref) => {
+                self.visit_inlines("label", &link_ref.label, path);
+                self.visit_inlines("text", &link_ref.text, path);
+            }
+            // Terminal nodes - no traversal needed
+            Inline::Text(_)
+            | Inline::Image(_)
+            | Inline::LineBreak
+            | Inline::SoftBreak
+            | Inline::Code(_)
+            | Inline::Html(_)
+            | Inline::Autolink(_)
+            | Inline::FootnoteReference(_)
+            | Inline::Latex(_)
+            | Inline::Empty => {}
+        }
+    }
+
+    /// Default traversal for list items
+    fn walk_list_item(&mut self, item: &ListItem, path: &mut NodePath) {
+        self.visit_blocks("blocks", &item.blocks, path);
+    }
+
+    /// Default traversal for table rows
+    fn walk_table_row(&mut self, row: &TableRow, path: &mut NodePath) {
+        for (index, cell) in row.iter().enumerate() {
+            path.push("cells", index);
+            self.visit_table_cell(cell, path);
+            path.pop();
+        }
+    }
+
+    /// Default traversal for table cells
+    fn walk_table_cell(&mut self, cell: &TableCell, path: &mut NodePath) {
+        self.visit_inlines("content", &cell.content, path);
+    }
+}
+
+/// Extension trait for visiting documents with node paths
+pub trait VisitWithPath {
+    /// Apply a path visitor to this node, starting from an empty path
+    fn visit_with_path<V: PathVisitor>(&self, visitor: &mut V);
+}
+
+impl VisitWithPath for Document {
+    fn visit_with_path<V: PathVisitor>(&self, visitor: &mut V) {
+        visitor.visit_document(self, &mut NodePath::new());
+    }
+}
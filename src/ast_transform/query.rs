@@ -376,11 +376,19 @@ fn collect_inlines_from_inline<'a, F>(
     }
 
     match inline {
-        Inline::Emphasis(inlines) | Inline::Strong(inlines) | Inline::Strikethrough(inlines) => {
+        Inline::Emphasis(inlines)
+        | Inline::Strong(inlines)
+        | Inline::Strikethrough(inlines)
+        | Inline::Highlight(inlines)
+        | Inline::Subscript(inlines)
+        | Inline::Superscript(inlines) => {
             for inline in inlines {
                 collect_inlines_from_inline(inline, predicate, results);
             }
         }
+        Inline::Delimited(delimited) => {
+            collect_inlines_from_inline(&delimited.inner, predicate, results);
+        }
         Inline::Link(link) => {
             for inline in &link.children {
                 collect_inlines_from_inline(inline, predicate, results);
@@ -514,13 +522,23 @@ where
     }
 
     match inline {
-        Inline::Emphasis(inlines) | Inline::Strong(inlines) | Inline::Strikethrough(inlines) => {
+        Inline::Emphasis(inlines)
+        | Inline::Strong(inlines)
+        | Inline::Strikethrough(inlines)
+        | Inline::Highlight(inlines)
+        | Inline::Subscript(inlines)
+        | Inline::Superscript(inlines) => {
             for inline in inlines {
                 if let Some(found) = find_first_inline_in_inline(inline, predicate) {
                     return Some(found);
                 }
             }
         }
+        Inline::Delimited(delimited) => {
+            if let Some(found) = find_first_inline_in_inline(&delimited.inner, predicate) {
+                return Some(found);
+            }
+        }
         Inline::Link(link) => {
             for inline in &link.children {
                 if let Some(found) = find_first_inline_in_inline(inline, predicate) {
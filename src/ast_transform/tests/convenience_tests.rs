@@ -1,5 +1,8 @@
 use crate::ast::*;
-use crate::ast_transform::{FilterTransform, Transform, Transformer};
+use crate::ast_transform::{
+    DefinitionConflict, ExcerptOptions, FilterTransform, HeadingOverflow, HeadingStyle, Query,
+    Transform, Transformer, UnreferencedFootnotes, UrlNormOptions,
+};
 
 // Helper function to create a document for testing
 fn create_test_doc() -> Document {
@@ -136,7 +139,10 @@ fn test_transform_html() {
                 Inline::Html("<em>inline HTML</em>".to_string()),
                 Inline::Text(" here.".to_string()),
             ]),
-            Block::HtmlBlock("<div class=\"content\">Block HTML</div>".to_string()),
+            Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<div class=\"content\">Block HTML</div>".to_string(),
+            }),
         ],
     };
 
@@ -152,7 +158,7 @@ fn test_transform_html() {
     // Check HTML block
     if let Block::HtmlBlock(html) = &result.blocks[1] {
         assert_eq!(
-            html,
+            html.content,
             "<!-- Processed --><div class=\"content\">Block HTML</div>"
         );
     }
@@ -355,3 +361,1395 @@ fn test_transform_image_urls_in_container() {
         panic!("Expected Block::Container");
     }
 }
+
+#[test]
+fn test_excerpt_max_blocks() {
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![Inline::Text("one".to_string())]),
+            Block::Paragraph(vec![Inline::Text("two".to_string())]),
+            Block::Paragraph(vec![Inline::Text("three".to_string())]),
+        ],
+    };
+
+    let result = doc.excerpt(ExcerptOptions {
+        max_blocks: Some(2),
+        more_marker: None,
+    });
+
+    assert_eq!(
+        result.blocks,
+        vec![
+            Block::Paragraph(vec![Inline::Text("one".to_string())]),
+            Block::Paragraph(vec![Inline::Text("two".to_string())]),
+        ]
+    );
+}
+
+#[test]
+fn test_excerpt_more_marker_wins_over_max_blocks() {
+    let input = "intro\n\nsecond\n\n<!-- more -->\n\nhidden\n";
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+
+    let result = doc.excerpt(ExcerptOptions {
+        max_blocks: Some(1),
+        more_marker: Some("<!-- more -->".to_string()),
+    });
+
+    assert_eq!(
+        result.blocks,
+        vec![
+            Block::Paragraph(vec![Inline::Text("intro".to_string())]),
+            Block::Paragraph(vec![Inline::Text("second".to_string())]),
+        ]
+    );
+}
+
+#[test]
+fn test_rebase_urls() {
+    let link = |destination: &str| {
+        Inline::Link(Link {
+            destination: destination.to_string(),
+            title: None,
+            children: vec![Inline::Text("link".to_string())],
+        })
+    };
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![
+                link("/a/b"),
+                link("./c"),
+                link("#frag"),
+                link("https://other.com"),
+                Inline::Image(Image {
+                    destination: "../img/foo.png?v=1".to_string(),
+                    title: None,
+                    alt: "foo".to_string(),
+                    attr: None,
+                }),
+            ]),
+            Block::Definition(LinkDefinition {
+                label: vec![Inline::Text("ref".to_string())],
+                destination: "d".to_string(),
+                title: None,
+            }),
+        ],
+    };
+
+    let result = doc.rebase_urls("https://example.com/docs/page/");
+
+    assert_eq!(
+        result.blocks,
+        vec![
+            Block::Paragraph(vec![
+                link("https://example.com/a/b"),
+                link("https://example.com/docs/page/c"),
+                link("#frag"),
+                link("https://other.com"),
+                Inline::Image(Image {
+                    destination: "https://example.com/docs/img/foo.png?v=1".to_string(),
+                    title: None,
+                    alt: "foo".to_string(),
+                    attr: None,
+                }),
+            ]),
+            Block::Definition(LinkDefinition {
+                label: vec![Inline::Text("ref".to_string())],
+                destination: "https://example.com/docs/page/d".to_string(),
+                title: None,
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_smart_punctuation_quotes_across_inlines() {
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![
+            Inline::Text("She said \"it's ".to_string()),
+            Inline::Emphasis(vec![Inline::Text("fine".to_string())]),
+            Inline::Text("\" and 'left'. ".to_string()),
+            Inline::Code("\"raw\"".to_string()),
+        ])],
+    };
+
+    let result = doc.smart_punctuation();
+
+    assert_eq!(
+        result.blocks,
+        vec![Block::Paragraph(vec![
+            Inline::Text("She said \u{201C}it\u{2019}s ".to_string()),
+            Inline::Emphasis(vec![Inline::Text("fine".to_string())]),
+            Inline::Text("\u{201D} and \u{2018}left\u{2019}. ".to_string()),
+            Inline::Code("\"raw\"".to_string()),
+        ])]
+    );
+}
+
+#[test]
+fn test_smart_punctuation_apostrophe_after_code() {
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![
+            Inline::Text("Use ".to_string()),
+            Inline::Code("x".to_string()),
+            Inline::Text("'s value".to_string()),
+        ])],
+    };
+
+    let result = doc.smart_punctuation();
+
+    assert_eq!(
+        result.blocks,
+        vec![Block::Paragraph(vec![
+            Inline::Text("Use ".to_string()),
+            Inline::Code("x".to_string()),
+            Inline::Text("\u{2019}s value".to_string()),
+        ])]
+    );
+}
+
+#[test]
+fn test_smart_punctuation_dashes_and_ellipsis() {
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![Inline::Text(
+            "pages 1--2 --- wait... done".to_string(),
+        )])],
+    };
+
+    let result = doc.smart_punctuation();
+
+    assert_eq!(
+        result.blocks,
+        vec![Block::Paragraph(vec![Inline::Text(
+            "pages 1\u{2013}2 \u{2014} wait\u{2026} done".to_string()
+        )])]
+    );
+}
+
+fn definition(label: &str, destination: &str) -> Block {
+    Block::Definition(LinkDefinition {
+        label: vec![Inline::Text(label.to_string())],
+        destination: destination.to_string(),
+        title: None,
+    })
+}
+
+fn reference(label: &str) -> Inline {
+    Inline::LinkReference(LinkReference {
+        label: vec![Inline::Text(label.to_string())],
+        text: vec![Inline::Text(label.to_string())],
+    })
+}
+
+#[test]
+fn test_dedupe_definitions_identical_duplicates() {
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![reference("docs")]),
+            definition("docs", "/docs"),
+            Block::BlockQuote(vec![definition("DOCS", "/docs")]),
+        ],
+    };
+
+    let result = doc.try_dedupe_definitions().unwrap();
+
+    assert_eq!(
+        result.blocks,
+        vec![
+            Block::Paragraph(vec![reference("docs")]),
+            definition("docs", "/docs"),
+            Block::BlockQuote(vec![]),
+        ]
+    );
+}
+
+#[test]
+fn test_dedupe_definitions_conflicting_destinations() {
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![reference("docs")]),
+            definition("docs", "/first"),
+            definition("docs", "/second"),
+        ],
+    };
+
+    assert_eq!(
+        doc.clone().try_dedupe_definitions(),
+        Err(DefinitionConflict {
+            label: "docs".to_string(),
+            first: "/first".to_string(),
+            second: "/second".to_string(),
+        })
+    );
+
+    // The lenient variant keeps the first definition
+    assert_eq!(
+        doc.dedupe_definitions().blocks,
+        vec![
+            Block::Paragraph(vec![reference("docs")]),
+            definition("docs", "/first"),
+        ]
+    );
+}
+
+#[test]
+fn test_dedupe_definitions_removes_unreferenced() {
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![reference("used")]),
+            definition("used", "/used"),
+            definition("unused", "/unused"),
+        ],
+    };
+
+    let result = doc.dedupe_definitions();
+
+    assert_eq!(
+        result.blocks,
+        vec![
+            Block::Paragraph(vec![reference("used")]),
+            definition("used", "/used"),
+        ]
+    );
+}
+
+#[test]
+fn test_inline_images() {
+    let image = |destination: &str| {
+        Inline::Image(Image {
+            destination: destination.to_string(),
+            title: None,
+            alt: "img".to_string(),
+            attr: None,
+        })
+    };
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![
+            image("local.png"),
+            image("https://example.com/remote.png"),
+        ])],
+    };
+
+    let mut requested = Vec::new();
+    let result = doc.inline_images(|destination| {
+        requested.push(destination.to_string());
+        (destination == "local.png").then(|| ("image/png".to_string(), b"hello".to_vec()))
+    });
+
+    assert_eq!(
+        requested,
+        vec!["local.png", "https://example.com/remote.png"]
+    );
+    assert_eq!(
+        result.blocks,
+        vec![Block::Paragraph(vec![
+            image("data:image/png;base64,aGVsbG8="),
+            image("https://example.com/remote.png"),
+        ])]
+    );
+}
+
+#[test]
+fn test_split_by_heading() {
+    let input = "# Book\n\nintro\n\n## One\n\nfirst\n\n### Sub\n\nnested\n\nTwo\n---\n\nsecond\n\n## Three\n";
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+
+    let sections = doc.split_by_heading(2);
+
+    let block_counts: Vec<_> = sections.iter().map(|doc| doc.blocks.len()).collect();
+    assert_eq!(block_counts, vec![2, 4, 2, 1]);
+    assert!(matches!(
+        &sections[2].blocks[0],
+        Block::Heading(Heading {
+            kind: HeadingKind::Setext(SetextHeading::Level2),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_normalize_heading_kinds() {
+    let heading = |kind: HeadingKind| {
+        Block::Heading(Heading {
+            kind,
+            content: vec![Inline::Text("Title".to_string())],
+        })
+    };
+    let kinds = |doc: &Document| -> Vec<HeadingKind> {
+        doc.blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::Heading(h) => Some(h.kind.clone()),
+                Block::BlockQuote(inner) => match &inner[0] {
+                    Block::Heading(h) => Some(h.kind.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    };
+    let doc = Document {
+        blocks: vec![
+            heading(HeadingKind::Setext(SetextHeading::Level1)),
+            heading(HeadingKind::Atx(2)),
+            heading(HeadingKind::Setext(SetextHeading::Level2)),
+            heading(HeadingKind::Atx(3)),
+            Block::BlockQuote(vec![heading(HeadingKind::Atx(1))]),
+        ],
+    };
+
+    let atx = doc.clone().normalize_heading_kinds(HeadingStyle::Atx);
+    assert_eq!(
+        kinds(&atx),
+        vec![
+            HeadingKind::Atx(1),
+            HeadingKind::Atx(2),
+            HeadingKind::Atx(2),
+            HeadingKind::Atx(3),
+            HeadingKind::Atx(1),
+        ]
+    );
+
+    let setext = doc.normalize_heading_kinds(HeadingStyle::Setext);
+    assert_eq!(
+        kinds(&setext),
+        vec![
+            HeadingKind::Setext(SetextHeading::Level1),
+            HeadingKind::Setext(SetextHeading::Level2),
+            HeadingKind::Setext(SetextHeading::Level2),
+            HeadingKind::Atx(3),
+            HeadingKind::Setext(SetextHeading::Level1),
+        ]
+    );
+}
+
+#[test]
+fn test_replace_regex() {
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![
+                Inline::Text("Fixes #12 and #345.".to_string()),
+                Inline::Link(Link {
+                    destination: "https://example.com".to_string(),
+                    title: None,
+                    children: vec![Inline::Text("see #7".to_string())],
+                }),
+            ]),
+            Block::List(List {
+                kind: ListKind::Bullet(ListBulletKind::Dash),
+                items: vec![ListItem {
+                    task: None,
+                    blocks: vec![Block::Paragraph(vec![Inline::Emphasis(vec![
+                        Inline::Text("#9".to_string()),
+                    ])])],
+                }],
+                tight: true,
+            }),
+            Block::Container(Container {
+                kind: "note".to_string(),
+                params: vec![],
+                blocks: vec![Block::Paragraph(vec![Inline::Text("#5".to_string())])],
+            }),
+        ],
+    };
+
+    let issue_link = |number: &str| {
+        Inline::Link(Link {
+            destination: format!("https://github.com/o/r/issues/{number}"),
+            title: None,
+            children: vec![Inline::Text(format!("#{number}"))],
+        })
+    };
+    let result = doc.replace_regex(regex::Regex::new(r"#(\d+)").unwrap(), |caps| {
+        vec![issue_link(&caps[1])]
+    });
+
+    assert_eq!(
+        result.blocks[0],
+        Block::Paragraph(vec![
+            Inline::Text("Fixes ".to_string()),
+            issue_link("12"),
+            Inline::Text(" and ".to_string()),
+            issue_link("345"),
+            Inline::Text(".".to_string()),
+            Inline::Link(Link {
+                destination: "https://example.com".to_string(),
+                title: None,
+                children: vec![Inline::Text("see #7".to_string())],
+            }),
+        ])
+    );
+    let Block::List(list) = &result.blocks[1] else {
+        panic!("expected list");
+    };
+    assert_eq!(
+        list.items[0].blocks[0],
+        Block::Paragraph(vec![Inline::Emphasis(vec![issue_link("9")])])
+    );
+    let Block::Container(container) = &result.blocks[2] else {
+        panic!("expected container");
+    };
+    assert_eq!(
+        container.blocks,
+        vec![Block::Paragraph(vec![issue_link("5")])]
+    );
+}
+
+#[test]
+fn test_autolink_bare_urls() {
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![Inline::Text(
+                "Docs at https://example.com/docs (mirror www.example.org) are up.".to_string(),
+            )]),
+            Block::Paragraph(vec![Inline::Text(
+                "Read https://en.wikipedia.org/wiki/Rust_(programming_language).".to_string(),
+            )]),
+            Block::Paragraph(vec![Inline::Link(Link {
+                destination: "https://example.com".to_string(),
+                title: None,
+                children: vec![Inline::Text("https://example.com".to_string())],
+            })]),
+            Block::Container(Container {
+                kind: "note".to_string(),
+                params: vec![],
+                blocks: vec![Block::Paragraph(vec![Inline::Text(
+                    "See https://example.net".to_string(),
+                )])],
+            }),
+        ],
+    };
+
+    let result = doc.autolink_bare_urls();
+    assert_eq!(
+        result.blocks[0],
+        Block::Paragraph(vec![
+            Inline::Text("Docs at ".to_string()),
+            Inline::Autolink("https://example.com/docs".to_string()),
+            Inline::Text(" (mirror ".to_string()),
+            Inline::Link(Link {
+                destination: "http://www.example.org".to_string(),
+                title: None,
+                children: vec![Inline::Text("www.example.org".to_string())],
+            }),
+            Inline::Text(") are up.".to_string()),
+        ])
+    );
+    assert_eq!(
+        result.blocks[1],
+        Block::Paragraph(vec![
+            Inline::Text("Read ".to_string()),
+            Inline::Autolink(
+                "https://en.wikipedia.org/wiki/Rust_(programming_language)".to_string()
+            ),
+            Inline::Text(".".to_string()),
+        ])
+    );
+    assert_eq!(
+        result.blocks[2],
+        Block::Paragraph(vec![Inline::Link(Link {
+            destination: "https://example.com".to_string(),
+            title: None,
+            children: vec![Inline::Text("https://example.com".to_string())],
+        })])
+    );
+    assert_eq!(
+        result.blocks[3],
+        Block::Container(Container {
+            kind: "note".to_string(),
+            params: vec![],
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("See ".to_string()),
+                Inline::Autolink("https://example.net".to_string()),
+            ])],
+        })
+    );
+}
+
+#[test]
+fn test_normalize_urls() {
+    let link = |destination: &str| {
+        Inline::Link(Link {
+            destination: destination.to_string(),
+            title: None,
+            children: vec![Inline::Text("link".to_string())],
+        })
+    };
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![
+                link("HTTP://Example.COM:80/Path"),
+                link("https://Example.com:8443/A?Q=Value&utm_source=feed"),
+                link("../Relative/Path"),
+                Inline::Image(Image {
+                    destination: "HTTPS://CDN.example.com:443/Logo.PNG".to_string(),
+                    title: None,
+                    alt: "logo".to_string(),
+                    attr: None,
+                }),
+                Inline::Autolink("HTTP://EXAMPLE.ORG/?utm_medium=x".to_string()),
+            ]),
+            Block::Definition(LinkDefinition {
+                label: vec![Inline::Text("ref".to_string())],
+                destination: "HTTP://Example.COM:80/Path".to_string(),
+                title: None,
+            }),
+        ],
+    };
+
+    let plain = doc.clone().normalize_urls(UrlNormOptions::default());
+    let Block::Paragraph(inlines) = &plain.blocks[0] else {
+        panic!("expected paragraph");
+    };
+    assert_eq!(inlines[0], link("http://example.com/Path"));
+    assert_eq!(
+        inlines[1],
+        link("https://example.com:8443/A?Q=Value&utm_source=feed")
+    );
+    assert_eq!(inlines[2], link("../Relative/Path"));
+    assert!(matches!(
+        &inlines[3],
+        Inline::Image(image) if image.destination == "https://cdn.example.com/Logo.PNG"
+    ));
+    assert_eq!(
+        inlines[4],
+        Inline::Autolink("http://example.org/?utm_medium=x".to_string())
+    );
+    assert!(matches!(
+        &plain.blocks[1],
+        Block::Definition(def) if def.destination == "http://example.com/Path"
+    ));
+
+    let stripped = doc.normalize_urls(UrlNormOptions {
+        strip_tracking_params: true,
+    });
+    let Block::Paragraph(inlines) = &stripped.blocks[0] else {
+        panic!("expected paragraph");
+    };
+    assert_eq!(inlines[1], link("https://example.com:8443/A?Q=Value"));
+    assert_eq!(
+        inlines[4],
+        Inline::Autolink("http://example.org/".to_string())
+    );
+}
+
+#[test]
+fn test_flatten_emphasis() {
+    let text = || vec![Inline::Text("word".to_string())];
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![Inline::Strong(vec![Inline::Strong(vec![
+                Inline::Strong(text()),
+            ])])]),
+            Block::Heading(Heading {
+                kind: HeadingKind::Atx(1),
+                content: vec![Inline::Emphasis(vec![Inline::Emphasis(text())])],
+            }),
+            Block::Paragraph(vec![
+                Inline::Strong(vec![Inline::Emphasis(text())]),
+                Inline::Strikethrough(vec![Inline::Strikethrough(text())]),
+                Inline::Strong(vec![
+                    Inline::Strong(text()),
+                    Inline::Text(" and more".to_string()),
+                ]),
+            ]),
+        ],
+    };
+
+    let result = doc.flatten_emphasis();
+    assert_eq!(
+        result.blocks[0],
+        Block::Paragraph(vec![Inline::Strong(text())])
+    );
+    assert_eq!(
+        result.blocks[1],
+        Block::Heading(Heading {
+            kind: HeadingKind::Atx(1),
+            content: vec![Inline::Emphasis(text())],
+        })
+    );
+    assert_eq!(
+        result.blocks[2],
+        Block::Paragraph(vec![
+            Inline::Strong(vec![Inline::Emphasis(text())]),
+            Inline::Strikethrough(text()),
+            Inline::Strong(vec![
+                Inline::Strong(text()),
+                Inline::Text(" and more".to_string()),
+            ]),
+        ])
+    );
+}
+
+#[test]
+fn test_clamp_heading_levels() {
+    let heading = |kind: HeadingKind, text: &str| {
+        Block::Heading(Heading {
+            kind,
+            content: vec![Inline::Text(text.to_string())],
+        })
+    };
+    let doc = Document {
+        blocks: vec![
+            heading(HeadingKind::Atx(2), "Two"),
+            heading(HeadingKind::Atx(3), "Three"),
+            heading(HeadingKind::Atx(5), "Five"),
+            Block::BlockQuote(vec![heading(HeadingKind::Atx(6), "Quoted")]),
+        ],
+    };
+
+    let clamped = doc.clone().clamp_heading_levels(3);
+    assert_eq!(
+        clamped.blocks,
+        vec![
+            heading(HeadingKind::Atx(2), "Two"),
+            heading(HeadingKind::Atx(3), "Three"),
+            heading(HeadingKind::Atx(3), "Five"),
+            Block::BlockQuote(vec![heading(HeadingKind::Atx(3), "Quoted")]),
+        ]
+    );
+
+    let bold = doc.clamp_heading_levels_with(3, HeadingOverflow::BoldParagraph);
+    assert_eq!(
+        bold.blocks,
+        vec![
+            heading(HeadingKind::Atx(2), "Two"),
+            heading(HeadingKind::Atx(3), "Three"),
+            Block::Paragraph(vec![Inline::Strong(vec![Inline::Text("Five".to_string())])]),
+            Block::BlockQuote(vec![Block::Paragraph(vec![Inline::Strong(vec![
+                Inline::Text("Quoted".to_string())
+            ])])]),
+        ]
+    );
+
+    let setext = Document {
+        blocks: vec![heading(HeadingKind::Setext(SetextHeading::Level2), "Sub")],
+    }
+    .clamp_heading_levels(1);
+    assert_eq!(
+        setext.blocks,
+        vec![heading(HeadingKind::Setext(SetextHeading::Level1), "Sub")]
+    );
+}
+
+#[test]
+fn test_text_transforms_leave_code_untouched() {
+    let code = "\"https://example.com\" -- www.example.org...";
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![
+                Inline::Text("text ".to_string()),
+                Inline::Code(code.to_string()),
+            ]),
+            Block::CodeBlock(CodeBlock {
+                kind: CodeBlockKind::Fenced {
+                    info: Some("text".to_string()),
+                },
+                literal: code.to_string(),
+            }),
+            Block::List(List {
+                kind: ListKind::Bullet(ListBulletKind::Dash),
+                items: vec![ListItem {
+                    task: None,
+                    blocks: vec![Block::BlockQuote(vec![Block::CodeBlock(CodeBlock {
+                        kind: CodeBlockKind::Indented,
+                        literal: code.to_string(),
+                    })])],
+                }],
+                tight: true,
+            }),
+        ],
+    };
+    let code_only = |doc: &Document| {
+        let Block::Paragraph(inlines) = &doc.blocks[0] else {
+            panic!("expected paragraph");
+        };
+        (inlines[1].clone(), doc.blocks[1..].to_vec())
+    };
+    let expected = code_only(&doc);
+
+    let upper = doc.clone().transform_text(|s| s.to_uppercase());
+    assert_eq!(
+        upper.blocks[0],
+        Block::Paragraph(vec![
+            Inline::Text("TEXT ".to_string()),
+            Inline::Code(code.to_string()),
+        ])
+    );
+    assert_eq!(code_only(&upper), expected);
+
+    assert_eq!(code_only(&doc.clone().smart_punctuation()), expected);
+    assert_eq!(code_only(&doc.clone().autolink_bare_urls()), expected);
+    let replaced = doc.replace_regex(regex::Regex::new("example").unwrap(), |_| {
+        vec![Inline::Text("changed".to_string())]
+    });
+    assert_eq!(code_only(&replaced), expected);
+}
+
+#[test]
+fn test_collect_footnotes_to_end() {
+    let footnote = |label: &str, blocks: Vec<Block>| {
+        Block::FootnoteDefinition(FootnoteDefinition {
+            label: label.to_string(),
+            blocks,
+        })
+    };
+    let text = |t: &str| Block::Paragraph(vec![Inline::Text(t.to_string())]);
+    let doc = Document {
+        blocks: vec![
+            footnote("second", vec![text("Second note")]),
+            Block::Paragraph(vec![
+                Inline::Text("See".to_string()),
+                Inline::FootnoteReference("first".to_string()),
+                Inline::FootnoteReference("Second".to_string()),
+            ]),
+            footnote("orphan", vec![text("Never referenced")]),
+            Block::BlockQuote(vec![
+                text("Quoted"),
+                footnote(
+                    "first",
+                    vec![Block::Paragraph(vec![
+                        Inline::Text("First note".to_string()),
+                        Inline::FootnoteReference("nested".to_string()),
+                    ])],
+                ),
+            ]),
+            footnote("nested", vec![text("Nested note")]),
+            text("End"),
+        ],
+    };
+
+    let result = doc.clone().collect_footnotes_to_end();
+    let labels: Vec<_> = result
+        .blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::FootnoteDefinition(def) => Some(def.label.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(labels, vec!["first", "second", "nested", "orphan"]);
+    assert_eq!(result.blocks[1], Block::BlockQuote(vec![text("Quoted")]));
+    assert_eq!(result.blocks[2], text("End"));
+    assert!(result.blocks[3..]
+        .iter()
+        .all(|block| matches!(block, Block::FootnoteDefinition(_))));
+
+    let dropped = doc.collect_footnotes_to_end_with(UnreferencedFootnotes::Drop);
+    assert_eq!(dropped.blocks, result.blocks[..6]);
+}
+
+#[test]
+fn test_map_headings_with_level() {
+    let heading = |kind: HeadingKind, text: &str| {
+        Block::Heading(Heading {
+            kind,
+            content: vec![Inline::Text(text.to_string())],
+        })
+    };
+    let doc = Document {
+        blocks: vec![
+            heading(HeadingKind::Setext(SetextHeading::Level1), "Guide"),
+            heading(HeadingKind::Atx(3), "Install"),
+            Block::List(List {
+                kind: ListKind::Bullet(ListBulletKind::Dash),
+                items: vec![ListItem {
+                    task: None,
+                    blocks: vec![heading(
+                        HeadingKind::Setext(SetextHeading::Level2),
+                        "Nested",
+                    )],
+                }],
+                tight: true,
+            }),
+        ],
+    };
+
+    let result = doc.map_headings(|level, mut heading| {
+        heading.content.insert(0, Inline::Text(format!("{level} ")));
+        heading
+    });
+
+    let texts: Vec<_> = result
+        .find_all_blocks(|block| matches!(block, Block::Heading(_)))
+        .into_iter()
+        .map(|block| match block {
+            Block::Heading(h) => h.content.clone(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(
+        texts,
+        vec![
+            vec![
+                Inline::Text("1 ".to_string()),
+                Inline::Text("Guide".to_string())
+            ],
+            vec![
+                Inline::Text("3 ".to_string()),
+                Inline::Text("Install".to_string())
+            ],
+            vec![
+                Inline::Text("2 ".to_string()),
+                Inline::Text("Nested".to_string())
+            ],
+        ]
+    );
+}
+
+#[test]
+fn test_prune_empty() {
+    let text = |t: &str| Block::Paragraph(vec![Inline::Text(t.to_string())]);
+    let container = |blocks: Vec<Block>| {
+        Block::Container(Container {
+            kind: "note".to_string(),
+            params: vec![],
+            blocks,
+        })
+    };
+    let list = |items: Vec<Vec<Block>>| {
+        Block::List(List {
+            kind: ListKind::Bullet(ListBulletKind::Dash),
+            items: items
+                .into_iter()
+                .map(|blocks| ListItem { task: None, blocks })
+                .collect(),
+            tight: true,
+        })
+    };
+
+    let doc = Document {
+        blocks: vec![
+            Block::BlockQuote(vec![]),
+            text("a"),
+            list(vec![vec![text("item")], vec![]]),
+            list(vec![vec![]]),
+            Block::BlockQuote(vec![container(vec![container(vec![])])]),
+            container(vec![text("b"), container(vec![])]),
+        ],
+    };
+
+    assert_eq!(
+        doc.prune_empty().blocks,
+        vec![
+            text("a"),
+            list(vec![vec![text("item")]]),
+            container(vec![text("b")]),
+        ]
+    );
+}
+
+#[test]
+fn test_backslash_escapes() {
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![
+            Inline::Text(r"\*a\* \\ \q".to_string()),
+            Inline::Code(r"\*code\*".to_string()),
+        ])],
+    };
+
+    let unescaped = doc.normalize_backslash_escapes();
+    assert_eq!(
+        unescaped.blocks[0],
+        Block::Paragraph(vec![
+            Inline::Text(r"*a* \ \q".to_string()),
+            Inline::Code(r"\*code\*".to_string()),
+        ])
+    );
+
+    let escaped = unescaped.add_backslash_escapes();
+    assert_eq!(
+        escaped.blocks[0],
+        Block::Paragraph(vec![
+            Inline::Text(r"\*a\* \\ \\q".to_string()),
+            Inline::Code(r"\*code\*".to_string()),
+        ])
+    );
+
+    // Unescaping undoes escaping
+    let plain = Document {
+        blocks: vec![Block::Paragraph(vec![Inline::Text(
+            r"a*b_[c] <d> # e|f ~ \g".to_string(),
+        )])],
+    };
+    assert_eq!(
+        plain
+            .clone()
+            .add_backslash_escapes()
+            .normalize_backslash_escapes(),
+        plain
+    );
+}
+
+#[test]
+fn test_default_code_language() {
+    let code = |kind: CodeBlockKind| {
+        Block::CodeBlock(CodeBlock {
+            kind,
+            literal: "x".to_string(),
+        })
+    };
+    let fenced = |info: Option<&str>| {
+        code(CodeBlockKind::Fenced {
+            info: info.map(str::to_string),
+        })
+    };
+    let doc = Document {
+        blocks: vec![
+            fenced(None),
+            fenced(Some("  ")),
+            fenced(Some("rust")),
+            code(CodeBlockKind::Indented),
+            Block::BlockQuote(vec![fenced(None)]),
+        ],
+    };
+
+    assert_eq!(
+        doc.clone().default_code_language("text").blocks,
+        vec![
+            fenced(Some("text")),
+            fenced(Some("text")),
+            fenced(Some("rust")),
+            code(CodeBlockKind::Indented),
+            Block::BlockQuote(vec![fenced(Some("text"))]),
+        ]
+    );
+    assert_eq!(
+        doc.default_code_language_with("text", true).blocks,
+        vec![
+            fenced(Some("text")),
+            fenced(Some("text")),
+            fenced(Some("rust")),
+            fenced(Some("text")),
+            Block::BlockQuote(vec![fenced(Some("text"))]),
+        ]
+    );
+}
+
+#[test]
+fn test_alerts_to_blockquotes() {
+    let paragraph = |text: &str| Block::Paragraph(vec![Inline::Text(text.to_string())]);
+    let doc = Document {
+        blocks: vec![Block::List(List {
+            kind: ListKind::Bullet(ListBulletKind::Dash),
+            tight: true,
+            items: vec![ListItem {
+                task: None,
+                blocks: vec![Block::GitHubAlert(GitHubAlert {
+                    alert_type: GitHubAlertType::Note,
+                    blocks: vec![paragraph("First"), paragraph("Second")],
+                })],
+            }],
+        })],
+    };
+
+    let result = doc.alerts_to_blockquotes();
+    let Block::List(list) = &result.blocks[0] else {
+        panic!("expected list, got {:?}", result.blocks[0]);
+    };
+    assert_eq!(
+        list.items[0].blocks,
+        vec![Block::BlockQuote(vec![
+            Block::Paragraph(vec![Inline::Strong(vec![Inline::Text("Note".to_string())])]),
+            paragraph("First"),
+            paragraph("Second"),
+        ])]
+    );
+}
+
+#[test]
+fn test_number_figures() {
+    let input = r#"See [@fig:b], [@tbl:t] and [@fig:missing].
+
+:::figure{id="fig:a" caption="First"}
+![a](a.png)
+:::
+
+:::figure{id="fig:b" caption="Second"}
+![b](b.png)
+:::
+
+:::figure{id="tbl:t" caption="Numbers"}
+| n |
+|---|
+| 1 |
+:::
+"#;
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap()
+        .number_figures();
+
+    let mut text = String::new();
+    if let Block::Paragraph(inlines) = &doc.blocks[0] {
+        for inline in inlines {
+            match inline {
+                Inline::Text(t) => text.push_str(t),
+                // Unknown labels stay references
+                Inline::LinkReference(_) => text.push_str("<unresolved>"),
+                _ => {}
+            }
+        }
+    }
+    assert_eq!(text, "See 2, 1 and <unresolved>.");
+
+    let captions: Vec<_> = doc.blocks[1..]
+        .iter()
+        .filter_map(|block| match block {
+            Block::Container(container) => container
+                .params
+                .iter()
+                .find(|(key, _)| key == "caption")
+                .map(|(_, caption)| caption.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        captions,
+        vec!["Figure 1: First", "Figure 2: Second", "Table 1: Numbers"]
+    );
+}
+
+#[test]
+fn test_expand_tabs_in_code() {
+    let doc = Document {
+        blocks: vec![
+            Block::CodeBlock(CodeBlock {
+                kind: CodeBlockKind::Fenced { info: None },
+                literal: "\tx\na\tx\nabc\tx\nabcd\tx\n\t\tx".to_string(),
+            }),
+            Block::Paragraph(vec![
+                Inline::Text("keep\ttext".to_string()),
+                Inline::Code("ab\tc".to_string()),
+            ]),
+        ],
+    };
+
+    let result = doc.expand_tabs_in_code(4);
+    assert_eq!(
+        result.blocks[0],
+        Block::CodeBlock(CodeBlock {
+            kind: CodeBlockKind::Fenced { info: None },
+            literal: "    x\na   x\nabc x\nabcd    x\n        x".to_string(),
+        })
+    );
+    assert_eq!(
+        result.blocks[1],
+        Block::Paragraph(vec![
+            Inline::Text("keep\ttext".to_string()),
+            Inline::Code("ab  c".to_string()),
+        ])
+    );
+}
+
+#[test]
+fn test_flatten_heading_inlines() {
+    let doc = Document {
+        blocks: vec![
+            Block::Heading(Heading {
+                kind: HeadingKind::Atx(1),
+                content: vec![
+                    Inline::Text("See ".to_string()),
+                    Inline::Link(Link {
+                        destination: "https://example.com".to_string(),
+                        title: None,
+                        children: vec![
+                            Inline::Text("the ".to_string()),
+                            Inline::Strong(vec![Inline::Text("docs".to_string())]),
+                        ],
+                    }),
+                    Inline::Text(" for ".to_string()),
+                    Inline::Emphasis(vec![Inline::Code("parse".to_string())]),
+                ],
+            }),
+            Block::Heading(Heading {
+                kind: HeadingKind::Atx(2),
+                content: vec![Inline::Emphasis(vec![])],
+            }),
+            Block::Paragraph(vec![Inline::Emphasis(vec![Inline::Text(
+                "kept".to_string(),
+            )])]),
+        ],
+    };
+
+    let result = doc.flatten_heading_inlines();
+    assert_eq!(
+        result.blocks,
+        vec![
+            Block::Heading(Heading {
+                kind: HeadingKind::Atx(1),
+                content: vec![Inline::Text("See the docs for parse".to_string())],
+            }),
+            Block::Heading(Heading {
+                kind: HeadingKind::Atx(2),
+                content: vec![],
+            }),
+            Block::Paragraph(vec![Inline::Emphasis(vec![Inline::Text(
+                "kept".to_string(),
+            )])]),
+        ]
+    );
+}
+
+#[test]
+fn test_tidy_whitespace() {
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![
+                Inline::Text("  Pasted  text with   gaps\t".to_string()),
+                Inline::Emphasis(vec![Inline::Text("kept".to_string())]),
+                Inline::Text(" between  \n  lines   ".to_string()),
+            ]),
+            Block::Heading(Heading {
+                kind: HeadingKind::Atx(2),
+                content: vec![
+                    Inline::Text("   ".to_string()),
+                    Inline::Code("  code  ".to_string()),
+                    Inline::Text(" title  ".to_string()),
+                ],
+            }),
+            Block::BlockQuote(vec![Block::Paragraph(vec![Inline::Text(
+                " quoted ".to_string(),
+            )])]),
+        ],
+    };
+
+    let result = doc.tidy_whitespace();
+    assert_eq!(
+        result.blocks,
+        vec![
+            Block::Paragraph(vec![
+                Inline::Text("Pasted text with gaps ".to_string()),
+                Inline::Emphasis(vec![Inline::Text("kept".to_string())]),
+                Inline::Text(" between\nlines".to_string()),
+            ]),
+            Block::Heading(Heading {
+                kind: HeadingKind::Atx(2),
+                content: vec![
+                    Inline::Code("  code  ".to_string()),
+                    Inline::Text(" title".to_string()),
+                ],
+            }),
+            Block::BlockQuote(vec![Block::Paragraph(vec![Inline::Text(
+                "quoted".to_string(),
+            )])]),
+        ]
+    );
+}
+
+#[test]
+fn test_htmlize_inlines() {
+    let html = |s: &str| Inline::Html(s.to_string());
+    let text = |s: &str| Inline::Text(s.to_string());
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![
+                html("<b>x</b>"),
+                text(" and "),
+                html("<a href=\"u\">t</a>"),
+                text(" and "),
+                html("<CODE>a < b</CODE>"),
+            ]),
+            Block::Paragraph(vec![
+                html("<a href='/docs' title=\"Docs\">"),
+                text("see "),
+                html("<del>"),
+                text("old"),
+                html("</del>"),
+                html("</a>"),
+                html("<em>"),
+                Inline::Strong(vec![text("bold")]),
+                html("</em>"),
+            ]),
+            Block::Paragraph(vec![
+                html("<b class=\"x\">kept</b>"),
+                html("<i>"),
+                text("unclosed"),
+                html("<span>x</span>"),
+                html("<code>"),
+                Inline::Emphasis(vec![text("not code")]),
+                html("</code>"),
+            ]),
+        ],
+    };
+
+    let result = doc.htmlize_inlines();
+    assert_eq!(
+        result.blocks,
+        vec![
+            Block::Paragraph(vec![
+                Inline::Strong(vec![text("x")]),
+                text(" and "),
+                Inline::Link(Link {
+                    destination: "u".to_string(),
+                    title: None,
+                    children: vec![text("t")],
+                }),
+                text(" and "),
+                html("<CODE>a < b</CODE>"),
+            ]),
+            Block::Paragraph(vec![
+                Inline::Link(Link {
+                    destination: "/docs".to_string(),
+                    title: Some("Docs".to_string()),
+                    children: vec![text("see "), Inline::Strikethrough(vec![text("old")])],
+                }),
+                Inline::Emphasis(vec![Inline::Strong(vec![text("bold")])]),
+            ]),
+            Block::Paragraph(vec![
+                html("<b class=\"x\">kept</b>"),
+                html("<i>"),
+                text("unclosed"),
+                html("<span>x</span>"),
+                html("<code>"),
+                Inline::Emphasis(vec![text("not code")]),
+                html("</code>"),
+            ]),
+        ]
+    );
+}
+
+#[test]
+fn test_normalize_list_markers() {
+    let input = "- one\n- two\n\nText\n\n* three\n* four\n\n3. five\n";
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let config = || crate::printer::config::Config::default().with_spaces_before_list_item(0);
+
+    let doc = doc.normalize_list_markers(ListBulletKind::Star, None);
+    let output = crate::printer::render_markdown(&doc, config());
+    assert_eq!(output, "* one\n* two\n\nText\n\n* three\n* four\n\n3. five");
+
+    let doc = doc.normalize_list_markers(ListBulletKind::Dash, Some(OrderedListStyle::LowerAlpha));
+    let output = crate::printer::render_markdown(&doc, config());
+    assert_eq!(output, "- one\n- two\n\nText\n\n- three\n- four\n\nc. five");
+
+    // Nested lists are normalized too
+    let nested = Document {
+        blocks: vec![Block::List(List {
+            kind: ListKind::Bullet(ListBulletKind::Plus),
+            items: vec![ListItem {
+                task: None,
+                blocks: vec![Block::List(List {
+                    kind: ListKind::Bullet(ListBulletKind::Star),
+                    items: vec![],
+                    tight: true,
+                })],
+            }],
+            tight: true,
+        })],
+    };
+    let Block::List(outer) = &nested
+        .normalize_list_markers(ListBulletKind::Dash, None)
+        .blocks[0]
+    else {
+        panic!("expected a list");
+    };
+    let Block::List(inner) = &outer.items[0].blocks[0] else {
+        panic!("expected a nested list");
+    };
+    assert_eq!(outer.kind, ListKind::Bullet(ListBulletKind::Dash));
+    assert_eq!(inner.kind, ListKind::Bullet(ListBulletKind::Dash));
+}
+
+#[test]
+fn test_whitespace_keeps_non_breaking_spaces() {
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![Inline::Text(
+            "\u{a0}10\u{a0}\u{a0}am  soft\u{ad}ware \u{a0}".to_string(),
+        )])],
+    };
+    let expected = Block::Paragraph(vec![Inline::Text(
+        "\u{a0}10\u{a0}\u{a0}am soft\u{ad}ware \u{a0}".to_string(),
+    )]);
+
+    assert_eq!(doc.clone().tidy_whitespace().blocks[0], expected);
+    assert_eq!(doc.normalize_whitespace().blocks[0], expected);
+}
+
+#[test]
+fn test_collapse_single_item_lists() {
+    let input =
+        "- Only item\n\nText\n\n- One\n- Two\n\n1. First\n\n- [ ] Task\n\n* Outer\n\n  - Inner\n";
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let paragraph = |text: &str| Block::Paragraph(vec![Inline::Text(text.to_string())]);
+
+    let collapsed = doc.clone().collapse_single_item_lists();
+    assert_eq!(collapsed.blocks[0], paragraph("Only item"));
+    // Multi-item, ordered and task lists are untouched
+    assert_eq!(collapsed.blocks[1..5], doc.blocks[1..5]);
+    // The outer item holds two blocks, only the nested list is collapsed
+    let Block::List(outer) = &collapsed.blocks[5] else {
+        panic!("expected a list, got {:?}", collapsed.blocks[5]);
+    };
+    assert_eq!(
+        outer.items[0].blocks,
+        vec![paragraph("Outer"), paragraph("Inner")]
+    );
+
+    let collapsed = doc.collapse_single_item_lists_with(true);
+    assert_eq!(collapsed.blocks[3], paragraph("First"));
+}
+
+#[test]
+fn test_merge_adjacent_blocks() {
+    let paragraph = |text: &str| Block::Paragraph(vec![Inline::Text(text.to_string())]);
+    let code = |kind: CodeBlockKind, literal: &str| {
+        Block::CodeBlock(CodeBlock {
+            kind,
+            literal: literal.to_string(),
+        })
+    };
+    let indented = |literal: &str| code(CodeBlockKind::Indented, literal);
+    let fenced = |literal: &str| code(CodeBlockKind::Fenced { info: None }, literal);
+
+    let doc = Document {
+        blocks: vec![
+            Block::BlockQuote(vec![
+                paragraph("one"),
+                Block::BlockQuote(vec![paragraph("a")]),
+            ]),
+            Block::BlockQuote(vec![Block::BlockQuote(vec![paragraph("b")])]),
+            paragraph("between"),
+            Block::BlockQuote(vec![paragraph("separate")]),
+            indented("x = 1"),
+            indented("y = 2"),
+            fenced("z = 3"),
+            fenced("w = 4"),
+            Block::List(List {
+                kind: ListKind::Bullet(ListBulletKind::Dash),
+                items: vec![ListItem {
+                    task: None,
+                    blocks: vec![indented("first"), indented("second")],
+                }],
+                tight: true,
+            }),
+        ],
+    };
+
+    let merged = doc.merge_adjacent_blocks();
+    assert_eq!(
+        merged.blocks,
+        vec![
+            Block::BlockQuote(vec![
+                paragraph("one"),
+                Block::BlockQuote(vec![paragraph("a"), paragraph("b")]),
+            ]),
+            paragraph("between"),
+            Block::BlockQuote(vec![paragraph("separate")]),
+            indented("x = 1\ny = 2"),
+            fenced("z = 3"),
+            fenced("w = 4"),
+            Block::List(List {
+                kind: ListKind::Bullet(ListBulletKind::Dash),
+                items: vec![ListItem {
+                    task: None,
+                    blocks: vec![indented("first\nsecond")],
+                }],
+                tight: true,
+            }),
+        ]
+    );
+}
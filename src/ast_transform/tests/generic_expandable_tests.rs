@@ -415,3 +415,45 @@ fn test_user_data_modification() {
         panic!("Expected paragraph");
     }
 }
+
+#[test]
+fn test_assign_heading_slugs() {
+    use crate::ast::HeadingKind;
+    use crate::ast_transform::AssignHeadingSlugs;
+
+    let heading = |text: &str| {
+        Block::Heading(Heading {
+            kind: HeadingKind::Atx(2),
+            content: vec![Inline::Text {
+                content: text.to_string(),
+                user_data: String::new(),
+            }],
+            user_data: String::new(),
+        })
+    };
+    let doc: Document<String> = Document {
+        blocks: vec![
+            heading("Getting Started"),
+            heading("Install"),
+            Block::BlockQuote {
+                blocks: vec![heading("Install")],
+                user_data: String::new(),
+            },
+            heading("?!"),
+        ],
+        user_data: String::new(),
+    };
+
+    fn collect(blocks: &[Block<String>], slugs: &mut Vec<String>) {
+        for block in blocks {
+            match block {
+                Block::Heading(heading) => slugs.push(heading.user_data.clone()),
+                Block::BlockQuote { blocks, .. } => collect(blocks, slugs),
+                _ => {}
+            }
+        }
+    }
+    let mut slugs = Vec::new();
+    collect(&doc.assign_heading_slugs().blocks, &mut slugs);
+    assert_eq!(slugs, vec!["getting-started", "install", "install-1", ""]);
+}
@@ -151,6 +151,7 @@ mod traversal_order_tests {
                             ])],
                         },
                     ],
+                    tight: true,
                 }),
             ],
         };
@@ -65,6 +65,7 @@ fn create_test_doc() -> Document {
                         ])],
                     },
                 ],
+                tight: true,
             }),
             // Table with correct structure (first row is header)
             Block::Table(Table {
@@ -217,8 +218,13 @@ impl Visitor for NodeCounter {
             Inline::LinkReference(_) => self.link_ref_count += 1,
             Inline::FootnoteReference(_) => self.footnote_ref_count += 1,
             Inline::LineBreak => {}
+            Inline::SoftBreak => {}
             Inline::Empty => {}
             Inline::Latex(_) => {}
+            Inline::Highlight(_) => {}
+            Inline::Subscript(_) => {}
+            Inline::Superscript(_) => {}
+            Inline::Delimited(_) => {}
         }
         self.walk_inline(inline);
     }
@@ -357,6 +363,7 @@ fn test_visitor_deep_nesting() {
                     }),
                 ])])],
             }],
+            tight: true,
         })])],
     };
 
@@ -405,7 +412,10 @@ fn test_visitor_with_thematic_break() {
 fn test_visitor_with_html_block() {
     let doc = Document {
         blocks: vec![
-            Block::HtmlBlock("<div>HTML content</div>".to_string()),
+            Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<div>HTML content</div>".to_string(),
+            }),
             Block::Paragraph(vec![Inline::Html("<span>Inline HTML</span>".to_string())]),
         ],
     };
@@ -417,3 +427,58 @@ fn test_visitor_with_html_block() {
     assert_eq!(counter.html_count, 1);
     assert_eq!(counter.paragraph_count, 1);
 }
+
+#[test]
+fn test_path_visitor_reports_nested_text_path() {
+    use crate::ast_transform::{NodePath, PathVisitor, VisitWithPath};
+
+    struct TextPaths(Vec<(String, String)>);
+
+    impl PathVisitor for TextPaths {
+        fn visit_inline(&mut self, inline: &Inline, path: &mut NodePath) {
+            if let Inline::Text(text) = inline {
+                self.0.push((path.to_string(), text.clone()));
+            }
+            self.walk_inline(inline, path);
+        }
+    }
+
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![Inline::Text("intro".to_string())]),
+            Block::ThematicBreak,
+            Block::List(List {
+                kind: ListKind::Bullet(ListBulletKind::Dash),
+                items: vec![ListItem {
+                    task: None,
+                    blocks: vec![
+                        Block::Paragraph(vec![Inline::Text("first".to_string())]),
+                        Block::Paragraph(vec![
+                            Inline::Code("code".to_string()),
+                            Inline::Emphasis(vec![Inline::Text("nested".to_string())]),
+                        ]),
+                    ],
+                }],
+                tight: false,
+            }),
+        ],
+    };
+
+    let mut visitor = TextPaths(Vec::new());
+    doc.visit_with_path(&mut visitor);
+
+    assert_eq!(
+        visitor.0,
+        vec![
+            ("blocks[0].inlines[0]".to_string(), "intro".to_string()),
+            (
+                "blocks[2].items[0].blocks[0].inlines[0]".to_string(),
+                "first".to_string()
+            ),
+            (
+                "blocks[2].items[0].blocks[1].inlines[1].children[0]".to_string(),
+                "nested".to_string()
+            ),
+        ]
+    );
+}
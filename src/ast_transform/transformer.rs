@@ -210,6 +210,28 @@ pub trait Transformer {
                     .map(|inline| self.transform_inline(inline))
                     .collect(),
             ),
+            Inline::Highlight(inlines) => Inline::Highlight(
+                inlines
+                    .into_iter()
+                    .map(|inline| self.transform_inline(inline))
+                    .collect(),
+            ),
+            Inline::Subscript(inlines) => Inline::Subscript(
+                inlines
+                    .into_iter()
+                    .map(|inline| self.transform_inline(inline))
+                    .collect(),
+            ),
+            Inline::Superscript(inlines) => Inline::Superscript(
+                inlines
+                    .into_iter()
+                    .map(|inline| self.transform_inline(inline))
+                    .collect(),
+            ),
+            Inline::Delimited(mut delimited) => {
+                delimited.inner = Box::new(self.transform_inline(*delimited.inner));
+                Inline::Delimited(delimited)
+            }
             Inline::Link(link) => Inline::Link(self.transform_link(link)),
             Inline::LinkReference(mut link_ref) => {
                 link_ref.label = link_ref
@@ -338,17 +360,17 @@ pub trait Transformer {
 
     /// Transform a table cell with possibility to expand into multiple cells
     fn expand_table_cell(&mut self, cell: TableCell) -> Vec<TableCell> {
-        vec![self.transform_table_cell(cell)]
+        self.walk_expand_table_cell(cell)
     }
 
     /// Transform a list item with possibility to expand into multiple items
     fn expand_list_item(&mut self, item: ListItem) -> Vec<ListItem> {
-        vec![self.transform_list_item(item)]
+        self.walk_expand_list_item(item)
     }
 
     /// Transform a table row with possibility to expand into multiple rows
     fn expand_table_row(&mut self, row: TableRow) -> Vec<TableRow> {
-        vec![self.transform_table_row(row)]
+        self.walk_expand_table_row(row)
     }
 
     /// Transform a heading with possibility to expand into multiple headings
@@ -467,6 +489,14 @@ pub trait Transformer {
                     .collect();
                 vec![Block::GitHubAlert(alert)]
             }
+            Block::Container(mut container) => {
+                container.blocks = container
+                    .blocks
+                    .into_iter()
+                    .flat_map(|block| self.expand_block(block))
+                    .collect();
+                vec![Block::Container(container)]
+            }
             Block::Definition(mut def) => {
                 def.label = def
                     .label
@@ -498,6 +528,27 @@ pub trait Transformer {
                 let inlines = inlines.into_iter().flat_map(|i| self.expand_inline(i)).collect();
                 vec![Inline::Strikethrough(inlines)]
             }
+            Inline::Highlight(inlines) => {
+                let inlines = inlines.into_iter().flat_map(|i| self.expand_inline(i)).collect();
+                vec![Inline::Highlight(inlines)]
+            }
+            Inline::Subscript(inlines) => {
+                let inlines = inlines.into_iter().flat_map(|i| self.expand_inline(i)).collect();
+                vec![Inline::Subscript(inlines)]
+            }
+            Inline::Superscript(inlines) => {
+                let inlines = inlines.into_iter().flat_map(|i| self.expand_inline(i)).collect();
+                vec![Inline::Superscript(inlines)]
+            }
+            Inline::Delimited(mut delimited) => {
+                let mut inlines = self.expand_inline(*delimited.inner);
+                // The delimiters only describe a single node
+                if inlines.len() != 1 {
+                    return inlines;
+                }
+                delimited.inner = Box::new(inlines.remove(0));
+                vec![Inline::Delimited(delimited)]
+            }
             Inline::Link(mut link) => {
                 link.children = link
                     .children
@@ -43,6 +43,13 @@ pub mod printer;
 #[cfg(feature = "typst-printer")]
 pub mod typst_printer;
 
+/// Render a document to several output formats at once.
+///
+/// Use [`render_all`](render::render_all) with [`RenderOptions`](render::RenderOptions)
+/// to select the formats.
+#[cfg(any(feature = "printer", feature = "typst-printer"))]
+pub mod render;
+
 /// AST transformation utilities for manipulating parsed Markdown.
 #[cfg(feature = "ast-transform")]
 pub mod ast_transform;
@@ -4,10 +4,10 @@ use crate::parser::MarkdownParserState;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::char,
-    combinator::{not, opt, peek, recognize, value},
-    multi::{many0, many1, many_m_n},
-    sequence::preceded,
+    character::complete::{char, line_ending, space0},
+    combinator::{fail, not, opt, peek, recognize, value, verify},
+    multi::{many0, many_m_n},
+    sequence::{preceded, terminated},
     IResult, Parser,
 };
 use std::rc::Rc;
@@ -25,15 +25,30 @@ pub(crate) fn code_block<'a>(
 }
 
 pub(crate) fn code_block_indented<'a>(
-    _state: Rc<MarkdownParserState>,
+    state: Rc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, CodeBlock> {
     move |input: &'a str| {
-        let line_parser = preceded(
-            alt((value((), many_m_n(4, 4, char(' '))), value((), char('\t')))),
-            line_terminated(not_eof_or_eol0),
-        );
+        if !state.config.indented_code_blocks {
+            return fail().parse(input);
+        }
+        let line_parser = || {
+            preceded(
+                alt((value((), many_m_n(4, 4, char(' '))), value((), char('\t')))),
+                line_terminated(verify(not_eof_or_eol0, |line: &str| {
+                    !line.trim().is_empty()
+                })),
+            )
+        };
+        let blank_line = terminated(space0, line_ending);
 
-        let (input, lines) = many1(line_parser).parse(input)?;
+        // Blank lines belong to the block only when more code follows them
+        let (input, first) = line_parser().parse(input)?;
+        let (input, rest) = many0((many0(blank_line), line_parser())).parse(input)?;
+        let mut lines = vec![first];
+        for (blanks, line) in rest {
+            lines.extend(blanks.iter().map(|_| ""));
+            lines.push(line);
+        }
         let literal = lines.join("\n");
 
         let code_block = CodeBlock {
@@ -3,12 +3,10 @@ use crate::parser::util::*;
 use crate::parser::MarkdownParserState;
 use nom::{
     branch::alt,
-    bytes::complete::{is_not, tag, take_while1},
-    character::complete::{
-        anychar, char, line_ending, multispace0, multispace1, not_line_ending, space0,
-    },
+    bytes::complete::{is_not, take_while1, take_while_m_n},
+    character::complete::{char, multispace0, multispace1, not_line_ending, space0},
     combinator::{cut, map, recognize},
-    multi::{many0, many_m_n, many_till, separated_list0},
+    multi::{many0, many_m_n, separated_list0},
     sequence::{delimited, preceded, separated_pair},
     IResult, Parser,
 };
@@ -54,15 +52,9 @@ pub(crate) fn container<'a>(
     state: Rc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Block> {
     move |input: &'a str| {
-        if !state.containers.is_empty() {
-            return Err(nom::Err::Error(nom::error::Error::new(
-                input,
-                nom::error::ErrorKind::Verify,
-            )));
-        }
-
         let (input, _) = many_m_n(0, 3, char(' ')).parse(input)?;
-        let (input, line) = line_terminated(preceded(tag(":::"), not_line_ending)).parse(input)?;
+        let (input, fence) = take_while_m_n(3, usize::MAX, |c| c == ':').parse(input)?;
+        let (input, line) = line_terminated(not_line_ending).parse(input)?;
 
         let (remainder, kind) = recognize(is_not("{ \t\r\n")).parse(line)?;
         let (remainder, _) = space0(remainder)?;
@@ -86,12 +78,11 @@ pub(crate) fn container<'a>(
         nested_state.containers.push(kind_trimmed.to_string());
         let nested_state_rc = Rc::new(nested_state);
 
-        let (input, (chars, _)) =
-            many_till(anychar, preceded(many_m_n(0, 3, char(' ')), tag(":::"))).parse(input)?;
-
-        let inner_content: String = chars.into_iter().collect();
+        let (inner_content, input) = split_at_closing_fence(input, fence.len()).ok_or(
+            nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify)),
+        )?;
         let (_, blocks) = many0(crate::parser::blocks::block(nested_state_rc))
-            .parse(&inner_content)
+            .parse(inner_content)
             .map_err(|err| err.map_input(|_| input))?;
 
         let container = Container {
@@ -100,8 +91,43 @@ pub(crate) fn container<'a>(
             blocks: blocks.into_iter().flatten().collect(),
         };
 
-        let (input, _) = line_ending(input)?;
-
         Ok((input, Block::Container(container)))
     }
 }
+
+/// Splits `input` into the container content and the text after the closing
+/// fence: the first line made of at least `fence_len` colons, indented by up
+/// to three spaces, that does not close a container nested inside.
+///
+/// A fence closes the innermost open container, so nested containers may use
+/// fences of the same length as the outer one.
+fn split_at_closing_fence(input: &str, fence_len: usize) -> Option<(&str, &str)> {
+    let mut open_fences: Vec<usize> = Vec::new();
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        let fence = line.trim_end_matches(['\n', '\r']);
+        let fence = fence.trim_end_matches([' ', '\t']);
+        let indent = fence.len() - fence.trim_start_matches(' ').len();
+        let rest = &fence[indent..];
+        let colons = rest.len() - rest.trim_start_matches(':').len();
+        if indent <= 3 && colons >= 3 {
+            if colons < rest.len() {
+                // Opening fence of a nested container
+                open_fences.push(colons);
+            } else {
+                match open_fences.last() {
+                    Some(&nested_len) if colons >= nested_len => {
+                        open_fences.pop();
+                    }
+                    Some(_) => {}
+                    None if colons >= fence_len => {
+                        return Some((&input[..offset], &input[offset + line.len()..]));
+                    }
+                    None => {}
+                }
+            }
+        }
+        offset += line.len();
+    }
+    None
+}
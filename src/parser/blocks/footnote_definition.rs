@@ -1,10 +1,11 @@
 use crate::ast::FootnoteDefinition;
 use crate::parser::util::{line_terminated, not_eof_or_eol1};
 use crate::parser::MarkdownParserState;
-use nom::character::complete::{char, none_of};
+use nom::character::complete::{char, none_of, space0};
 use nom::{
+    branch::alt,
     bytes::complete::tag,
-    combinator::{recognize, verify},
+    combinator::{map, recognize, verify},
     multi::{many0, many1, many_m_n},
     sequence::preceded,
     IResult, Parser,
@@ -21,11 +22,7 @@ pub(crate) fn footnote_definition<'a>(
         let (input, _) = tag("]:").parse(input)?;
         let (input, _) = many_m_n(0, 3, char(' ')).parse(input)?;
         let (input, first_line) = line_terminated(not_eof_or_eol1).parse(input)?;
-        let (input, rest_lines) = many0(preceded(
-            many_m_n(3, 3, char(' ')),
-            line_terminated(not_eof_or_eol1),
-        ))
-        .parse(input)?;
+        let (input, rest_lines) = many0(footnote_definition_rest_line).parse(input)?;
 
         let total_size = first_line.len() + rest_lines.len();
         let mut footnote_content = String::with_capacity(total_size);
@@ -34,7 +31,9 @@ pub(crate) fn footnote_definition<'a>(
         }
         for line in rest_lines {
             footnote_content.push('\n');
-            footnote_content.push_str(line)
+            for subline in line {
+                footnote_content.push_str(subline)
+            }
         }
 
         let nested_state = Rc::new(state.nested());
@@ -52,3 +51,27 @@ pub(crate) fn footnote_definition<'a>(
         Ok((input, v))
     }
 }
+
+/// Continuation line of a footnote definition.
+///
+/// A continuation line is indented by at least 3 spaces. Up to 4 spaces of
+/// indentation are stripped, so deeper indented content (e.g. code blocks)
+/// keeps its relative indentation. Blank lines belong to the definition only
+/// if they are followed by an indented line, which allows multi-paragraph
+/// footnotes. A non-indented line ends the definition.
+fn footnote_definition_rest_line(input: &str) -> IResult<&str, Vec<&str>> {
+    let indent = || many_m_n(3, 4, char(' '));
+    alt((
+        map(preceded(indent(), line_terminated(not_eof_or_eol1)), |v| {
+            vec![v]
+        }),
+        map(
+            (
+                recognize(many1(line_terminated(space0))),
+                preceded(indent(), line_terminated(not_eof_or_eol1)),
+            ),
+            |(newlines, content)| vec![newlines, content],
+        ),
+    ))
+    .parse(input)
+}
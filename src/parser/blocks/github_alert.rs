@@ -56,7 +56,11 @@ pub(crate) fn github_alert<'a>(
             preceded((prefix, opt(char(' '))), line_terminated(not_eof_or_eol0)).parse(input)?;
 
         // Check if the first line contains a GitHub alert marker
-        let alert_type = if let Some(alert_type) = parse_alert_marker(first_line.trim()) {
+        let alert_type = match parse_alert_marker(first_line.trim()) {
+            Some(GitHubAlertType::Custom(_)) if !state.config.custom_github_alerts => None,
+            alert_type => alert_type,
+        };
+        let alert_type = if let Some(alert_type) = alert_type {
             alert_type
         } else {
             // Not a GitHub alert, fail to let regular blockquote parser handle it
@@ -1,3 +1,4 @@
+use crate::ast::HtmlBlockKind;
 use crate::parser::MarkdownParserState;
 use nom::{
     branch::alt,
@@ -5,7 +6,7 @@ use nom::{
     character::complete::{
         alpha1, alphanumeric1, anychar, char, line_ending, one_of, satisfy, space0, space1,
     },
-    combinator::{eof, not, opt, peek, recognize, value, verify},
+    combinator::{eof, map, not, opt, peek, recognize, value, verify},
     multi::{many0, many1, many_m_n},
     sequence::{delimited, pair, preceded, terminated},
     IResult, Parser,
@@ -14,21 +15,62 @@ use std::rc::Rc;
 
 pub(crate) fn html_block(
     state: Rc<MarkdownParserState>,
-) -> impl FnMut(&str) -> IResult<&str, &str> {
+) -> impl FnMut(&str) -> IResult<&str, (HtmlBlockKind, &str)> {
     move |input: &str| {
         alt((
-            html_block1(state.clone()),
-            html_block2(state.clone()),
-            html_block3(state.clone()),
-            html_block4(state.clone()),
-            html_block5(state.clone()),
-            html_block6(state.clone()),
-            html_block7(state.clone()),
+            map(html_block1(state.clone()), |s| (HtmlBlockKind::Raw, s)),
+            map(html_block2(state.clone()), |s| (HtmlBlockKind::Comment, s)),
+            map(html_block3(state.clone()), |s| {
+                (HtmlBlockKind::ProcessingInstruction, s)
+            }),
+            map(html_block4(state.clone()), |s| {
+                (HtmlBlockKind::Declaration, s)
+            }),
+            map(html_block5(state.clone()), |s| (HtmlBlockKind::CData, s)),
+            map(html_block6(state.clone()), |s| (HtmlBlockKind::BlockTag, s)),
+            map(html_block7(state.clone()), |s| (HtmlBlockKind::OtherTag, s)),
         ))
         .parse(input)
     }
 }
 
+/// Tags that GFM's "Disallowed Raw HTML" extension neutralizes.
+const GFM_DISALLOWED_TAGS: [&str; 9] = [
+    "title",
+    "textarea",
+    "style",
+    "xmp",
+    "iframe",
+    "noembed",
+    "noframes",
+    "script",
+    "plaintext",
+];
+
+/// Replace the leading `<` of disallowed opening and closing tags with `&lt;`.
+pub(crate) fn gfm_tagfilter(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(pos) = rest.find('<') {
+        result.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let name = after.strip_prefix('/').unwrap_or(after);
+        let disallowed = GFM_DISALLOWED_TAGS.iter().any(|tag| {
+            name.len() >= tag.len()
+                && name.is_char_boundary(tag.len())
+                && name[..tag.len()].eq_ignore_ascii_case(tag)
+                && name[tag.len()..]
+                    .chars()
+                    .next()
+                    .is_none_or(|c| c.is_ascii_whitespace() || c == '>' || c == '/')
+        });
+        result.push_str(if disallowed { "&lt;" } else { "<" });
+        rest = after;
+    }
+    result.push_str(rest);
+    result
+}
+
 fn html_block1(_state: Rc<MarkdownParserState>) -> impl FnMut(&str) -> IResult<&str, &str> {
     move |input: &str| {
         let tag_variant_parser = || {
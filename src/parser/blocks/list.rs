@@ -1,11 +1,13 @@
-use crate::ast::{ListBulletKind, ListItem, ListKind, ListOrderedKindOptions, TaskState};
+use crate::ast::{
+    Block, ListBulletKind, ListItem, ListKind, ListOrderedKindOptions, OrderedListStyle, TaskState,
+};
 use crate::parser::util::*;
 use crate::parser::MarkdownParserState;
 use nom::combinator::verify;
 use nom::{
     branch::alt,
-    character::complete::{char, one_of, space0},
-    combinator::{map, not, opt, peek, recognize, value},
+    character::complete::{alpha1, char, one_of, space0},
+    combinator::{map, map_opt, not, opt, peek, recognize, value},
     multi::{many0, many1, many_m_n},
     sequence::{delimited, preceded, terminated},
     IResult, Parser,
@@ -24,14 +26,16 @@ fn list_item_task_state(input: &str) -> IResult<&str, TaskState> {
     .parse(input)
 }
 
-fn list_marker(input: &str) -> IResult<&str, ListKind> {
-    alt((
-        list_marker_ordered,
-        list_marker_star,
-        list_marker_plus,
-        list_marker_dash,
-    ))
-    .parse(input)
+fn list_marker(letters: bool) -> impl FnMut(&str) -> IResult<&str, ListKind> {
+    move |input: &str| {
+        alt((
+            list_marker_ordered(letters),
+            list_marker_star,
+            list_marker_plus,
+            list_marker_dash,
+        ))
+        .parse(input)
+    }
 }
 
 fn list_marker_star(input: &str) -> IResult<&str, ListKind> {
@@ -46,20 +50,98 @@ fn list_marker_dash(input: &str) -> IResult<&str, ListKind> {
     map(char('-'), |_| ListKind::Bullet(ListBulletKind::Dash)).parse(input)
 }
 
-fn list_marker_ordered(input: &str) -> IResult<&str, ListKind> {
-    map(
-        terminated(nom::character::complete::u64, one_of(".)")),
-        |start| ListKind::Ordered(ListOrderedKindOptions { start }),
-    )
+/// Ordered list marker; with `letters`, also `a.` and `iv.` style markers
+fn list_marker_ordered(letters: bool) -> impl FnMut(&str) -> IResult<&str, ListKind> {
+    move |input: &str| {
+        let (rest, (start, style)) = if letters {
+            alt((decimal_number, map_opt(alpha1, letter_number))).parse(input)?
+        } else {
+            decimal_number(input)?
+        };
+        let (rest, _) = one_of(".)").parse(rest)?;
+        Ok((
+            rest,
+            ListKind::Ordered(ListOrderedKindOptions { start, style }),
+        ))
+    }
+}
+
+fn decimal_number(input: &str) -> IResult<&str, (u64, OrderedListStyle)> {
+    map(nom::character::complete::u64, |start| {
+        (start, OrderedListStyle::Decimal)
+    })
     .parse(input)
 }
 
+/// Number and style of a letter or roman numeral list marker
+fn letter_number(marker: &str) -> Option<(u64, OrderedListStyle)> {
+    let upper = marker.bytes().all(|b| b.is_ascii_uppercase());
+    if !upper && !marker.bytes().all(|b| b.is_ascii_lowercase()) {
+        return None;
+    }
+    let lower = marker.to_ascii_lowercase();
+    // A lone letter other than `i` is alphabetic even if it is a roman digit
+    if marker.len() != 1 || lower == "i" {
+        if let Some(number) = roman_value(&lower) {
+            let style = if upper {
+                OrderedListStyle::UpperRoman
+            } else {
+                OrderedListStyle::LowerRoman
+            };
+            return Some((number, style));
+        }
+    }
+    let number = alpha_value(&lower)?;
+    let style = if upper {
+        OrderedListStyle::UpperAlpha
+    } else {
+        OrderedListStyle::LowerAlpha
+    };
+    Some((number, style))
+}
+
+/// Value of a lowercase alphabetic counter: `a`..`z`, then `aa`, `ab`, ...
+fn alpha_value(letters: &str) -> Option<u64> {
+    letters.bytes().try_fold(0u64, |acc, b| {
+        acc.checked_mul(26)?.checked_add(u64::from(b - b'a') + 1)
+    })
+}
+
+/// Value of a well-formed lowercase roman numeral
+fn roman_value(numeral: &str) -> Option<u64> {
+    if numeral.len() > 15 {
+        return None;
+    }
+    let digit = |c: char| match c {
+        'i' => Some(1),
+        'v' => Some(5),
+        'x' => Some(10),
+        'l' => Some(50),
+        'c' => Some(100),
+        'd' => Some(500),
+        'm' => Some(1000),
+        _ => None,
+    };
+    let digits = numeral.chars().map(digit).collect::<Option<Vec<u64>>>()?;
+    let value = digits
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (i, &d)| match digits.get(i + 1) {
+            Some(&next) if next > d => acc - d as i64,
+            _ => acc + d as i64,
+        });
+    let value = u64::try_from(value).ok()?;
+    // Reject sloppy forms such as `iiii` or `vx`
+    (OrderedListStyle::LowerRoman.format(value) == numeral).then_some(value)
+}
+
 fn list_marker_followed_by_spaces(
+    letters: bool,
     input: &str,
 ) -> IResult<&str, (ListKind, usize, Option<TaskState>)> {
     let (remaining, kind) = delimited(
         many_m_n(0, 3, char(' ')),
-        list_marker,
+        list_marker(letters),
         many_m_n(1, 4, char(' ')),
     )
     .parse(input)?;
@@ -72,9 +154,11 @@ fn list_marker_followed_by_spaces(
 }
 
 fn list_marker_followed_by_newline(
+    letters: bool,
     input: &str,
 ) -> IResult<&str, (ListKind, usize, Option<TaskState>)> {
-    let (remaining, kind) = preceded(many_m_n(0, 3, char(' ')), list_marker).parse(input)?;
+    let (remaining, kind) =
+        preceded(many_m_n(0, 3, char(' ')), list_marker(letters)).parse(input)?;
 
     // Cases:
     // 1.
@@ -95,18 +179,19 @@ fn list_marker_followed_by_newline(
 }
 
 pub(crate) fn list_marker_with_span_size(
+    letters: bool,
     input: &str,
 ) -> IResult<&str, (ListKind, usize, Option<TaskState>, String)> {
     alt((
         map(
-            list_marker_followed_by_newline,
+            |input| list_marker_followed_by_newline(letters, input),
             |(list_kind, prefix_length, task_state)| {
                 (list_kind, prefix_length, task_state, String::new())
             },
         ),
         (map(
             (
-                list_marker_followed_by_spaces,
+                |input| list_marker_followed_by_spaces(letters, input),
                 line_terminated(not_eof_or_eol0),
             ),
             |((list_kind, prefix_length, task_state), s)| {
@@ -131,11 +216,13 @@ fn list_item_rest_line(
             )));
         }
 
-        let marker_parser = match list_kind {
-            ListKind::Ordered(_) => list_marker_ordered,
-            ListKind::Bullet(ListBulletKind::Star) => list_marker_star,
-            ListKind::Bullet(ListBulletKind::Plus) => list_marker_plus,
-            ListKind::Bullet(ListBulletKind::Dash) => list_marker_dash,
+        let letters = state.config.alphabetic_list_markers;
+        let kind = list_kind.clone();
+        let marker_parser = move |input| match kind {
+            ListKind::Ordered(_) => list_marker_ordered(letters).parse(input),
+            ListKind::Bullet(ListBulletKind::Star) => list_marker_star(input),
+            ListKind::Bullet(ListBulletKind::Plus) => list_marker_plus(input),
+            ListKind::Bullet(ListBulletKind::Dash) => list_marker_dash(input),
         };
 
         line_terminated(preceded(
@@ -195,10 +282,10 @@ fn list_item_lines(
 
 pub(crate) fn list_item(
     state: Rc<MarkdownParserState>,
-) -> impl FnMut(&str) -> IResult<&str, (ListKind, ListItem)> {
+) -> impl FnMut(&str) -> IResult<&str, (ListKind, ListItem, bool)> {
     move |input: &str| {
         let (input, (list_kind, item_prefix_length, task_state, first_line)) =
-            list_marker_with_span_size(input)?;
+            list_marker_with_span_size(state.config.alphabetic_list_markers, input)?;
 
         let (input, rest_lines) =
             list_item_lines(state.clone(), list_kind.clone(), item_prefix_length).parse(input)?;
@@ -216,34 +303,81 @@ pub(crate) fn list_item(
         }
 
         let nested_state = Rc::new(state.nested());
-        let (_, blocks) = many0(crate::parser::blocks::block(nested_state))
-            .parse(&item_content)
-            .map_err(|err| err.map_input(|_| input))?;
-
-        let blocks = blocks.into_iter().flatten().collect();
+        let (blocks, loose) =
+            item_blocks(nested_state, &item_content).map_err(|err| err.map_input(|_| input))?;
 
         let item = ListItem {
             task: task_state,
             blocks,
         };
-        Ok((input, (list_kind, item)))
+        Ok((input, (list_kind, item, loose)))
     }
 }
 
+/// Parse the content of a list item into blocks.
+///
+/// Also reports whether two direct children of the item are separated by a
+/// blank line, which makes the whole list loose.
+fn item_blocks(
+    state: Rc<MarkdownParserState>,
+    mut input: &str,
+) -> Result<(Vec<Block>, bool), nom::Err<nom::error::Error<&str>>> {
+    let mut blocks = Vec::new();
+    let mut seen_block = false;
+    let mut loose = false;
+    loop {
+        let (_, blank_lines) = many_empty_lines0(input).unwrap_or((input, Vec::new()));
+        match crate::parser::blocks::block(state.clone()).parse(input) {
+            Ok((rest, parsed)) if rest.len() < input.len() => {
+                loose |= seen_block && !blank_lines.is_empty();
+                seen_block |= !parsed.is_empty();
+                blocks.extend(parsed);
+                input = rest;
+            }
+            Ok(_) | Err(nom::Err::Error(_)) => break,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok((blocks, loose))
+}
+
 pub(crate) fn list(
     state: Rc<MarkdownParserState>,
 ) -> impl FnMut(&str) -> IResult<&str, crate::ast::List> {
     move |input: &str| {
-        let (input, items) = many1(list_item(state.clone())).parse(input)?;
+        let (mut input, (kind, first_item, mut loose)) = list_item(state.clone()).parse(input)?;
+        let mut items = vec![first_item];
 
-        // With many1(), first element always present
-        let first_item = items.first().unwrap();
+        loop {
+            let (rest, blank_lines) = many_empty_lines0(input).unwrap_or((input, Vec::new()));
+            match list_item(state.clone()).parse(rest) {
+                // After a blank line only an item of the same type continues the list
+                Ok((rest, (item_kind, item, item_loose)))
+                    if blank_lines.is_empty() || same_list_type(&kind, &item_kind) =>
+                {
+                    loose |= item_loose || !blank_lines.is_empty();
+                    items.push(item);
+                    input = rest;
+                }
+                Ok(_) | Err(nom::Err::Error(_)) => break,
+                Err(err) => return Err(err),
+            }
+        }
 
         let list = crate::ast::List {
-            kind: first_item.0.clone(),
-            items: items.into_iter().map(|(_, item)| item).collect(),
+            kind,
+            items,
+            tight: !loose,
         };
 
         Ok((input, list))
     }
 }
+
+fn same_list_type(a: &ListKind, b: &ListKind) -> bool {
+    match (a, b) {
+        (ListKind::Ordered(_), ListKind::Ordered(_)) => true,
+        (ListKind::Bullet(a), ListKind::Bullet(b)) => a == b,
+        _ => false,
+    }
+}
@@ -16,114 +16,130 @@ mod thematic_break;
 #[cfg(test)]
 mod tests;
 
-use crate::ast::Block;
+use crate::ast::{Block, HtmlBlock};
 use crate::parser::util::*;
 use crate::parser::MarkdownParserState;
 use nom::branch::alt;
 use nom::combinator::fail;
-use nom::{combinator::map, sequence::preceded, IResult, Parser};
+use nom::{combinator::map, IResult, Parser};
 use std::rc::Rc;
 
 pub(crate) fn block<'a>(
     state: Rc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Block>> {
     move |input: &'a str| {
-        preceded(
-            many_empty_lines0,
-            alt((
-                conditional_block(
-                    state.config.block_code_block_behavior.clone(),
-                    map(
-                        crate::parser::blocks::code_block::code_block(state.clone()),
-                        Block::CodeBlock,
-                    ),
+        let (input, blank_lines) = many_empty_lines0(input)?;
+        let (input, blocks) = alt((
+            conditional_block(
+                state.config.block_code_block_behavior.clone(),
+                map(
+                    crate::parser::blocks::code_block::code_block(state.clone()),
+                    Block::CodeBlock,
                 ),
-                conditional_block(
-                    state.config.block_heading_v1_behavior.clone(),
-                    map(
-                        crate::parser::blocks::heading::heading_v1(state.clone()),
-                        Block::Heading,
-                    ),
+            ),
+            conditional_block(
+                state.config.block_heading_v1_behavior.clone(),
+                map(
+                    crate::parser::blocks::heading::heading_v1(state.clone()),
+                    Block::Heading,
                 ),
-                conditional_block(
-                    state.config.block_container_behavior.clone(),
-                    crate::parser::blocks::container::container(state.clone()),
+            ),
+            conditional_block(
+                state.config.block_container_behavior.clone(),
+                crate::parser::blocks::container::container(state.clone()),
+            ),
+            map(crate::parser::blocks::macro_block::macro_block, |b| vec![b]),
+            conditional_block(
+                state.config.block_thematic_break_behavior.clone(),
+                map(
+                    crate::parser::blocks::thematic_break::thematic_break(state.clone()),
+                    |()| Block::ThematicBreak,
                 ),
-                map(crate::parser::blocks::macro_block::macro_block, |b| vec![b]),
-                conditional_block(
-                    state.config.block_thematic_break_behavior.clone(),
-                    map(
-                        crate::parser::blocks::thematic_break::thematic_break(state.clone()),
-                        |()| Block::ThematicBreak,
-                    ),
+            ),
+            // NOTE: It's important that the latex parser comes before the paragraph parser
+            map(crate::parser::blocks::latex::latex_block, |b| vec![b]),
+            conditional_block(
+                state.config.block_heading_v2_behavior.clone(),
+                crate::parser::blocks::heading::heading_v2_or_paragraph(state.clone()),
+            ),
+            // GitHub alerts should be checked before regular blockquotes
+            conditional_block_vec(
+                state.config.block_github_alert_behavior.clone(),
+                crate::parser::blocks::github_alert::github_alert(state.clone()),
+            ),
+            conditional_block(
+                state.config.block_blockquote_behavior.clone(),
+                map(
+                    crate::parser::blocks::blockquote::blockquote(state.clone()),
+                    Block::BlockQuote,
                 ),
-                // NOTE: It's important that the latex parser comes before the paragraph parser
-                map(crate::parser::blocks::latex::latex_block, |b| vec![b]),
-                conditional_block(
-                    state.config.block_heading_v2_behavior.clone(),
-                    crate::parser::blocks::heading::heading_v2_or_paragraph(state.clone()),
+            ),
+            conditional_block(
+                state.config.block_list_behavior.clone(),
+                map(
+                    crate::parser::blocks::list::list(state.clone()),
+                    Block::List,
                 ),
-                // GitHub alerts should be checked before regular blockquotes
-                conditional_block_vec(
-                    state.config.block_github_alert_behavior.clone(),
-                    crate::parser::blocks::github_alert::github_alert(state.clone()),
+            ),
+            conditional_block(
+                state.config.block_html_block_behavior.clone(),
+                map(
+                    crate::parser::blocks::html_block::html_block(state.clone()),
+                    |(kind, s)| {
+                        let content = if state.config.gfm_tagfilter {
+                            crate::parser::blocks::html_block::gfm_tagfilter(s)
+                        } else {
+                            s.to_owned()
+                        };
+                        Block::HtmlBlock(HtmlBlock {
+                            kind: state.config.html_block_kinds.then_some(kind),
+                            content,
+                        })
+                    },
                 ),
-                conditional_block(
-                    state.config.block_blockquote_behavior.clone(),
-                    map(
-                        crate::parser::blocks::blockquote::blockquote(state.clone()),
-                        Block::BlockQuote,
-                    ),
+            ),
+            // Alway try before link definition
+            conditional_block(
+                state.config.block_footnote_definition_behavior.clone(),
+                map(
+                    crate::parser::blocks::footnote_definition::footnote_definition(state.clone()),
+                    Block::FootnoteDefinition,
                 ),
-                conditional_block(
-                    state.config.block_list_behavior.clone(),
-                    map(
-                        crate::parser::blocks::list::list(state.clone()),
-                        Block::List,
-                    ),
+            ),
+            conditional_block(
+                state.config.block_link_definition_behavior.clone(),
+                map(
+                    crate::parser::blocks::link_definition::link_definition(state.clone()),
+                    Block::Definition,
                 ),
-                conditional_block(
-                    state.config.block_html_block_behavior.clone(),
-                    map(
-                        crate::parser::blocks::html_block::html_block(state.clone()),
-                        |s| Block::HtmlBlock(s.to_owned()),
-                    ),
+            ),
+            custom_parser(state.clone()),
+            conditional_block(
+                state.config.block_table_behavior.clone(),
+                map(
+                    crate::parser::blocks::table::table(state.clone()),
+                    Block::Table,
                 ),
-                // Alway try before link definition
-                conditional_block(
-                    state.config.block_footnote_definition_behavior.clone(),
-                    map(
-                        crate::parser::blocks::footnote_definition::footnote_definition(
-                            state.clone(),
-                        ),
-                        Block::FootnoteDefinition,
-                    ),
+            ),
+            conditional_block(
+                state.config.block_paragraph_behavior.clone(),
+                map(
+                    crate::parser::blocks::paragraph::paragraph(state.clone(), false),
+                    Block::Paragraph,
                 ),
-                conditional_block(
-                    state.config.block_link_definition_behavior.clone(),
-                    map(
-                        crate::parser::blocks::link_definition::link_definition(state.clone()),
-                        Block::Definition,
-                    ),
-                ),
-                custom_parser(state.clone()),
-                conditional_block(
-                    state.config.block_table_behavior.clone(),
-                    map(
-                        crate::parser::blocks::table::table(state.clone()),
-                        Block::Table,
-                    ),
-                ),
-                conditional_block(
-                    state.config.block_paragraph_behavior.clone(),
-                    map(
-                        crate::parser::blocks::paragraph::paragraph(state.clone(), false),
-                        Block::Paragraph,
-                    ),
-                ),
-            )),
-        )
-        .parse(input)
+            ),
+        ))
+        .parse(input)?;
+
+        if state.config.preserve_blank_lines
+            && !state.is_nested_block_context
+            && blank_lines.len() > 1
+        {
+            let mut preserved = vec![Block::Empty; blank_lines.len() - 1];
+            preserved.extend(blocks);
+            return Ok((input, preserved));
+        }
+        Ok((input, blocks))
     }
 }
 
@@ -12,6 +12,21 @@ use nom::{
 use std::borrow::Cow;
 use std::rc::Rc;
 
+/// Indentation stripped from paragraph lines: up to three spaces, or any
+/// amount when indented code blocks are disabled
+fn line_indent<'a>(
+    state: &MarkdownParserState,
+) -> impl Parser<&'a str, Output = (), Error = nom::error::Error<&'a str>> {
+    let any_indent = !state.config.indented_code_blocks;
+    move |input: &'a str| {
+        if any_indent {
+            value((), space0).parse(input)
+        } else {
+            value((), many_m_n(0, 3, char(' '))).parse(input)
+        }
+    }
+}
+
 pub(crate) fn paragraph<'a>(
     state: Rc<MarkdownParserState>,
     check_first_line: bool,
@@ -23,7 +38,7 @@ pub(crate) fn paragraph<'a>(
         } else {
             // Skip checks for the first line, just make it a paragraph
             let (input, first_line) =
-                preceded(many_m_n(0, 3, char(' ')), not_eof_or_eol1).parse(input)?;
+                preceded(line_indent(&state), not_eof_or_eol1).parse(input)?;
             lines.push(first_line);
             input
         };
@@ -32,7 +47,7 @@ pub(crate) fn paragraph<'a>(
             line_ending,
             preceded(
                 is_paragraph_line_start(state.clone()),
-                preceded(many_m_n(0, 3, char(' ')), not_eof_or_eol1),
+                preceded(line_indent(&state), not_eof_or_eol1),
             ),
         );
         let (input, rest_lines) = line_terminated(paragraph_parser).parse(input)?;
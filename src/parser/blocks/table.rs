@@ -103,8 +103,9 @@ fn process_spans(rows: &mut Vec<TableRow>) {
                                     let target_rowspan = target_cell_mut.rowspan.get_or_insert(1);
                                     *target_rowspan += source_rowspan;
 
-                                    let target_colspan = target_cell_mut.colspan.get_or_insert(1);
                                     if let Some(source_colspan) = source_colspan {
+                                        let target_colspan =
+                                            target_cell_mut.colspan.get_or_insert(1);
                                         *target_colspan =
                                             std::cmp::max(*target_colspan, source_colspan);
                                     }
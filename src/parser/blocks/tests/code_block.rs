@@ -1,5 +1,5 @@
 use crate::ast::*;
-use crate::parser::{parse_markdown, MarkdownParserState};
+use crate::parser::{parse_markdown, MarkdownParserConfig, MarkdownParserState};
 
 #[test]
 fn code_block_indented1() {
@@ -29,6 +29,20 @@ fn code_block_indented2() {
     );
 }
 
+#[test]
+fn code_block_indented_with_blank_lines() {
+    let doc = parse_markdown(MarkdownParserState::default(), "    a\n\n  \n    b\n\n").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::CodeBlock(CodeBlock {
+                kind: CodeBlockKind::Indented,
+                literal: "a\n\n\nb".to_owned()
+            })]
+        }
+    );
+}
+
 #[test]
 fn code_block_fenced1() {
     let doc = parse_markdown(MarkdownParserState::default(), "```\na\n```").unwrap();
@@ -86,3 +100,61 @@ fn code_block_fenced4() {
         }
     );
 }
+
+#[test]
+fn code_block_fenced_literal_is_verbatim() {
+    let input = "```\nlet x = 1;  \n\tindented\n\n```";
+    let doc = parse_markdown(MarkdownParserState::default(), input).unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::CodeBlock(CodeBlock {
+                kind: CodeBlockKind::Fenced { info: None },
+                literal: "let x = 1;  \n\tindented\n".to_owned()
+            })]
+        }
+    );
+
+    let doc = parse_markdown(MarkdownParserState::default(), "```\n\n\n```").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::CodeBlock(CodeBlock {
+                kind: CodeBlockKind::Fenced { info: None },
+                literal: "\n".to_owned()
+            })]
+        }
+    );
+}
+
+#[test]
+fn indented_code_blocks_disabled() {
+    let config = MarkdownParserConfig::default().with_indented_code_blocks(false);
+    let state = || MarkdownParserState::with_config(config.clone());
+    let paragraph = |text: &str| Block::Paragraph(vec![Inline::Text(text.to_owned())]);
+
+    let doc = parse_markdown(state(), "    indented text\n    more").unwrap();
+    assert_eq!(doc.blocks, vec![paragraph("indented text\nmore")]);
+
+    let doc = parse_markdown(state(), "Intro\n\n\tTabbed\n\n```\nfenced\n```").unwrap();
+    assert_eq!(
+        doc.blocks,
+        vec![
+            paragraph("Intro"),
+            paragraph("Tabbed"),
+            Block::CodeBlock(CodeBlock {
+                kind: CodeBlockKind::Fenced { info: None },
+                literal: "fenced".to_owned()
+            }),
+        ]
+    );
+
+    let doc = parse_markdown(state(), "- item\n\n      continued").unwrap();
+    let Block::List(list) = &doc.blocks[0] else {
+        panic!("expected a list, got {:?}", doc.blocks);
+    };
+    assert_eq!(
+        list.items[0].blocks,
+        vec![paragraph("item"), paragraph("continued")]
+    );
+}
@@ -108,3 +108,104 @@ fn test_empty_container() {
         })]
     );
 }
+
+#[test]
+fn test_container_closing_fence_length() {
+    let a = r#"::::outer
+:::inner
+some content
+:::
+::::
+after
+"#;
+    let state = MarkdownParserState::new();
+    let doc = parse_markdown(state, a).unwrap();
+    // The shorter fences do not close the outer container
+    assert_eq!(doc.blocks.len(), 2);
+    let Block::Container(outer) = &doc.blocks[0] else {
+        panic!("expected container, got {:?}", doc.blocks[0]);
+    };
+    assert_eq!(outer.kind, "outer");
+    assert_eq!(
+        doc.blocks[1],
+        Block::Paragraph(vec![Inline::Text("after".to_string())])
+    );
+}
+
+#[test]
+fn test_container_closed_by_longer_fence() {
+    let a = ":::a\nsome content\n  :::::  \n";
+    let state = MarkdownParserState::new();
+    let doc = parse_markdown(state, a).unwrap();
+    assert_eq!(
+        doc.blocks,
+        vec![Block::Container(Container {
+            kind: "a".to_string(),
+            params: vec![],
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "some content".to_string()
+            )])]
+        })]
+    );
+}
+
+#[test]
+fn test_nested_container() {
+    let a = r#"::::note
+Read this first.
+
+:::details{summary=More}
+Hidden text
+:::
+::::
+"#;
+    let state = MarkdownParserState::new();
+    let doc = parse_markdown(state, a).unwrap();
+    assert_eq!(
+        doc.blocks,
+        vec![Block::Container(Container {
+            kind: "note".to_string(),
+            params: vec![],
+            blocks: vec![
+                Block::Paragraph(vec![Inline::Text("Read this first.".to_string())]),
+                Block::Container(Container {
+                    kind: "details".to_string(),
+                    params: vec![("summary".to_string(), "More".to_string())],
+                    blocks: vec![Block::Paragraph(vec![Inline::Text(
+                        "Hidden text".to_string()
+                    )])]
+                })
+            ]
+        })]
+    );
+}
+
+#[test]
+fn test_nested_container_same_fence_length() {
+    let a = r#":::note
+:::details
+Hidden text
+:::
+:::
+after
+"#;
+    let state = MarkdownParserState::new();
+    let doc = parse_markdown(state, a).unwrap();
+    assert_eq!(
+        doc.blocks,
+        vec![
+            Block::Container(Container {
+                kind: "note".to_string(),
+                params: vec![],
+                blocks: vec![Block::Container(Container {
+                    kind: "details".to_string(),
+                    params: vec![],
+                    blocks: vec![Block::Paragraph(vec![Inline::Text(
+                        "Hidden text".to_string()
+                    )])]
+                })]
+            }),
+            Block::Paragraph(vec![Inline::Text("after".to_string())])
+        ]
+    );
+}
@@ -38,3 +38,98 @@ fn footnote_definition2() {
         }
     );
 }
+
+#[test]
+fn footnote_definition_multiple_paragraphs() {
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "[^foo]: paragraph1
+
+    paragraph2
+
+after",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![
+                Block::FootnoteDefinition(FootnoteDefinition {
+                    label: "foo".to_owned(),
+                    blocks: vec![
+                        Block::Paragraph(vec![Inline::Text("paragraph1".to_owned())]),
+                        Block::Paragraph(vec![Inline::Text("paragraph2".to_owned())]),
+                    ]
+                }),
+                Block::Paragraph(vec![Inline::Text("after".to_owned())]),
+            ]
+        }
+    );
+}
+
+#[test]
+fn footnote_definition_with_list() {
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "[^foo]: intro
+
+    - item1
+    - item2
+",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::FootnoteDefinition(FootnoteDefinition {
+                label: "foo".to_owned(),
+                blocks: vec![
+                    Block::Paragraph(vec![Inline::Text("intro".to_owned())]),
+                    Block::List(List {
+                        kind: ListKind::Bullet(ListBulletKind::Dash),
+                        items: vec![
+                            ListItem {
+                                task: None,
+                                blocks: vec![Block::Paragraph(vec![Inline::Text(
+                                    "item1".to_owned()
+                                )])]
+                            },
+                            ListItem {
+                                task: None,
+                                blocks: vec![Block::Paragraph(vec![Inline::Text(
+                                    "item2".to_owned()
+                                )])]
+                            },
+                        ],
+                        tight: true,
+                    }),
+                ]
+            })]
+        }
+    );
+}
+
+#[test]
+fn footnote_definition_ends_at_non_indented_line() {
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "[^foo]: definition
+
+not a footnote",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![
+                Block::FootnoteDefinition(FootnoteDefinition {
+                    label: "foo".to_owned(),
+                    blocks: vec![Block::Paragraph(vec![Inline::Text(
+                        "definition".to_owned()
+                    )])]
+                }),
+                Block::Paragraph(vec![Inline::Text("not a footnote".to_owned())]),
+            ]
+        }
+    );
+}
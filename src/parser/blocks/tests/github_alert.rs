@@ -1,5 +1,5 @@
 use crate::ast::*;
-use crate::parser::{parse_markdown, MarkdownParserState};
+use crate::parser::{parse_markdown, MarkdownParserConfig, MarkdownParserState};
 use crate::printer::{config::Config as PrinterConfig, render_markdown};
 
 #[test]
@@ -476,3 +476,66 @@ fn github_alert_standard_types_roundtrip() {
         assert_eq!(doc1, doc2, "Roundtrip failed for input: {}", input);
     }
 }
+
+#[test]
+fn github_alert_warning_roundtrip_wraps_to_width() {
+    let input = "> [!WARNING]\n> Do not feed the gremlins after midnight, whatever they say";
+    let config = PrinterConfig::default().with_width(30);
+
+    let doc1 = parse_markdown(MarkdownParserState::default(), input).unwrap();
+    let rendered = render_markdown(&doc1, config.clone());
+    assert_eq!(
+        rendered,
+        "> [!WARNING]\n> Do not feed the gremlins\n> after midnight, whatever\n> they say"
+    );
+
+    // Wrapping introduces soft breaks, so compare the re-rendered output
+    let doc2 = parse_markdown(MarkdownParserState::default(), &rendered).unwrap();
+    assert!(matches!(
+        doc2.blocks.as_slice(),
+        [Block::GitHubAlert(GitHubAlert {
+            alert_type: GitHubAlertType::Warning,
+            ..
+        })]
+    ));
+    assert_eq!(render_markdown(&doc2, config), rendered);
+}
+
+#[test]
+fn github_alert_custom_roundtrip_in_list() {
+    let input = "- > [!DEPRECATED]\n  > Use the new API";
+
+    let doc1 = parse_markdown(MarkdownParserState::default(), input).unwrap();
+    let rendered = render_markdown(&doc1, PrinterConfig::default().with_width(20));
+    assert_eq!(rendered, " - > [!DEPRECATED]\n   > Use the new API");
+
+    let doc2 = parse_markdown(MarkdownParserState::default(), &rendered).unwrap();
+    assert_eq!(doc1, doc2);
+}
+
+#[test]
+fn github_alert_custom_disabled() {
+    let config = MarkdownParserConfig::default().with_custom_github_alerts(false);
+    let doc = parse_markdown(
+        MarkdownParserState::with_config(config.clone()),
+        "> [!FOO]\n> Not an alert",
+    )
+    .unwrap();
+    assert!(matches!(doc.blocks.as_slice(), [Block::BlockQuote(_)]));
+
+    // Standard types are still recognized
+    let doc = parse_markdown(
+        MarkdownParserState::with_config(config),
+        "> [!WARNING]\n> Careful",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::GitHubAlert(GitHubAlert {
+                alert_type: GitHubAlertType::Warning,
+                blocks: vec![Block::Paragraph(vec![Inline::Text("Careful".to_string())])],
+            })],
+        }
+    );
+}
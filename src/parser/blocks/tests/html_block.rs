@@ -1,5 +1,5 @@
 use crate::ast::*;
-use crate::parser::{parse_markdown, MarkdownParserState};
+use crate::parser::{parse_markdown, MarkdownParserConfig, MarkdownParserState};
 
 #[test]
 fn html_block1() {
@@ -7,7 +7,10 @@ fn html_block1() {
     assert_eq!(
         doc,
         Document {
-            blocks: vec![Block::HtmlBlock("<script>\n</script>".to_owned())]
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<script>\n</script>".to_owned(),
+            })]
         }
     );
 
@@ -19,9 +22,10 @@ fn html_block1() {
     assert_eq!(
         doc,
         Document {
-            blocks: vec![Block::HtmlBlock(
-                "<script>\n\n<h1>hello</h1></script>".to_owned()
-            )]
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<script>\n\n<h1>hello</h1></script>".to_owned(),
+            })]
         }
     );
 }
@@ -36,9 +40,10 @@ fn html_block2() {
     assert_eq!(
         doc,
         Document {
-            blocks: vec![Block::HtmlBlock(
-                "<!-- \n\nsome commented\n out code -->".to_owned()
-            )]
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<!-- \n\nsome commented\n out code -->".to_owned(),
+            })]
         }
     );
 }
@@ -53,7 +58,10 @@ fn html_block3() {
     assert_eq!(
         doc,
         Document {
-            blocks: vec![Block::HtmlBlock("<? \n\nsome \n   code ?>".to_owned())]
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<? \n\nsome \n   code ?>".to_owned(),
+            })]
         }
     );
 }
@@ -64,7 +72,10 @@ fn html_block4() {
     assert_eq!(
         doc,
         Document {
-            blocks: vec![Block::HtmlBlock("<!A some \n\n\n text >".to_owned())]
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<!A some \n\n\n text >".to_owned(),
+            })]
         }
     );
 }
@@ -79,7 +90,10 @@ fn html_block5() {
     assert_eq!(
         doc,
         Document {
-            blocks: vec![Block::HtmlBlock("<![CDATA[ ]\n\n[[]]<> ]]>".to_owned())]
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<![CDATA[ ]\n\n[[]]<> ]]>".to_owned(),
+            })]
         }
     );
 }
@@ -90,7 +104,10 @@ fn html_block6() {
     assert_eq!(
         doc,
         Document {
-            blocks: vec![Block::HtmlBlock("<body  \n".to_owned())]
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<body  \n".to_owned(),
+            })]
         }
     );
 
@@ -102,9 +119,10 @@ fn html_block6() {
     assert_eq!(
         doc,
         Document {
-            blocks: vec![Block::HtmlBlock(
-                "<body a b=c d='e' f=\"g\" >\n</body>\n".to_owned()
-            )]
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<body a b=c d='e' f=\"g\" >\n</body>\n".to_owned(),
+            })]
         }
     );
 
@@ -112,7 +130,10 @@ fn html_block6() {
     assert_eq!(
         doc,
         Document {
-            blocks: vec![Block::HtmlBlock("</body> <p>\n</p>\n".to_owned())]
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "</body> <p>\n</p>\n".to_owned(),
+            })]
         }
     );
 }
@@ -179,3 +200,79 @@ fn html_block_ignore1() {
         }
     );
 }
+
+#[test]
+fn html_block_gfm_tagfilter() {
+    let config = MarkdownParserConfig::default().with_gfm_tagfilter();
+    let doc = parse_markdown(
+        MarkdownParserState::with_config(config.clone()),
+        "<script>alert(1)</SCRIPT>",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "&lt;script>alert(1)&lt;/SCRIPT>".to_owned(),
+            })]
+        }
+    );
+
+    let doc = parse_markdown(
+        MarkdownParserState::with_config(config),
+        "<div class=\"x\">\n<scripts><iframe src=\"a\"/>\n</div>",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<div class=\"x\">\n<scripts>&lt;iframe src=\"a\"/>\n</div>".to_owned(),
+            })]
+        }
+    );
+
+    let doc = parse_markdown(MarkdownParserState::default(), "<script>\n</script>").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::HtmlBlock(HtmlBlock {
+                kind: None,
+                content: "<script>\n</script>".to_owned(),
+            })]
+        }
+    );
+}
+
+#[test]
+fn html_block_classification() {
+    let input = "<!-- comment -->\n\n<script>\nlet a = 1;\n\nlet b = 2;\n</script>\n\n<div class=\"box\">\ncontent\n</div>\n\n<custom-tag>\ntext\n</custom-tag>\n\n<?php echo 1; ?>\n\n<!DOCTYPE html>\n\n<![CDATA[x]]>";
+    let kinds = |state: MarkdownParserState| -> Vec<_> {
+        parse_markdown(state, input)
+            .unwrap()
+            .blocks
+            .into_iter()
+            .map(|block| match block {
+                Block::HtmlBlock(html) => html.kind,
+                other => panic!("unexpected block {other:?}"),
+            })
+            .collect()
+    };
+
+    let config = MarkdownParserConfig::default().with_html_block_kinds();
+    assert_eq!(
+        kinds(MarkdownParserState::with_config(config)),
+        vec![
+            Some(HtmlBlockKind::Comment),
+            Some(HtmlBlockKind::Raw),
+            Some(HtmlBlockKind::BlockTag),
+            Some(HtmlBlockKind::OtherTag),
+            Some(HtmlBlockKind::ProcessingInstruction),
+            Some(HtmlBlockKind::Declaration),
+            Some(HtmlBlockKind::CData),
+        ]
+    );
+    assert_eq!(kinds(MarkdownParserState::default()), vec![None; 7]);
+}
@@ -8,11 +8,15 @@ fn list1() {
         doc,
         Document {
             blocks: vec![Block::List(List {
-                kind: ListKind::Ordered(ListOrderedKindOptions { start: 1 }),
+                kind: ListKind::Ordered(ListOrderedKindOptions {
+                    start: 1,
+                    style: OrderedListStyle::Decimal,
+                }),
                 items: vec![ListItem {
                     task: None,
                     blocks: vec![Block::Paragraph(vec![Inline::Text("a".to_owned())])]
-                }]
+                }],
+                tight: true,
             })]
         }
     );
@@ -25,11 +29,15 @@ fn list2() {
         doc,
         Document {
             blocks: vec![Block::List(List {
-                kind: ListKind::Ordered(ListOrderedKindOptions { start: 100 }),
+                kind: ListKind::Ordered(ListOrderedKindOptions {
+                    start: 100,
+                    style: OrderedListStyle::Decimal,
+                }),
                 items: vec![ListItem {
                     task: None,
                     blocks: vec![Block::Paragraph(vec![Inline::Text("a".to_owned())])]
-                }]
+                }],
+                tight: true,
             })]
         }
     );
@@ -42,11 +50,15 @@ fn list3() {
         doc,
         Document {
             blocks: vec![Block::List(List {
-                kind: ListKind::Ordered(ListOrderedKindOptions { start: 1 }),
+                kind: ListKind::Ordered(ListOrderedKindOptions {
+                    start: 1,
+                    style: OrderedListStyle::Decimal,
+                }),
                 items: vec![ListItem {
                     task: None,
                     blocks: vec![Block::Paragraph(vec![Inline::Text("a".to_owned())])]
-                }]
+                }],
+                tight: true,
             })]
         }
     );
@@ -63,7 +75,8 @@ fn list4() {
                 items: vec![ListItem {
                     task: None,
                     blocks: vec![Block::Paragraph(vec![Inline::Text("a".to_owned())])]
-                }]
+                }],
+                tight: true,
             })]
         }
     );
@@ -76,7 +89,10 @@ fn list5() {
         doc,
         Document {
             blocks: vec![Block::List(List {
-                kind: ListKind::Ordered(ListOrderedKindOptions { start: 1 }),
+                kind: ListKind::Ordered(ListOrderedKindOptions {
+                    start: 1,
+                    style: OrderedListStyle::Decimal,
+                }),
                 items: vec![
                     ListItem {
                         task: None,
@@ -86,7 +102,8 @@ fn list5() {
                         task: None,
                         blocks: vec![Block::Paragraph(vec![Inline::Text("b".to_owned())])]
                     }
-                ]
+                ],
+                tight: true,
             })]
         }
     );
@@ -103,7 +120,8 @@ fn list6() {
                 items: vec![ListItem {
                     task: None,
                     blocks: vec![Block::Paragraph(vec![Inline::Text("a\nb".to_owned())])]
-                }]
+                }],
+                tight: true,
             })]
         }
     );
@@ -121,7 +139,8 @@ fn list7() {
                     items: vec![ListItem {
                         task: None,
                         blocks: vec![Block::Paragraph(vec![Inline::Text("a\nb".to_owned())])]
-                    }]
+                    }],
+                    tight: true,
                 }),
                 Block::Paragraph(vec![Inline::Text("c".to_owned())])
             ]
@@ -143,7 +162,8 @@ fn list8() {
                         Block::Paragraph(vec![Inline::Text("a\nb".to_owned())]),
                         Block::Paragraph(vec![Inline::Text("c".to_owned())]),
                     ]
-                }]
+                }],
+                tight: false,
             })]
         },
     );
@@ -160,7 +180,10 @@ fn list9() {
         doc,
         Document {
             blocks: vec![Block::List(List {
-                kind: ListKind::Ordered(ListOrderedKindOptions { start: 1 }),
+                kind: ListKind::Ordered(ListOrderedKindOptions {
+                    start: 1,
+                    style: OrderedListStyle::Decimal,
+                }),
                 items: vec![
                     ListItem {
                         task: None,
@@ -181,7 +204,8 @@ fn list9() {
                                             "list2".to_owned()
                                         )]),]
                                     }
-                                ]
+                                ],
+                                tight: true,
                             })
                         ]
                     },
@@ -189,7 +213,8 @@ fn list9() {
                         task: None,
                         blocks: vec![Block::Paragraph(vec![Inline::Text("list1".to_owned())])]
                     }
-                ]
+                ],
+                tight: true,
             })]
         },
     );
@@ -212,7 +237,8 @@ fn list10() {
                         task: None,
                         blocks: vec![Block::Paragraph(vec![Inline::Text("list1".to_owned())])]
                     }
-                ]
+                ],
+                tight: true,
             })]
         },
     );
@@ -229,7 +255,8 @@ fn task_list1() {
                 items: vec![ListItem {
                     task: Some(TaskState::Incomplete),
                     blocks: vec![Block::Paragraph(vec![Inline::Text("a".to_owned())])]
-                }]
+                }],
+                tight: true,
             })]
         },
     );
@@ -246,7 +273,8 @@ fn task_list2() {
                 items: vec![ListItem {
                     task: Some(TaskState::Complete),
                     blocks: vec![Block::Paragraph(vec![Inline::Text("a".to_owned())])]
-                }]
+                }],
+                tight: true,
             })]
         },
     );
@@ -263,7 +291,8 @@ fn task_list3() {
                 items: vec![ListItem {
                     task: Some(TaskState::Complete),
                     blocks: vec![Block::Paragraph(vec![Inline::Text("a".to_owned())])]
-                }]
+                }],
+                tight: true,
             })]
         },
     );
@@ -280,7 +309,8 @@ fn task_list4() {
                 items: vec![ListItem {
                     task: Some(TaskState::Incomplete),
                     blocks: vec![]
-                }]
+                }],
+                tight: true,
             })]
         },
     );
@@ -297,8 +327,184 @@ fn task_list5() {
                 items: vec![ListItem {
                     task: Some(TaskState::Incomplete),
                     blocks: vec![Block::Paragraph(vec![Inline::Text("a".to_owned())])]
-                }]
+                }],
+                tight: true,
+            })]
+        },
+    );
+}
+
+#[test]
+fn tight_and_loose_lists() {
+    let doc = parse_markdown(MarkdownParserState::default(), "- a\n- b").unwrap();
+    assert!(matches!(
+        &doc.blocks[..],
+        [Block::List(List { tight: true, .. })]
+    ));
+
+    let doc = parse_markdown(MarkdownParserState::default(), "- a\n\n- b").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::List(List {
+                kind: ListKind::Bullet(ListBulletKind::Dash),
+                items: vec![
+                    ListItem {
+                        task: None,
+                        blocks: vec![Block::Paragraph(vec![Inline::Text("a".to_owned())])]
+                    },
+                    ListItem {
+                        task: None,
+                        blocks: vec![Block::Paragraph(vec![Inline::Text("b".to_owned())])]
+                    }
+                ],
+                tight: false,
             })]
         },
     );
+
+    // A blank line followed by a different marker starts a new list
+    let doc = parse_markdown(MarkdownParserState::default(), "- a\n\n* b").unwrap();
+    assert!(matches!(
+        &doc.blocks[..],
+        [
+            Block::List(List { tight: true, .. }),
+            Block::List(List { tight: true, .. })
+        ]
+    ));
+
+    // A blank line between the children of one item makes the list loose
+    let doc = parse_markdown(MarkdownParserState::default(), "- a\n\n  b\n").unwrap();
+    assert!(matches!(
+        &doc.blocks[..],
+        [Block::List(List { tight: false, .. })]
+    ));
+
+    // Blank lines inside a nested list do not make the outer list loose
+    let doc = parse_markdown(MarkdownParserState::default(), "- a\n  - b\n\n  - c\n- d").unwrap();
+    let Block::List(outer) = &doc.blocks[0] else {
+        panic!("expected list");
+    };
+    assert!(outer.tight);
+    assert!(matches!(
+        &outer.items[0].blocks[..],
+        [Block::Paragraph(_), Block::List(List { tight: false, .. })]
+    ));
+}
+
+#[test]
+fn ordered_list_arbitrary_start() {
+    let item = |text: &str| ListItem {
+        task: None,
+        blocks: vec![Block::Paragraph(vec![Inline::Text(text.to_owned())])],
+    };
+
+    // Item numbers after the first do not matter
+    let doc = parse_markdown(MarkdownParserState::default(), "3. a\n7. b\n0. c").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::List(List {
+                kind: ListKind::Ordered(ListOrderedKindOptions {
+                    start: 3,
+                    style: OrderedListStyle::Decimal,
+                }),
+                items: vec![item("a"), item("b"), item("c")],
+                tight: true,
+            })]
+        }
+    );
+
+    let doc = parse_markdown(MarkdownParserState::default(), "   3. a\n   4. b").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::List(List {
+                kind: ListKind::Ordered(ListOrderedKindOptions {
+                    start: 3,
+                    style: OrderedListStyle::Decimal,
+                }),
+                items: vec![item("a"), item("b")],
+                tight: true,
+            })]
+        }
+    );
+
+    // A paragraph between items ends the list
+    let doc = parse_markdown(MarkdownParserState::default(), "3. a\n\ntext\n\n4. b").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![
+                Block::List(List {
+                    kind: ListKind::Ordered(ListOrderedKindOptions {
+                        start: 3,
+                        style: OrderedListStyle::Decimal,
+                    }),
+                    items: vec![item("a")],
+                    tight: true,
+                }),
+                Block::Paragraph(vec![Inline::Text("text".to_owned())]),
+                Block::List(List {
+                    kind: ListKind::Ordered(ListOrderedKindOptions {
+                        start: 4,
+                        style: OrderedListStyle::Decimal,
+                    }),
+                    items: vec![item("b")],
+                    tight: true,
+                }),
+            ]
+        }
+    );
+}
+
+#[test]
+fn alphabetic_and_roman_list_markers() {
+    let item = |text: &str| ListItem {
+        task: None,
+        blocks: vec![Block::Paragraph(vec![Inline::Text(text.to_owned())])],
+    };
+    let ordered = |start, style| {
+        Block::List(List {
+            kind: ListKind::Ordered(ListOrderedKindOptions { start, style }),
+            items: vec![item("one"), item("two")],
+            tight: true,
+        })
+    };
+    let state = || {
+        MarkdownParserState::with_config(
+            crate::parser::config::MarkdownParserConfig::default().with_alphabetic_list_markers(),
+        )
+    };
+
+    for (input, expected) in [
+        ("a. one\nb. two", ordered(1, OrderedListStyle::LowerAlpha)),
+        ("C) one\nD) two", ordered(3, OrderedListStyle::UpperAlpha)),
+        ("i. one\nii. two", ordered(1, OrderedListStyle::LowerRoman)),
+        (
+            "XIV. one\nXV. two",
+            ordered(14, OrderedListStyle::UpperRoman),
+        ),
+        ("v. one\nw. two", ordered(22, OrderedListStyle::LowerAlpha)),
+        (
+            "aa. one\nab. two",
+            ordered(27, OrderedListStyle::LowerAlpha),
+        ),
+        (
+            "VX. one\nVY. two",
+            ordered(596, OrderedListStyle::UpperAlpha),
+        ),
+        ("2. one\n3. two", ordered(2, OrderedListStyle::Decimal)),
+    ] {
+        let doc = parse_markdown(state(), input).unwrap();
+        assert_eq!(doc.blocks, vec![expected], "{input:?}");
+    }
+
+    // Not list markers: mixed case, or the option off
+    for input in ["Ab. one", "aB. one"] {
+        let doc = parse_markdown(state(), input).unwrap();
+        assert!(matches!(doc.blocks[0], Block::Paragraph(_)), "{input:?}");
+    }
+    let doc = parse_markdown(MarkdownParserState::default(), "a. one").unwrap();
+    assert!(matches!(doc.blocks[0], Block::Paragraph(_)));
 }
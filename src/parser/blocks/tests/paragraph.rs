@@ -1,5 +1,5 @@
 use crate::ast::*;
-use crate::parser::{parse_markdown, MarkdownParserState};
+use crate::parser::{parse_markdown, MarkdownParserConfig, MarkdownParserState};
 
 #[test]
 fn minimal_paragraph() {
@@ -94,3 +94,32 @@ fn paragraph_with_indented_line1() {
         }
     );
 }
+
+#[test]
+fn paragraphs_separated_by_blank_lines() {
+    let input = "a\n\n\n\nb\n\nc";
+    let paragraph = |text: &str| Block::Paragraph(vec![Inline::Text(text.to_string())]);
+
+    let doc = parse_markdown(MarkdownParserState::default(), input).unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![paragraph("a"), paragraph("b"), paragraph("c")],
+        }
+    );
+
+    let config = MarkdownParserConfig::default().with_preserve_blank_lines();
+    let doc = parse_markdown(MarkdownParserState::with_config(config), input).unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![
+                paragraph("a"),
+                Block::Empty,
+                Block::Empty,
+                paragraph("b"),
+                paragraph("c"),
+            ],
+        }
+    );
+}
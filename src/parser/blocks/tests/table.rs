@@ -485,7 +485,7 @@ fn table_with_merged_cells() {
                         },
                         TableCell {
                             content: vec![Inline::Text("A3".to_owned())],
-                            colspan: Some(1),
+                            colspan: None,
                             rowspan: Some(2),
                             removed_by_extended_table: false
                         }
@@ -516,3 +516,19 @@ fn table_with_merged_cells() {
         }
     );
 }
+
+#[cfg(feature = "typst-printer")]
+#[test]
+fn table_with_merged_cells_renders_spans_in_typst() {
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "| A1 | < | A3 |
+| :-- | :-: | --: |
+| B1 | B2 | ^ |",
+    )
+    .unwrap();
+    let result =
+        crate::typst_printer::render_typst(&doc, crate::typst_printer::config::Config::default());
+    assert!(result.contains(r##"table.cell(colspan: 2)[#"A1"],  table.cell(rowspan: 2)[#"A3"],"##));
+    assert!(result.contains(r##"[#"B1"],  [#"B2"],"##));
+}
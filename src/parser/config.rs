@@ -1,6 +1,6 @@
 use nom::IResult;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 /// Function type for mapping elements.
@@ -46,6 +46,28 @@ pub struct MarkdownParserConfig {
     /// If true, the parser will allow headings without a space after the hash marks.
     pub(crate) allow_no_space_in_headings: bool,
 
+    /// Lowercased URI schemes recognized in `<...>` autolinks. `None` accepts
+    /// any syntactically valid scheme, as CommonMark does.
+    pub(crate) autolink_schemes: Option<HashSet<String>>,
+
+    /// If true, raw HTML blocks are passed through the GFM tag filter.
+    pub(crate) gfm_tagfilter: bool,
+
+    /// If true, raw HTML blocks record their CommonMark kind.
+    pub(crate) html_block_kinds: bool,
+
+    /// If true, runs of several blank lines between top-level blocks are kept
+    /// as `Block::Empty` markers, one per blank line beyond the first.
+    pub(crate) preserve_blank_lines: bool,
+
+    /// URL template for `@user` mentions, with `{user}` as placeholder.
+    /// Mentions are left as text if `None`.
+    pub(crate) mention_url: Option<String>,
+
+    /// URL template for `#123` issue references, with `{number}` as
+    /// placeholder. References are left as text if `None`.
+    pub(crate) issue_url: Option<String>,
+
     /// A map of HTML entities to their corresponding `Entity` structs.
     pub(crate) html_entities_map: HashMap<String, &'static entities::Entity>,
 
@@ -55,6 +77,10 @@ pub struct MarkdownParserConfig {
     /// The behavior of the parser when encountering GitHub alerts.
     pub(crate) block_github_alert_behavior: ElementBehavior<crate::ast::Block>,
 
+    /// If true, unknown alert markers such as `[!FOO]` produce
+    /// `GitHubAlertType::Custom`; otherwise they stay regular blockquotes.
+    pub(crate) custom_github_alerts: bool,
+
     /// The behavior of the parser when encountering headings in style 1 (e.g., `# Heading`).
     pub(crate) block_heading_v1_behavior: ElementBehavior<crate::ast::Block>,
 
@@ -67,6 +93,14 @@ pub struct MarkdownParserConfig {
     /// The behavior of the parser when encountering lists.
     pub(crate) block_list_behavior: ElementBehavior<crate::ast::Block>,
 
+    /// If true, ordered list markers may also be letters (`a.`, `B)`) or
+    /// roman numerals (`iv.`, `IX)`).
+    pub(crate) alphabetic_list_markers: bool,
+
+    /// If false, lines indented by four spaces or a tab do not start a code
+    /// block; only fenced code blocks are recognized.
+    pub(crate) indented_code_blocks: bool,
+
     /// The behavior of the parser when encountering code blocks.
     pub(crate) block_code_block_behavior: ElementBehavior<crate::ast::Block>,
 
@@ -112,12 +146,30 @@ pub struct MarkdownParserConfig {
     /// The behavior of the parser when encountering inline emphasis.
     pub(crate) inline_emphasis_behavior: ElementBehavior<crate::ast::Inline>,
 
+    /// How deeply emphasis may nest before further delimiters are kept as text.
+    pub(crate) max_emphasis_nesting: usize,
+
+    /// Whether emphasis keeps the delimiter run it was written with.
+    pub(crate) preserve_emphasis_delimiters: bool,
+
     /// The behavior of the parser when encountering inline strikethrough.
     pub(crate) inline_strikethrough_behavior: ElementBehavior<crate::ast::Inline>,
 
+    /// The behavior of the parser when encountering `==highlight==`.
+    pub(crate) inline_highlight_behavior: ElementBehavior<crate::ast::Inline>,
+
+    /// The behavior of the parser when encountering `~subscript~`.
+    pub(crate) inline_subscript_behavior: ElementBehavior<crate::ast::Inline>,
+
+    /// The behavior of the parser when encountering `^superscript^`.
+    pub(crate) inline_superscript_behavior: ElementBehavior<crate::ast::Inline>,
+
     /// The behavior of the parser when encountering inline text.
     pub(crate) inline_text_behavior: ElementBehavior<crate::ast::Inline>,
 
+    /// Whether newlines within a paragraph become `Inline::SoftBreak`.
+    pub(crate) preserve_soft_breaks: bool,
+
     /// A custom parser for blocks. This is a function that takes a string and returns a `Block`.
     pub(crate) custom_block_parser: Option<CustomBlockParserFn>,
 
@@ -132,13 +184,22 @@ impl Default for MarkdownParserConfig {
     fn default() -> Self {
         Self {
             allow_no_space_in_headings: false,
+            autolink_schemes: None,
+            gfm_tagfilter: false,
+            html_block_kinds: false,
+            preserve_blank_lines: false,
+            mention_url: None,
+            issue_url: None,
             html_entities_map: Self::make_html_entities_map(),
             block_blockquote_behavior: ElementBehavior::Parse,
             block_github_alert_behavior: ElementBehavior::Parse,
+            custom_github_alerts: true,
             block_heading_v1_behavior: ElementBehavior::Parse,
             block_heading_v2_behavior: ElementBehavior::Parse,
             block_thematic_break_behavior: ElementBehavior::Parse,
             block_list_behavior: ElementBehavior::Parse,
+            alphabetic_list_markers: false,
+            indented_code_blocks: true,
             block_code_block_behavior: ElementBehavior::Parse,
             block_html_block_behavior: ElementBehavior::Parse,
             block_footnote_definition_behavior: ElementBehavior::Parse,
@@ -154,8 +215,14 @@ impl Default for MarkdownParserConfig {
             inline_image_behavior: ElementBehavior::Parse,
             inline_code_span_behavior: ElementBehavior::Parse,
             inline_emphasis_behavior: ElementBehavior::Parse,
+            max_emphasis_nesting: 32,
+            preserve_emphasis_delimiters: false,
             inline_strikethrough_behavior: ElementBehavior::Parse,
+            inline_highlight_behavior: ElementBehavior::Ignore,
+            inline_subscript_behavior: ElementBehavior::Ignore,
+            inline_superscript_behavior: ElementBehavior::Ignore,
             inline_text_behavior: ElementBehavior::Parse,
+            preserve_soft_breaks: false,
             custom_block_parser: None,
             custom_inline_parser: None,
             inline_macro_replacer: None,
@@ -180,6 +247,118 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Only recognize `<scheme:...>` autolinks whose scheme is in `schemes`.
+    ///
+    /// Schemes are compared case-insensitively. A `<...>` with any other
+    /// scheme is parsed as plain text. Email autolinks (`<user@example.com>`)
+    /// are not affected.
+    ///
+    /// ```rust
+    /// use markdown_ppp::parser::config::MarkdownParserConfig;
+    ///
+    /// let config = MarkdownParserConfig::default()
+    ///     .with_autolink_schemes(["http", "https", "mailto", "ftp"]);
+    /// ```
+    pub fn with_autolink_schemes<I, S>(self, schemes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            autolink_schemes: Some(
+                schemes
+                    .into_iter()
+                    .map(|scheme| scheme.as_ref().to_ascii_lowercase())
+                    .collect(),
+            ),
+            ..self
+        }
+    }
+
+    /// Enable the GFM tag filter for raw HTML.
+    ///
+    /// Tags disallowed by GFM (`<script>`, `<style>`, `<iframe>`, …) are
+    /// neutralized by replacing their leading `<` with `&lt;`.
+    pub fn with_gfm_tagfilter(self) -> Self {
+        Self {
+            gfm_tagfilter: true,
+            ..self
+        }
+    }
+
+    /// Record the CommonMark kind of each raw HTML block.
+    ///
+    /// The kind is stored in `HtmlBlock::kind`, so downstream tools can, for
+    /// example, drop comments but keep `<pre>` blocks. Without this option the
+    /// field is `None`.
+    pub fn with_html_block_kinds(self) -> Self {
+        Self {
+            html_block_kinds: true,
+            ..self
+        }
+    }
+
+    /// Keep runs of blank lines between top-level blocks.
+    ///
+    /// By default any number of blank lines between blocks is collapsed. With
+    /// this option each blank line beyond the first becomes a `Block::Empty`,
+    /// which the Markdown printer turns back into a blank line when its
+    /// `with_preserve_blank_lines` option is set.
+    pub fn with_preserve_blank_lines(self) -> Self {
+        Self {
+            preserve_blank_lines: true,
+            ..self
+        }
+    }
+
+    /// Turn `@user` mentions in text into links.
+    ///
+    /// `template` is the link destination, with `{user}` replaced by the
+    /// user name. A mention is only recognized at the start of a word, so
+    /// e-mail addresses are left alone, and never in code or link text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::parser::{config::MarkdownParserConfig, parse_markdown, MarkdownParserState};
+    ///
+    /// let config = MarkdownParserConfig::default()
+    ///     .with_mention_url("https://github.com/{user}")
+    ///     .with_issue_url("https://github.com/owner/repo/issues/{number}");
+    /// let doc = parse_markdown(MarkdownParserState::with_config(config), "Thanks @octocat!").unwrap();
+    /// assert_eq!(
+    ///     doc.blocks[0],
+    ///     Block::Paragraph(vec![
+    ///         Inline::Text("Thanks ".to_string()),
+    ///         Inline::Link(Link {
+    ///             destination: "https://github.com/octocat".to_string(),
+    ///             title: None,
+    ///             children: vec![Inline::Text("@octocat".to_string())],
+    ///         }),
+    ///         Inline::Text("!".to_string()),
+    ///     ])
+    /// );
+    /// ```
+    pub fn with_mention_url(self, template: impl Into<String>) -> Self {
+        Self {
+            mention_url: Some(template.into()),
+            ..self
+        }
+    }
+
+    /// Turn `#123` issue references in text into links.
+    ///
+    /// `template` is the link destination, with `{number}` replaced by the
+    /// issue number. Like mentions, references are only recognized at the
+    /// start of a word and never in code or link text.
+    pub fn with_issue_url(self, template: impl Into<String>) -> Self {
+        Self {
+            issue_url: Some(template.into()),
+            ..self
+        }
+    }
+
     /// Set a custom map of HTML entities.
     pub fn with_html_entities_map(
         self,
@@ -213,6 +392,18 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Set whether unknown GitHub alert markers are parsed as custom alerts.
+    ///
+    /// Enabled by default: `> [!FOO]` becomes `GitHubAlertType::Custom("FOO")`.
+    /// When disabled, only the five standard alert types are recognized and
+    /// any other marker is parsed as a regular blockquote.
+    pub fn with_custom_github_alerts(self, enabled: bool) -> Self {
+        Self {
+            custom_github_alerts: enabled,
+            ..self
+        }
+    }
+
     /// Set the behavior of the parser when encountering headings in style 1 (e.g., `# Heading`).
     pub fn with_block_heading_v1_behavior(
         self,
@@ -254,6 +445,32 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Accept letters and roman numerals as ordered list markers.
+    ///
+    /// A well-formed roman numeral in one case starts a roman list (`i.`,
+    /// `XIV)`); any other run of letters in one case, including a lone `v`
+    /// or `x`, an alphabetic one (`a.`, `C)`, `aa.`).
+    /// The style is recorded in `ListOrderedKindOptions::style`. This is not
+    /// CommonMark, where `a. text` is a paragraph, so it is off by default.
+    pub fn with_alphabetic_list_markers(self) -> Self {
+        Self {
+            alphabetic_list_markers: true,
+            ..self
+        }
+    }
+
+    /// Set whether indented lines start a code block.
+    ///
+    /// Enabled by default, as in CommonMark. When disabled, only fenced code
+    /// blocks are recognized and text indented by four or more spaces is
+    /// parsed as a paragraph, or as the continuation of the paragraph above.
+    pub fn with_indented_code_blocks(self, enabled: bool) -> Self {
+        Self {
+            indented_code_blocks: enabled,
+            ..self
+        }
+    }
+
     /// Set the behavior of the parser when encountering code blocks.
     pub fn with_block_code_block_behavior(
         self,
@@ -410,6 +627,32 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Set how deeply emphasis may nest.
+    ///
+    /// Emphasis and strong emphasis nested more than `depth` levels deep are
+    /// kept as literal text, which bounds the recursion on inputs with
+    /// long runs of delimiters. Each `***` run counts as two levels. The
+    /// default is 32.
+    pub fn with_max_emphasis_nesting(self, depth: usize) -> Self {
+        Self {
+            max_emphasis_nesting: depth,
+            ..self
+        }
+    }
+
+    /// Keep the delimiters emphasis was written with.
+    ///
+    /// By default `_x_` and `*x*` both become `Inline::Emphasis`. With this
+    /// option every emphasis and strong emphasis is wrapped in an
+    /// `Inline::Delimited` that records its delimiter run, which the Markdown
+    /// printer reproduces.
+    pub fn with_preserve_emphasis_delimiters(self) -> Self {
+        Self {
+            preserve_emphasis_delimiters: true,
+            ..self
+        }
+    }
+
     /// Set the behavior of the parser when encountering inline strikethrough.
     pub fn with_inline_strikethrough_behavior(
         self,
@@ -421,6 +664,50 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Set the behavior of the parser when encountering `==highlight==`.
+    ///
+    /// Highlighting is not part of CommonMark or GFM, so the default is
+    /// [`ElementBehavior::Ignore`] and `==` is kept as text. Use
+    /// [`ElementBehavior::Parse`] to produce [`Inline::Highlight`](crate::ast::Inline::Highlight).
+    pub fn with_inline_highlight_behavior(
+        self,
+        behavior: ElementBehavior<crate::ast::Inline>,
+    ) -> Self {
+        Self {
+            inline_highlight_behavior: behavior,
+            ..self
+        }
+    }
+
+    /// Set the behavior of the parser when encountering `~subscript~`.
+    ///
+    /// Pandoc-style subscript is off by default ([`ElementBehavior::Ignore`]).
+    /// The content may not contain unescaped spaces, and `~~` still starts
+    /// strikethrough.
+    pub fn with_inline_subscript_behavior(
+        self,
+        behavior: ElementBehavior<crate::ast::Inline>,
+    ) -> Self {
+        Self {
+            inline_subscript_behavior: behavior,
+            ..self
+        }
+    }
+
+    /// Set the behavior of the parser when encountering `^superscript^`.
+    ///
+    /// Pandoc-style superscript is off by default ([`ElementBehavior::Ignore`]).
+    /// The content may not contain unescaped spaces.
+    pub fn with_inline_superscript_behavior(
+        self,
+        behavior: ElementBehavior<crate::ast::Inline>,
+    ) -> Self {
+        Self {
+            inline_superscript_behavior: behavior,
+            ..self
+        }
+    }
+
     /// Set the behavior of the parser when encountering inline text.
     pub fn with_inline_text_behavior(self, behavior: ElementBehavior<crate::ast::Inline>) -> Self {
         Self {
@@ -429,6 +716,19 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Keep soft line breaks as nodes of their own.
+    ///
+    /// By default a newline within a paragraph stays part of the surrounding
+    /// `Inline::Text`. With this option it becomes an `Inline::SoftBreak`,
+    /// and spaces at the end of the line are dropped, so printers can decide
+    /// how to render it.
+    pub fn with_preserve_soft_breaks(self) -> Self {
+        Self {
+            preserve_soft_breaks: true,
+            ..self
+        }
+    }
+
     /// Set a custom parser for blocks.
     pub fn with_custom_block_parser(self, parser: CustomBlockParserFn) -> Self {
         Self {
@@ -0,0 +1,351 @@
+//! Non-fatal diagnostics for parsed documents
+//!
+//! [`parse_markdown_with_diagnostics`] parses a document as usual and then
+//! reports constructs that parse fine but are probably mistakes, such as
+//! references to undefined labels or headings that skip a level. Each
+//! diagnostic carries the byte offset in the source where it was found.
+
+use crate::ast::visitor::{VisitWith, Visitor};
+use crate::ast::{
+    label_key, Block, DefinitionCollector, Document, Heading, Inline, ValidationIssueKind,
+};
+use crate::parser::{parse_top_level_blocks, MarkdownParserState};
+
+/// Category of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// A reference link has no matching link definition.
+    UndefinedLinkReference,
+
+    /// A footnote reference has no matching footnote definition.
+    UndefinedFootnote,
+
+    /// A link definition repeats the label of an earlier one and is ignored.
+    DuplicateLinkDefinition,
+
+    /// A footnote definition repeats the label of an earlier one.
+    DuplicateFootnoteDefinition,
+
+    /// A heading is more than one level deeper than the heading before it.
+    SkippedHeadingLevel,
+
+    /// A table row has a different number of cells than the header row.
+    InconsistentTableColumns,
+}
+
+/// A suspicious construct found by [`parse_markdown_with_diagnostics`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Category of the problem.
+    pub kind: DiagnosticKind,
+
+    /// Human-readable description.
+    pub message: String,
+
+    /// Byte offset in the source. Points at the construct itself where it
+    /// can be found in the source, otherwise at the start of the top-level
+    /// block containing it.
+    pub offset: usize,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at byte {})", self.message, self.offset)
+    }
+}
+
+/// Parse a Markdown string and report suspicious constructs.
+///
+/// Returns the same document as [`parse_markdown`](crate::parser::parse_markdown)
+/// together with diagnostics in source order. The diagnostics never make the
+/// parse fail.
+///
+/// Table columns are only checked for top-level tables, whose rows can be
+/// read back from the source.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::parser::{parse_markdown_with_diagnostics, DiagnosticKind, MarkdownParserState};
+///
+/// let input = "See [docs].\n\n[guide]: /guide\n";
+/// let (doc, diagnostics) = parse_markdown_with_diagnostics(MarkdownParserState::new(), input).unwrap();
+/// assert_eq!(doc.blocks.len(), 2);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].kind, DiagnosticKind::UndefinedLinkReference);
+/// assert_eq!(diagnostics[0].offset, 4);
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`parse_markdown`](crate::parser::parse_markdown).
+pub fn parse_markdown_with_diagnostics(
+    state: MarkdownParserState,
+    input: &str,
+) -> Result<(Document, Vec<Diagnostic>), nom::Err<nom::error::Error<String>>> {
+    let blocks = parse_top_level_blocks(state, input)?;
+
+    let mut checker = Checker {
+        input,
+        block_start: 0,
+        block_end: 0,
+        cursor: 0,
+        checking_references: false,
+        definitions: DefinitionCollector::default(),
+        last_heading_level: None,
+        diagnostics: Vec::new(),
+    };
+    // Definitions may follow their uses, so collect them all first
+    for pass in [false, true] {
+        checker.checking_references = pass;
+        for (index, (offset, block)) in blocks.iter().enumerate() {
+            let end = blocks[index + 1..]
+                .iter()
+                .map(|(next, _)| *next)
+                .find(|next| next > offset)
+                .unwrap_or(input.len());
+            checker.enter_block(*offset, end);
+            if pass {
+                if let Block::Table(table) = block {
+                    checker.check_table_rows(table.alignments.len());
+                }
+            }
+            block.visit_with(&mut checker);
+        }
+    }
+    checker.diagnostics.sort_by_key(|d| d.offset);
+
+    let document = Document {
+        blocks: blocks.into_iter().map(|(_, block)| block).collect(),
+    };
+    Ok((document, checker.diagnostics))
+}
+
+struct Checker<'a> {
+    input: &'a str,
+    block_start: usize,
+    block_end: usize,
+    /// Where to continue looking for constructs in the current block
+    cursor: usize,
+    /// First pass collects definitions, second pass checks references
+    checking_references: bool,
+    definitions: DefinitionCollector,
+    last_heading_level: Option<u8>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker<'_> {
+    fn enter_block(&mut self, start: usize, end: usize) {
+        self.block_start = start;
+        self.block_end = end;
+        self.cursor = start;
+    }
+
+    fn report(&mut self, kind: DiagnosticKind, message: String, offset: usize) {
+        self.diagnostics.push(Diagnostic {
+            kind,
+            message,
+            offset,
+        });
+    }
+
+    /// Offset of the next ASCII case-insensitive occurrence of `needle` in
+    /// the current block, or of the block itself if there is none
+    fn locate(&mut self, needle: &str) -> usize {
+        let rest = self.input[self.cursor..self.block_end].to_ascii_lowercase();
+        match rest.find(&needle.to_ascii_lowercase()) {
+            Some(position) => {
+                let offset = self.cursor + position;
+                self.cursor = offset + needle.len();
+                offset
+            }
+            None => self.block_start,
+        }
+    }
+
+    /// Compare the cell count of each row of a top-level table to `columns`
+    fn check_table_rows(&mut self, columns: usize) {
+        let source = &self.input[self.block_start..self.block_end];
+        let mut offset = self.block_start;
+        // The delimiter row always matches the header
+        for (index, line) in source.split_inclusive('\n').enumerate() {
+            let cells = table_row_cells(line);
+            if index != 1 && cells != 0 && cells != columns {
+                self.report(
+                    DiagnosticKind::InconsistentTableColumns,
+                    format!("table row has {cells} cells, expected {columns}"),
+                    offset,
+                );
+            }
+            offset += line.len();
+        }
+    }
+}
+
+impl Visitor for Checker<'_> {
+    fn visit_block(&mut self, block: &Block) {
+        if !self.checking_references {
+            match block {
+                Block::FootnoteDefinition(footnote)
+                    if !self.definitions.footnotes.insert(footnote.label.clone()) =>
+                {
+                    let offset = self.locate(&format!("[^{}]", footnote.label));
+                    self.report(
+                        DiagnosticKind::DuplicateFootnoteDefinition,
+                        format!("footnote [^{}] is defined more than once", footnote.label),
+                        offset,
+                    );
+                }
+                Block::Definition(definition) => {
+                    let key = label_key(&definition.label);
+                    if !self.definitions.links.insert(key.clone()) {
+                        let offset = self.locate(&label_needle(&key));
+                        self.report(
+                            DiagnosticKind::DuplicateLinkDefinition,
+                            format!("link definition [{key}] is defined more than once"),
+                            offset,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.walk_block(block);
+    }
+
+    fn visit_heading(&mut self, heading: &Heading) {
+        if self.checking_references {
+            let level = heading.level();
+            if let Some(last) = self.last_heading_level.filter(|&last| level > last + 1) {
+                let offset = self.block_start;
+                self.report(
+                    DiagnosticKind::SkippedHeadingLevel,
+                    format!("heading level {level} follows level {last}"),
+                    offset,
+                );
+            }
+            self.last_heading_level = Some(level);
+        }
+        self.walk_heading(heading);
+    }
+
+    fn visit_inline(&mut self, inline: &Inline) {
+        if self.checking_references {
+            if let Some((issue, label)) = self.definitions.undefined_reference(inline) {
+                let (kind, needle) = match issue.kind {
+                    ValidationIssueKind::UndefinedFootnote => {
+                        (DiagnosticKind::UndefinedFootnote, format!("[^{label}]"))
+                    }
+                    _ => (DiagnosticKind::UndefinedLinkReference, label_needle(&label)),
+                };
+                let offset = self.locate(&needle);
+                self.report(kind, issue.message, offset);
+            }
+        }
+        self.walk_inline(inline);
+    }
+}
+
+/// Start of a bracketed label in the source; only the first word is used
+/// since the rest may be wrapped across lines
+fn label_needle(key: &str) -> String {
+    format!("[{}", key.split_whitespace().next().unwrap_or_default())
+}
+
+/// Number of cells in a table row line, or 0 for a blank line
+fn table_row_cells(line: &str) -> usize {
+    let line = line.trim();
+    if line.is_empty() {
+        return 0;
+    }
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(rest) if !rest.ends_with('\\') => rest,
+        _ => line,
+    };
+    let mut cells = 1;
+    let mut escaped = false;
+    for c in line.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '|' => cells += 1,
+            _ => {}
+        }
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnose(input: &str) -> Vec<(DiagnosticKind, usize)> {
+        let (_, diagnostics) =
+            parse_markdown_with_diagnostics(MarkdownParserState::default(), input).unwrap();
+        diagnostics
+            .into_iter()
+            .map(|d| (d.kind, d.offset))
+            .collect()
+    }
+
+    #[test]
+    fn duplicate_definitions() {
+        let input =
+            "See [docs] and[^1].\n\n[docs]: /a\n\n[Docs]: /b\n\n[^1]: One.\n\n[^1]: Again.\n";
+        assert_eq!(
+            diagnose(input),
+            vec![
+                (DiagnosticKind::DuplicateLinkDefinition, 33),
+                (DiagnosticKind::DuplicateFootnoteDefinition, 57),
+            ]
+        );
+        assert_eq!(&input[33..39], "[Docs]");
+        assert_eq!(&input[57..62], "[^1]:");
+    }
+
+    #[test]
+    fn undefined_references() {
+        let input =
+            "Intro.\n\nSee [missing], [docs] and [missing] again[^note].\n\n[docs]: /docs\n";
+        assert_eq!(
+            diagnose(input),
+            vec![
+                (DiagnosticKind::UndefinedLinkReference, 12),
+                (DiagnosticKind::UndefinedLinkReference, 34),
+                (DiagnosticKind::UndefinedFootnote, 49),
+            ]
+        );
+        assert_eq!(&input[49..56], "[^note]");
+    }
+
+    #[test]
+    fn skipped_heading_levels() {
+        let input = "# One\n\n### Three\n\n## Two\n\n#### Four\n";
+        assert_eq!(
+            diagnose(input),
+            vec![
+                (DiagnosticKind::SkippedHeadingLevel, 7),
+                (DiagnosticKind::SkippedHeadingLevel, 26),
+            ]
+        );
+    }
+
+    #[test]
+    fn inconsistent_table_columns() {
+        let input = "| a | b |\n|---|---|\n| 1 |\n| 1 | 2 |\n| 1 | 2 | 3 |\n";
+        assert_eq!(
+            diagnose(input),
+            vec![
+                (DiagnosticKind::InconsistentTableColumns, 20),
+                (DiagnosticKind::InconsistentTableColumns, 36),
+            ]
+        );
+    }
+
+    #[test]
+    fn clean_document() {
+        let input = "# Title\n\n## Part\n\nSee [docs].\n\n| a | b |\n|---|---|\n| 1 | 2 |\n\n[docs]: /docs\n";
+        assert_eq!(diagnose(input), vec![]);
+    }
+}
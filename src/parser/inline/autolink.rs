@@ -1,14 +1,27 @@
+use crate::parser::MarkdownParserState;
 use nom::{
     branch::alt,
     bytes::complete::{take_while, take_while1},
     character::complete::{char, satisfy},
-    combinator::{map, recognize},
+    combinator::{map, recognize, verify},
     sequence::{delimited, pair, terminated},
     IResult, Parser,
 };
+use std::rc::Rc;
 
-pub(crate) fn autolink(input: &str) -> IResult<&str, String> {
-    delimited(char('<'), alt((uri, email)), char('>')).parse(input)
+pub(crate) fn autolink<'a>(
+    state: Rc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, String> {
+    move |input: &'a str| {
+        let allowed_uri = verify(uri, |link: &String| {
+            let Some(schemes) = &state.config.autolink_schemes else {
+                return true;
+            };
+            let scheme = link.split(':').next().unwrap_or_default();
+            schemes.contains(&scheme.to_ascii_lowercase())
+        });
+        delimited(char('<'), alt((allowed_uri, email)), char('>')).parse(input)
+    }
 }
 
 /// uri: scheme ":" [^<>\u0000-\u0020]*
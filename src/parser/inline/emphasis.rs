@@ -1,10 +1,10 @@
-use crate::ast::Inline;
+use crate::ast::{Delimited, Inline};
 use crate::parser::MarkdownParserState;
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::anychar,
-    combinator::{map, map_opt, not, peek, recognize, value, verify},
+    combinator::{map, not, peek, recognize, success, value, verify},
     multi::many1,
     sequence::{delimited, preceded},
     IResult, Parser,
@@ -15,82 +15,102 @@ pub(crate) fn emphasis(
     state: Rc<MarkdownParserState>,
 ) -> impl FnMut(&str) -> IResult<&str, Inline> {
     move |input: &str| {
-        alt((
-            map(
-                alt((
-                    delimited(
-                        open_tag("***"),
-                        emphasis_content(state.clone(), close_tag("***")),
-                        close_tag("***"),
-                    ),
-                    delimited(
-                        open_tag("___"),
-                        emphasis_content(state.clone(), close_tag("___")),
-                        close_tag("___"),
-                    ),
-                )),
-                |inner| Inline::Strong(vec![Inline::Emphasis(inner)]),
-            ),
-            map(
-                alt((
-                    delimited(
-                        open_tag("**"),
-                        emphasis_content(state.clone(), close_tag("**")),
-                        close_tag("**"),
-                    ),
-                    delimited(
-                        open_tag("__"),
-                        emphasis_content(state.clone(), close_tag("__")),
-                        close_tag("__"),
-                    ),
-                )),
-                Inline::Strong,
-            ),
-            map(
-                alt((
-                    delimited(
-                        open_tag("*"),
-                        emphasis_content(state.clone(), close_tag("*")),
-                        close_tag("*"),
-                    ),
-                    delimited(
-                        open_tag("_"),
-                        emphasis_content(state.clone(), close_tag("_")),
-                        close_tag("_"),
+        // Past the nesting limit delimiters stay text, bounding the recursion
+        if state.emphasis_depth >= state.config.max_emphasis_nesting {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::TooLarge,
+            )));
+        }
+        let inner = Rc::new(state.with_emphasis_depth(state.emphasis_depth + 1));
+        // `***` opens two levels at once
+        let strong_inner = Rc::new(state.with_emphasis_depth(state.emphasis_depth + 2));
+        let strong_allowed = state.emphasis_depth + 2 <= state.config.max_emphasis_nesting;
+
+        let preserve_delimiters = state.config.preserve_emphasis_delimiters;
+
+        map(
+            alt((
+                map(
+                    preceded(
+                        verify(success(()), move |_| strong_allowed),
+                        alt((
+                            delimiter_run(strong_inner.clone(), "***"),
+                            delimiter_run(strong_inner.clone(), "___"),
+                        )),
                     ),
-                )),
-                Inline::Emphasis,
-            ),
-        ))
+                    |(delimiter, inner)| (delimiter, Inline::Strong(vec![Inline::Emphasis(inner)])),
+                ),
+                map(
+                    alt((
+                        delimiter_run(inner.clone(), "**"),
+                        delimiter_run(inner.clone(), "__"),
+                    )),
+                    |(delimiter, inner)| (delimiter, Inline::Strong(inner)),
+                ),
+                map(
+                    alt((
+                        delimiter_run(inner.clone(), "*"),
+                        delimiter_run(inner.clone(), "_"),
+                    )),
+                    |(delimiter, inner)| (delimiter, Inline::Emphasis(inner)),
+                ),
+            )),
+            move |(delimiter, node)| {
+                if preserve_delimiters {
+                    Inline::Delimited(Delimited {
+                        delimiter: delimiter.to_string(),
+                        inner: Box::new(node),
+                    })
+                } else {
+                    node
+                }
+            },
+        )
         .parse(input)
     }
 }
 
-fn emphasis_content<'a, P>(
+/// Content enclosed in a run of `tag_value` on both sides, with the run
+fn delimiter_run<'a>(
     state: Rc<MarkdownParserState>,
-    mut close_tag: P,
-) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Inline>>
-where
-    P: Parser<&'a str, Output = (), Error = nom::error::Error<&'a str>>,
-{
-    move |input: &str| {
-        let not_end = |i: &'a str| close_tag.parse(i);
-        map_opt(
-            recognize(many1(preceded(
-                peek(not(not_end)),
-                alt((value((), tag("\\*")), value((), anychar))),
-            ))),
-            |content: &str| {
-                crate::parser::inline::inline_many1(state.clone())
-                    .parse(content)
-                    .map(|(_, content)| content)
-                    .ok()
-            },
+    tag_value: &'static str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (&'static str, Vec<Inline>)> {
+    move |input: &'a str| {
+        map(
+            delimited(
+                open_tag(tag_value),
+                emphasis_content(state.clone(), tag_value),
+                close_tag(tag_value),
+            ),
+            |content| (tag_value, content),
         )
         .parse(input)
     }
 }
 
+fn emphasis_content<'a>(
+    state: Rc<MarkdownParserState>,
+    tag_value: &'static str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Inline>> {
+    move |input: &'a str| {
+        let (rest, content) = recognize(many1(preceded(
+            peek(not(close_tag(tag_value))),
+            alt((value((), tag("\\*")), value((), anychar))),
+        )))
+        .parse(input)?;
+        // Only parse the content once the closing delimiter is known to follow,
+        // otherwise every unmatched opener would parse the rest of the input again
+        peek(close_tag(tag_value)).parse(rest)?;
+        let (_, content) = crate::parser::inline::inline_many1(state.clone())
+            .parse(content)
+            .map_err(|_| {
+                nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::MapOpt))
+            })?;
+        Ok((rest, content))
+    }
+}
+
 fn open_tag(tag_value: &'static str) -> impl FnMut(&str) -> IResult<&str, ()> {
     move |input: &str| {
         value(
@@ -110,7 +130,9 @@ fn can_open(marker: char, next: Option<char>) -> bool {
         return false;
     }
     if marker == '_' {
-        let right_flanking = next.is_none_or(|c| c.is_whitespace() || is_punctuation(c));
+        // An asterisk run may follow, as in `_**strong emphasis**_`
+        let right_flanking =
+            next.is_none_or(|c| c.is_whitespace() || (is_punctuation(c) && c != '*'));
         return !right_flanking;
     }
     true
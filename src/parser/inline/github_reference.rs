@@ -0,0 +1,91 @@
+use crate::ast::{Inline, Link};
+use crate::parser::MarkdownParserState;
+
+/// Replace `@user` mentions and `#123` issue references in the text
+/// elements of `inlines` with links, as configured
+pub(crate) fn github_references(state: &MarkdownParserState, inlines: Vec<Inline>) -> Vec<Inline> {
+    let config = &state.config;
+    if state.in_link_label || (config.mention_url.is_none() && config.issue_url.is_none()) {
+        return inlines;
+    }
+
+    let mut result = Vec::with_capacity(inlines.len());
+    for inline in inlines {
+        let Inline::Text(text) = inline else {
+            result.push(inline);
+            continue;
+        };
+        let mut last = 0;
+        let mut position = 0;
+        let mut prev = None;
+        while let Some(c) = text[position..].chars().next() {
+            let found = is_word_start(prev)
+                .then(|| reference(state, &text[position..]))
+                .flatten();
+            let Some((len, destination)) = found else {
+                prev = Some(c);
+                position += c.len_utf8();
+                continue;
+            };
+            if last < position {
+                result.push(Inline::Text(text[last..position].to_string()));
+            }
+            let end = position + len;
+            result.push(Inline::Link(Link {
+                destination,
+                title: None,
+                children: vec![Inline::Text(text[position..end].to_string())],
+            }));
+            prev = text[..end].chars().next_back();
+            position = end;
+            last = end;
+        }
+        if last < text.len() {
+            result.push(Inline::Text(text[last..].to_string()));
+        }
+    }
+    result
+}
+
+/// Whether a reference may start after `prev`
+fn is_word_start(prev: Option<char>) -> bool {
+    prev.is_none_or(|c| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | '@' | '#')))
+}
+
+/// Length and link destination of the mention or issue reference at the
+/// start of `input`
+fn reference(state: &MarkdownParserState, input: &str) -> Option<(usize, String)> {
+    let (template, placeholder, name) = match input.as_bytes().first()? {
+        b'@' => {
+            let template = state.config.mention_url.as_ref()?;
+            // GitHub user names: alphanumerics and single inner hyphens
+            let name = word(&input[1..], |c| c.is_ascii_alphanumeric() || c == '-');
+            if name.starts_with('-') || name.ends_with('-') || name.contains("--") {
+                return None;
+            }
+            (template, "{user}", name)
+        }
+        b'#' => {
+            let template = state.config.issue_url.as_ref()?;
+            (
+                template,
+                "{number}",
+                word(&input[1..], |c| c.is_ascii_digit()),
+            )
+        }
+        _ => return None,
+    };
+    let len = 1 + name.len();
+    // The reference must end at a word boundary too
+    let next = input[len..].chars().next();
+    if name.is_empty() || next.is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '@' | '/'))
+    {
+        return None;
+    }
+    Some((len, template.replace(placeholder, name)))
+}
+
+fn word(input: &str, allowed: impl Fn(char) -> bool) -> &str {
+    let end = input.find(|c: char| !allowed(c)).unwrap_or(input.len());
+    &input[..end]
+}
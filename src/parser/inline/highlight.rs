@@ -0,0 +1,28 @@
+use crate::ast::Inline;
+use crate::parser::MarkdownParserState;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{anychar, char},
+    combinator::{not, peek, recognize},
+    multi::many1,
+    sequence::{preceded, terminated},
+    IResult, Parser,
+};
+use std::rc::Rc;
+
+pub(crate) fn highlight<'a>(
+    state: Rc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
+    move |input: &'a str| {
+        let (input, _) = terminated(tag("=="), peek(not(char('=')))).parse(input)?;
+        let not_a_closing_tag = (tag("=="), char('='));
+        let closing_tag = preceded(peek(not(not_a_closing_tag)), tag("=="));
+        let (input, content) =
+            recognize(many1(preceded(peek(not(closing_tag)), anychar))).parse(input)?;
+        let (input, _) = tag("==").parse(input)?;
+
+        let (_, inline) = crate::parser::inline::inline_many1(state.clone()).parse(content)?;
+
+        Ok((input, Inline::Highlight(inline)))
+    }
+}
@@ -3,12 +3,15 @@ mod code_span;
 mod emphasis;
 mod environment_variable;
 mod footnote_reference;
+mod github_reference;
 mod hard_newline;
+mod highlight;
 mod html_entity;
 mod image;
 mod inline_link;
 mod latex;
 mod reference_link;
+mod script;
 mod strikethrough;
 mod text;
 
@@ -16,6 +19,7 @@ mod text;
 mod tests;
 
 use crate::ast::Inline;
+use crate::parser::config::ElementBehavior;
 use crate::parser::MarkdownParserState;
 use nom::{
     branch::alt,
@@ -60,13 +64,64 @@ fn merge_consecutive_text_elements(inlines: Vec<Inline>) -> Vec<Inline> {
     result
 }
 
+/// Splits Text elements at newlines, which become SoftBreak elements
+fn split_soft_breaks(inlines: Vec<Inline>) -> Vec<Inline> {
+    let mut result = Vec::with_capacity(inlines.len());
+    for inline in inlines {
+        let Inline::Text(text) = inline else {
+            result.push(inline);
+            continue;
+        };
+        let mut lines = text.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            let last = lines.peek().is_none();
+            // Spaces at the end of a line are not part of the content
+            let line = if last {
+                line
+            } else {
+                line.trim_end_matches([' ', '\t', '\r'])
+            };
+            if !line.is_empty() {
+                result.push(Inline::Text(line.to_string()));
+            }
+            if !last {
+                result.push(Inline::SoftBreak);
+            }
+        }
+    }
+    result
+}
+
+/// Characters that can start an inline element other than text
+const INLINE_TRIGGERS: [char; 11] = ['\\', '*', '_', '[', '`', '<', '&', '~', '=', '^', '$'];
+
+/// Whether `input` is non-empty and nothing in it but text can match, so
+/// it parses to a single Text element without running the inline parsers
+fn is_plain_text(state: &MarkdownParserState, input: &str) -> bool {
+    !input.is_empty()
+        && state.config.custom_inline_parser.is_none()
+        && matches!(state.config.inline_text_behavior, ElementBehavior::Parse)
+        && !input.contains(INLINE_TRIGGERS)
+        // Two trailing spaces make a hard line break
+        && !input.contains("  \n")
+        && !input.contains("  \r")
+}
+
 pub(crate) fn inline_many0<'a>(
     state: Rc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Inline>> {
     move |input: &'a str| {
-        let (input, list_of_lists) = many0(inline(state.clone())).parse(input)?;
-        let r: Vec<_> = list_of_lists.into_iter().flatten().collect();
-        let merged = merge_consecutive_text_elements(r);
+        let (input, merged) = if is_plain_text(&state, input) {
+            ("", vec![Inline::Text(input.to_string())])
+        } else {
+            let (input, list_of_lists) = many0(inline(state.clone())).parse(input)?;
+            let r: Vec<_> = list_of_lists.into_iter().flatten().collect();
+            (input, merge_consecutive_text_elements(r))
+        };
+        let merged = github_reference::github_references(&state, merged);
+        if state.config.preserve_soft_breaks {
+            return Ok((input, split_soft_breaks(merged)));
+        }
         Ok((input, merged))
     }
 }
@@ -75,9 +130,17 @@ pub(crate) fn inline_many1<'a>(
     state: Rc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Inline>> {
     move |input: &'a str| {
-        let (input, list_of_lists) = many1(inline(state.clone())).parse(input)?;
-        let r: Vec<_> = list_of_lists.into_iter().flatten().collect();
-        let merged = merge_consecutive_text_elements(r);
+        let (input, merged) = if is_plain_text(&state, input) {
+            ("", vec![Inline::Text(input.to_string())])
+        } else {
+            let (input, list_of_lists) = many1(inline(state.clone())).parse(input)?;
+            let r: Vec<_> = list_of_lists.into_iter().flatten().collect();
+            (input, merge_consecutive_text_elements(r))
+        };
+        let merged = github_reference::github_references(&state, merged);
+        if state.config.preserve_soft_breaks {
+            return Ok((input, split_soft_breaks(merged)));
+        }
         Ok((input, merged))
     }
 }
@@ -89,7 +152,10 @@ pub(crate) fn inline<'a>(
         alt((
             conditional_inline(
                 state.config.inline_autolink_behavior.clone(),
-                map(crate::parser::inline::autolink::autolink, Inline::Autolink),
+                map(
+                    crate::parser::inline::autolink::autolink(state.clone()),
+                    Inline::Autolink,
+                ),
             ),
             conditional_inline(
                 state.config.inline_link_behavior.clone(),
@@ -132,6 +198,18 @@ pub(crate) fn inline<'a>(
                 state.config.inline_strikethrough_behavior.clone(),
                 crate::parser::inline::strikethrough::strikethrough(state.clone()),
             ),
+            conditional_inline(
+                state.config.inline_highlight_behavior.clone(),
+                crate::parser::inline::highlight::highlight(state.clone()),
+            ),
+            conditional_inline(
+                state.config.inline_subscript_behavior.clone(),
+                crate::parser::inline::script::subscript(state.clone()),
+            ),
+            conditional_inline(
+                state.config.inline_superscript_behavior.clone(),
+                crate::parser::inline::script::superscript(state.clone()),
+            ),
             custom_parser(state.clone()),
             conditional_inline(
                 state.config.inline_text_behavior.clone(),
@@ -0,0 +1,50 @@
+use crate::ast::Inline;
+use crate::parser::MarkdownParserState;
+use nom::{
+    branch::alt,
+    character::complete::{char, none_of},
+    combinator::{recognize, value},
+    multi::many1,
+    sequence::{delimited, preceded},
+    IResult, Parser,
+};
+use std::rc::Rc;
+
+/// Pandoc-style subscript: `H~2~O`
+pub(crate) fn subscript<'a>(
+    state: Rc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
+    script(state, '~', Inline::Subscript)
+}
+
+/// Pandoc-style superscript: `mc^2^`
+pub(crate) fn superscript<'a>(
+    state: Rc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
+    script(state, '^', Inline::Superscript)
+}
+
+/// Content between two `delimiter`s, without unescaped whitespace
+fn script<'a>(
+    state: Rc<MarkdownParserState>,
+    delimiter: char,
+    wrap: fn(Vec<Inline>) -> Inline,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
+    let forbidden = [delimiter, ' ', '\t', '\r', '\n'];
+    move |input: &'a str| {
+        let (input, content) = delimited(
+            char(delimiter),
+            recognize(many1(alt((
+                value((), preceded(char('\\'), char(delimiter))),
+                value((), preceded(char('\\'), char(' '))),
+                value((), none_of(&forbidden[..])),
+            )))),
+            char(delimiter),
+        )
+        .parse(input)?;
+
+        let (_, inline) = crate::parser::inline::inline_many1(state.clone()).parse(content)?;
+
+        Ok((input, wrap(inline)))
+    }
+}
@@ -1,5 +1,5 @@
 use crate::ast::*;
-use crate::parser::{parse_markdown, MarkdownParserState};
+use crate::parser::{parse_markdown, MarkdownParserConfig, MarkdownParserState};
 
 #[test]
 fn autolink1() {
@@ -76,3 +76,36 @@ fn autolink6() {
         }
     );
 }
+
+#[test]
+fn autolink_scheme_allowlist() {
+    let config = MarkdownParserConfig::default().with_autolink_schemes(["HTTPS", "mailto", "ftp"]);
+    let parse = |input: &str| {
+        parse_markdown(MarkdownParserState::with_config(config.clone()), input).unwrap()
+    };
+
+    assert_eq!(
+        parse("<https://example.com>"),
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Autolink(
+                "https://example.com".to_owned()
+            )])]
+        }
+    );
+    assert_eq!(
+        parse("<ftp://example.com/file>"),
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Autolink(
+                "ftp://example.com/file".to_owned()
+            )])]
+        }
+    );
+    assert_eq!(
+        parse("<irc://foo.bar:2233/baz>"),
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "<irc://foo.bar:2233/baz>".to_owned()
+            )])]
+        }
+    );
+}
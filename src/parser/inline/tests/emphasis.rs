@@ -39,7 +39,8 @@ fn emphasis2() {
                 items: vec![ListItem {
                     task: None,
                     blocks: vec![Block::Paragraph(vec![Inline::Text("a *".to_owned())])]
-                }]
+                }],
+                tight: true,
             })]
         }
     );
@@ -191,3 +192,118 @@ fn test_false_positive_prevention() {
         }
     );
 }
+
+#[test]
+fn underscore_emphasis_around_strong() {
+    let doc = parse_markdown(MarkdownParserState::default(), "a _**both**_ b").unwrap();
+
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("a ".to_string()),
+                Inline::Emphasis(vec![Inline::Strong(vec![Inline::Text("both".to_string())])]),
+                Inline::Text(" b".to_string())
+            ])],
+        }
+    );
+}
+
+#[test]
+fn emphasis_nesting_limit() {
+    let input = "*a __b _c_ b__ a*";
+    let nested = |inner: Inline| Document {
+        blocks: vec![Block::Paragraph(vec![Inline::Emphasis(vec![
+            Inline::Text("a ".to_string()),
+            Inline::Strong(vec![
+                Inline::Text("b ".to_string()),
+                inner,
+                Inline::Text(" b".to_string()),
+            ]),
+            Inline::Text(" a".to_string()),
+        ])])],
+    };
+
+    let doc = parse_markdown(MarkdownParserState::default(), input).unwrap();
+    assert_eq!(
+        doc,
+        nested(Inline::Emphasis(vec![Inline::Text("c".to_string())]))
+    );
+
+    let config =
+        crate::parser::config::MarkdownParserConfig::default().with_max_emphasis_nesting(2);
+    let doc = parse_markdown(MarkdownParserState::with_config(config), input).unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Emphasis(vec![
+                Inline::Text("a ".to_string()),
+                Inline::Strong(vec![Inline::Text("b _c_ b".to_string())]),
+                Inline::Text(" a".to_string()),
+            ])])],
+        }
+    );
+}
+
+#[test]
+fn pathological_delimiter_runs() {
+    let inputs = [
+        format!("{}a{}", "*".repeat(25_000), "*".repeat(25_000)),
+        format!("x{}", "*".repeat(50_000)),
+        format!("{}b{}", "*a ".repeat(10_000), " c*".repeat(10_000)),
+        // Unmatched openers used to re-parse the rest of the paragraph each
+        "_a ".repeat(200),
+    ];
+    for input in inputs {
+        let start = std::time::Instant::now();
+        let doc = parse_markdown(MarkdownParserState::default(), &input).unwrap();
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "took {:?}",
+            start.elapsed()
+        );
+        assert_eq!(doc.blocks.len(), 1);
+    }
+}
+
+#[test]
+fn preserve_emphasis_delimiters() {
+    let state = MarkdownParserState::with_config(
+        crate::parser::config::MarkdownParserConfig::default().with_preserve_emphasis_delimiters(),
+    );
+    let delimited = |delimiter: &str, inner: Inline| {
+        Inline::Delimited(Delimited {
+            delimiter: delimiter.to_string(),
+            inner: Box::new(inner),
+        })
+    };
+    let x = || vec![Inline::Text("x".to_string())];
+
+    let doc = parse_markdown(state, "_x_ *x* __x__ ***x***").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                delimited("_", Inline::Emphasis(x())),
+                Inline::Text(" ".to_string()),
+                delimited("*", Inline::Emphasis(x())),
+                Inline::Text(" ".to_string()),
+                delimited("__", Inline::Strong(x())),
+                Inline::Text(" ".to_string()),
+                delimited("***", Inline::Strong(vec![Inline::Emphasis(x())])),
+            ])],
+        }
+    );
+
+    let doc = parse_markdown(MarkdownParserState::default(), "_x_ *x*").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Emphasis(x()),
+                Inline::Text(" ".to_string()),
+                Inline::Emphasis(x()),
+            ])],
+        }
+    );
+}
@@ -0,0 +1,80 @@
+use crate::ast::*;
+use crate::parser::{parse_markdown, MarkdownParserConfig, MarkdownParserState};
+
+fn parse(input: &str) -> Vec<Inline> {
+    let config = MarkdownParserConfig::default()
+        .with_mention_url("https://github.com/{user}")
+        .with_issue_url("https://github.com/owner/repo/issues/{number}");
+    let doc = parse_markdown(MarkdownParserState::with_config(config), input).unwrap();
+    match doc.blocks.as_slice() {
+        [Block::Paragraph(inlines)] => inlines.clone(),
+        blocks => panic!("expected a paragraph, got {blocks:?}"),
+    }
+}
+
+fn text(s: &str) -> Inline {
+    Inline::Text(s.to_owned())
+}
+
+fn link(destination: &str, label: &str) -> Inline {
+    Inline::Link(Link {
+        destination: destination.to_owned(),
+        title: None,
+        children: vec![text(label)],
+    })
+}
+
+#[test]
+fn mention_and_issue() {
+    assert_eq!(
+        parse("Thanks @octo-cat, see #123."),
+        vec![
+            text("Thanks "),
+            link("https://github.com/octo-cat", "@octo-cat"),
+            text(", see "),
+            link("https://github.com/owner/repo/issues/123", "#123"),
+            text("."),
+        ]
+    );
+    assert_eq!(
+        parse("*@octocat* fixed #7"),
+        vec![
+            Inline::Emphasis(vec![link("https://github.com/octocat", "@octocat")]),
+            text(" fixed "),
+            link("https://github.com/owner/repo/issues/7", "#7"),
+        ]
+    );
+}
+
+#[test]
+fn word_boundaries() {
+    for input in [
+        "Mail me@example.com",
+        "Go to page#123 or #12ab",
+        "A user@@name and @-dash",
+        "The C# language",
+    ] {
+        assert_eq!(parse(input), vec![text(input)], "{input}");
+    }
+}
+
+#[test]
+fn not_in_code_or_links() {
+    assert_eq!(
+        parse("`@octocat #1` and [@octocat](/u)"),
+        vec![
+            Inline::Code("@octocat #1".to_owned()),
+            text(" and "),
+            link("/u", "@octocat"),
+        ]
+    );
+}
+
+#[test]
+fn disabled_by_default() {
+    let doc = parse_markdown(MarkdownParserState::default(), "Thanks @octocat for #1").unwrap();
+    assert_eq!(
+        doc.blocks,
+        vec![Block::Paragraph(vec![text("Thanks @octocat for #1")])]
+    );
+}
@@ -0,0 +1,60 @@
+use crate::ast::*;
+use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+use crate::parser::{parse_markdown, MarkdownParserState};
+
+fn highlight_state() -> MarkdownParserState {
+    MarkdownParserState::with_config(
+        MarkdownParserConfig::default().with_inline_highlight_behavior(ElementBehavior::Parse),
+    )
+}
+
+#[test]
+fn highlight1() {
+    let doc = parse_markdown(highlight_state(), "This is ==important== text").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("This is ".to_string()),
+                Inline::Highlight(vec![Inline::Text("important".to_string())]),
+                Inline::Text(" text".to_string()),
+            ])],
+        }
+    );
+}
+
+#[test]
+fn highlight_nested() {
+    let doc = parse_markdown(highlight_state(), "==**very** important==").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Highlight(vec![
+                Inline::Strong(vec![Inline::Text("very".to_string())]),
+                Inline::Text(" important".to_string()),
+            ])])],
+        }
+    );
+}
+
+#[test]
+fn highlight_disabled_by_default() {
+    let doc = parse_markdown(MarkdownParserState::default(), "==important==").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "==important==".to_string()
+            )])],
+        }
+    );
+}
+
+#[cfg(feature = "printer")]
+#[test]
+fn highlight_round_trip() {
+    let input = "a ==important== b";
+    let doc = parse_markdown(highlight_state(), input).unwrap();
+    let output = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(output, input);
+}
@@ -298,3 +298,68 @@ fn inline_link_empty_nested_brackets() {
         }
     );
 }
+
+#[test]
+fn inline_link_title_delimiters() {
+    let cases = [
+        (r#"[x](/u "title")"#, "title"),
+        ("[x](/u 'title')", "title"),
+        ("[x](/u (title))", "title"),
+        (r#"[x](/u "say \"hi\"")"#, r#"say "hi""#),
+        (r"[x](/u 'it\'s')", "it's"),
+        (r"[x](/u (a \(b\)))", "a (b)"),
+        (r#"[x](/u "it's (fine)")"#, "it's (fine)"),
+        (r"[x](/u 'C:\path')", r"C:\path"),
+    ];
+    for (input, title) in cases {
+        let doc = parse_markdown(MarkdownParserState::default(), input).unwrap();
+        assert_eq!(
+            doc,
+            Document {
+                blocks: vec![Block::Paragraph(vec![Inline::Link(Link {
+                    destination: "/u".to_owned(),
+                    title: Some(title.to_owned()),
+                    children: vec![Inline::Text("x".to_owned())]
+                })])]
+            },
+            "input: {input}"
+        );
+    }
+}
+
+#[test]
+fn inline_link_paren_title_with_unescaped_paren() {
+    // `(` must be escaped inside a parenthesized title, so this is not a link
+    let doc = parse_markdown(MarkdownParserState::default(), "[x](/u (a (b)))").unwrap();
+    assert!(!matches!(
+        doc.blocks.as_slice(),
+        [Block::Paragraph(inlines)] if matches!(inlines.as_slice(), [Inline::Link(_)])
+    ));
+}
+
+#[test]
+fn inline_link_destinations() {
+    let cases = [
+        ("[x](<a b>)", "a b"),
+        ("[x](/a%20b)", "/a%20b"),
+        ("[x](<a%20b c>)", "a%20b c"),
+        (r"[x](<a\<b\>c>)", "a<b>c"),
+        (r"[x](<a\*b\d>)", r"a*b\d"),
+        ("[x](<a)b>)", "a)b"),
+        ("[x](<>)", ""),
+    ];
+    for (input, destination) in cases {
+        let doc = parse_markdown(MarkdownParserState::default(), input).unwrap();
+        assert_eq!(
+            doc,
+            Document {
+                blocks: vec![Block::Paragraph(vec![Inline::Link(Link {
+                    destination: destination.to_owned(),
+                    title: None,
+                    children: vec![Inline::Text("x".to_owned())]
+                })])]
+            },
+            "input: {input}"
+        );
+    }
+}
@@ -4,13 +4,19 @@ mod consecutive_text_elements;
 mod emphasis;
 mod environment_variable;
 mod footnote_reference;
+mod github_reference;
 mod hard_newline;
+mod highlight;
 mod html_entity;
 mod image;
 mod image_attr;
 mod image_attr_unquoted;
 mod inline_link;
-mod latex;
 mod inline_macro_replacer;
+mod latex;
+mod parse_inlines;
+mod plain_text;
 mod reference_link;
+mod script;
+mod soft_break;
 mod strikethrough;
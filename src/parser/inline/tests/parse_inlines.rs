@@ -0,0 +1,37 @@
+use crate::ast::*;
+use crate::parser::{parse_inlines, MarkdownParserState};
+
+#[test]
+fn parse_inlines_emphasis() {
+    let inlines = parse_inlines(MarkdownParserState::default(), "a *b* c").unwrap();
+    assert_eq!(
+        inlines,
+        vec![
+            Inline::Text("a ".to_string()),
+            Inline::Emphasis(vec![Inline::Text("b".to_string())]),
+            Inline::Text(" c".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn parse_inlines_ignores_block_syntax() {
+    let inlines = parse_inlines(MarkdownParserState::default(), "# see [docs](/d)").unwrap();
+    assert_eq!(
+        inlines,
+        vec![
+            Inline::Text("# see ".to_string()),
+            Inline::Link(Link {
+                destination: "/d".to_string(),
+                title: None,
+                children: vec![Inline::Text("docs".to_string())],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn parse_inlines_empty() {
+    let inlines = parse_inlines(MarkdownParserState::default(), "").unwrap();
+    assert_eq!(inlines, vec![]);
+}
@@ -0,0 +1,48 @@
+use crate::ast::*;
+use crate::parser::config::MarkdownParserConfig;
+use crate::parser::{parse_markdown, MarkdownParserState};
+use nom::Parser;
+use rstest::rstest;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A state whose custom inline parser never matches, which rules out the
+/// plain text shortcut and runs every inline parser
+fn full_parse_state(config: MarkdownParserConfig) -> MarkdownParserState {
+    MarkdownParserState::with_config(config.with_custom_inline_parser(Rc::new(RefCell::new(
+        Box::new(|input: &str| nom::combinator::fail().parse(input)),
+    ))))
+}
+
+#[rstest(
+    input,
+    case("Plain text with no markup at all."),
+    case("Two lines\nof plain text, ending in a space "),
+    case("Numbers 1 + 2 - 3 / 4 (in parens), \"quotes\" and 'apostrophes'!"),
+    case("Unicode: naïve café, 日本語, emoji 🎉"),
+    case("# heading\n\nparagraph one\n\n> quoted text\n\n- item one\n- item two"),
+    case("# trigger *emphasis*\n\nplain paragraph")
+)]
+fn plain_text_matches_full_parse(input: &str) {
+    for config in [
+        MarkdownParserConfig::default(),
+        MarkdownParserConfig::default().with_preserve_soft_breaks(),
+    ] {
+        let fast = parse_markdown(MarkdownParserState::with_config(config.clone()), input).unwrap();
+        let full = parse_markdown(full_parse_state(config), input).unwrap();
+        assert_eq!(fast, full);
+    }
+}
+
+#[test]
+fn plain_paragraph_is_single_text() {
+    let doc = parse_markdown(MarkdownParserState::default(), "one\ntwo three").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "one\ntwo three".to_string()
+            )])],
+        }
+    );
+}
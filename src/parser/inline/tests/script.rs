@@ -0,0 +1,108 @@
+use crate::ast::*;
+use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+use crate::parser::{parse_markdown, MarkdownParserState};
+
+fn script_state() -> MarkdownParserState {
+    MarkdownParserState::with_config(
+        MarkdownParserConfig::default()
+            .with_inline_subscript_behavior(ElementBehavior::Parse)
+            .with_inline_superscript_behavior(ElementBehavior::Parse),
+    )
+}
+
+#[test]
+fn subscript1() {
+    let doc = parse_markdown(script_state(), "H~2~O").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("H".to_string()),
+                Inline::Subscript(vec![Inline::Text("2".to_string())]),
+                Inline::Text("O".to_string()),
+            ])],
+        }
+    );
+}
+
+#[test]
+fn superscript1() {
+    let doc = parse_markdown(script_state(), "E=mc^2^").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("E=mc".to_string()),
+                Inline::Superscript(vec![Inline::Text("2".to_string())]),
+            ])],
+        }
+    );
+}
+
+#[test]
+fn script_requires_no_spaces() {
+    let doc = parse_markdown(script_state(), "a ~b c~ d ^e f^").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "a ~b c~ d ^e f^".to_string()
+            )])],
+        }
+    );
+}
+
+#[test]
+fn script_strikethrough_still_parses() {
+    let doc = parse_markdown(script_state(), "~~gone~~").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Strikethrough(vec![
+                Inline::Text("gone".to_string())
+            ])])],
+        }
+    );
+}
+
+#[test]
+fn script_not_in_code() {
+    let doc = parse_markdown(script_state(), "`H~2~O` and\n\n```\nx^2^\n```").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![
+                Block::Paragraph(vec![
+                    Inline::Code("H~2~O".to_string()),
+                    Inline::Text(" and".to_string()),
+                ]),
+                Block::CodeBlock(CodeBlock {
+                    kind: CodeBlockKind::Fenced { info: None },
+                    literal: "x^2^".to_string(),
+                }),
+            ],
+        }
+    );
+}
+
+#[test]
+fn script_disabled_by_default() {
+    let doc = parse_markdown(MarkdownParserState::default(), "H~2~O x^2^").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "H~2~O x^2^".to_string()
+            )])],
+        }
+    );
+}
+
+#[cfg(feature = "printer")]
+#[test]
+fn script_round_trip() {
+    let input = "H~2~O and E=mc^2^";
+    let doc = parse_markdown(script_state(), input).unwrap();
+    let output = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(output, input);
+}
@@ -0,0 +1,57 @@
+use crate::ast::*;
+use crate::parser::config::MarkdownParserConfig;
+use crate::parser::{parse_markdown, MarkdownParserState};
+
+fn preserving() -> MarkdownParserState {
+    MarkdownParserState::with_config(MarkdownParserConfig::default().with_preserve_soft_breaks())
+}
+
+#[test]
+fn soft_break_kept_in_text_by_default() {
+    let doc = parse_markdown(MarkdownParserState::default(), "line1\nline2").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "line1\nline2".to_string()
+            )])],
+        }
+    );
+}
+
+#[test]
+fn soft_break_preserved() {
+    let doc = parse_markdown(preserving(), "line1 \nline2 *a\nb*\\\nline3").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("line1".to_string()),
+                Inline::SoftBreak,
+                Inline::Text("line2 ".to_string()),
+                Inline::Emphasis(vec![
+                    Inline::Text("a".to_string()),
+                    Inline::SoftBreak,
+                    Inline::Text("b".to_string()),
+                ]),
+                Inline::LineBreak,
+                Inline::Text("line3".to_string()),
+            ])],
+        }
+    );
+}
+
+#[test]
+fn soft_break_after_inline() {
+    let doc = parse_markdown(preserving(), "`code`\nnext").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Code("code".to_string()),
+                Inline::SoftBreak,
+                Inline::Text("next".to_string()),
+            ])],
+        }
+    );
+}
@@ -47,7 +47,7 @@ fn not_a_text<'a>(
             alt((
                 conditional_inline_unit(
                     state.config.inline_autolink_behavior.clone(),
-                    value((), crate::parser::inline::autolink::autolink),
+                    value((), crate::parser::inline::autolink::autolink(state.clone())),
                 ),
                 conditional_inline_unit(
                     state.config.inline_reference_link_behavior.clone(),
@@ -102,6 +102,24 @@ fn not_a_text<'a>(
                         crate::parser::inline::strikethrough::strikethrough(state.clone()),
                     ),
                 ),
+                conditional_inline_unit(
+                    state.config.inline_highlight_behavior.clone(),
+                    value(
+                        (),
+                        crate::parser::inline::highlight::highlight(state.clone()),
+                    ),
+                ),
+                conditional_inline_unit(
+                    state.config.inline_subscript_behavior.clone(),
+                    value((), crate::parser::inline::script::subscript(state.clone())),
+                ),
+                conditional_inline_unit(
+                    state.config.inline_superscript_behavior.clone(),
+                    value(
+                        (),
+                        crate::parser::inline::script::superscript(state.clone()),
+                    ),
+                ),
             )),
             map(
                 value(
@@ -1,4 +1,4 @@
-use nom::character::complete::{anychar, char, none_of, one_of, satisfy};
+use nom::character::complete::{anychar, char, one_of, satisfy};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -30,7 +30,7 @@ fn link_label_inner<'a>(
         .parse(input)?;
 
         // Recursively parse the label content as inline elements
-        let (_, label) = crate::parser::inline::inline_many1(state.clone())
+        let (_, label) = crate::parser::inline::inline_many1(Rc::new(state.in_link_label()))
             .parse(label.as_str())
             .map_err(|err| err.map_input(|_| input))?;
 
@@ -63,8 +63,16 @@ fn link_title_inner(end_delim: char) -> impl FnMut(&str) -> IResult<&str, String
     move |input: &str| {
         fold_many0(
             alt((
-                map(escaped_char, |c| c.to_string()),
-                map(none_of(&[end_delim, '\\'][..]), |c| c.to_string()),
+                // Only ASCII punctuation can be escaped; other backslashes are kept
+                map(
+                    preceded(char('\\'), satisfy(|c| c.is_ascii_punctuation())),
+                    |c| c.to_string(),
+                ),
+                map(
+                    satisfy(|c| c != end_delim && c != '\\' && !(end_delim == ')' && c == '(')),
+                    |c| c.to_string(),
+                ),
+                map(char('\\'), |c| c.to_string()),
             )),
             String::new,
             |mut acc, s| {
@@ -140,8 +148,9 @@ pub(crate) fn link_destination(input: &str) -> IResult<&str, String> {
 fn link_destination1(input: &str) -> IResult<&str, String> {
     let (input, _) = char('<').parse(input)?;
 
+    // Escaped ASCII punctuation is unescaped; other backslashes are kept
     let (input, chars) = many0(alt((
-        preceded(char('\\'), one_of("<>")),
+        preceded(char('\\'), satisfy(|c| c.is_ascii_punctuation())),
         preceded(peek(not(one_of("\n<>"))), anychar),
     )))
     .parse(input)?;
@@ -48,18 +48,25 @@ mod blocks;
 
 /// Configuration options for Markdown parsing behavior.
 pub mod config;
+mod diagnostics;
 mod inline;
 mod link_util;
+#[cfg(feature = "ast-specialized")]
+mod reparse;
 mod util;
 
-use crate::ast::Document;
+pub use diagnostics::{parse_markdown_with_diagnostics, Diagnostic, DiagnosticKind};
+#[cfg(feature = "ast-specialized")]
+pub use reparse::reparse;
+
+use crate::ast::{Block, BorrowedDocument, Document, Inline};
 use crate::parser::config::MarkdownParserConfig;
 use nom::{
     branch::alt,
     character::complete::{line_ending, space1},
-    combinator::eof,
+    combinator::{consumed, eof},
     multi::many0,
-    sequence::terminated,
+    sequence::{preceded, terminated},
     Parser,
 };
 use std::rc::Rc;
@@ -93,9 +100,15 @@ pub struct MarkdownParserState {
     /// This field is for internal use only.
     pub(crate) is_nested_block_context: bool,
 
-    /// The stack of containers that are currently being parsed.
-    /// This is used to prevent self-nesting.
+    /// The kinds of the containers that are currently being parsed, outermost
+    /// first.
     pub(crate) containers: Vec<String>,
+
+    /// How many emphasis levels enclose the inline content being parsed.
+    pub(crate) emphasis_depth: usize,
+
+    /// Whether the inline content being parsed is the text of a link.
+    pub(crate) in_link_label: bool,
 }
 
 impl MarkdownParserState {
@@ -131,6 +144,8 @@ impl MarkdownParserState {
             config: Rc::new(config),
             is_nested_block_context: false,
             containers: Vec::new(),
+            emphasis_depth: 0,
+            in_link_label: false,
         }
     }
 
@@ -144,6 +159,30 @@ impl MarkdownParserState {
             config: self.config.clone(),
             is_nested_block_context: true,
             containers: self.containers.clone(),
+            emphasis_depth: self.emphasis_depth,
+            in_link_label: self.in_link_label,
+        }
+    }
+
+    /// Create a state for inline content enclosed by `depth` emphasis levels
+    pub(crate) fn with_emphasis_depth(&self, depth: usize) -> Self {
+        Self {
+            config: self.config.clone(),
+            is_nested_block_context: self.is_nested_block_context,
+            containers: self.containers.clone(),
+            emphasis_depth: depth,
+            in_link_label: self.in_link_label,
+        }
+    }
+
+    /// Create a state for the text of a link
+    pub(crate) fn in_link_label(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            is_nested_block_context: self.is_nested_block_context,
+            containers: self.containers.clone(),
+            emphasis_depth: self.emphasis_depth,
+            in_link_label: true,
         }
     }
 }
@@ -207,26 +246,114 @@ pub fn parse_markdown(
     state: MarkdownParserState,
     input: &str,
 ) -> Result<Document, nom::Err<nom::error::Error<String>>> {
+    let blocks = parse_top_level_blocks(state, input)?
+        .into_iter()
+        .map(|(_, block)| block)
+        .collect();
+    Ok(Document { blocks })
+}
+
+/// Top-level blocks of `input`, each with the byte offset it starts at
+fn parse_top_level_blocks(
+    state: MarkdownParserState,
+    input: &str,
+) -> Result<Vec<(usize, Block)>, nom::Err<nom::error::Error<String>>> {
     let empty_lines = many0(alt((space1, line_ending)));
     let mut parser = terminated(
-        many0(crate::parser::blocks::block(Rc::new(state))),
+        preceded(
+            crate::parser::util::many_empty_lines0,
+            many0(consumed(crate::parser::blocks::block(Rc::new(state)))),
+        ),
         (empty_lines, eof),
     );
-    let result = parser.parse(input);
+    let (_, blocks) = parser.parse(input).map_err(to_owned_error)?;
+    Ok(blocks
+        .into_iter()
+        .flat_map(|(source, blocks)| {
+            // Blank lines before a block are consumed along with it
+            let blank: usize = source
+                .split_inclusive('\n')
+                .take_while(|line| line.trim().is_empty() && line.ends_with('\n'))
+                .map(str::len)
+                .sum();
+            let offset = source.as_ptr() as usize - input.as_ptr() as usize + blank;
+            blocks.into_iter().map(move |block| (offset, block))
+        })
+        .collect())
+}
 
-    match result {
-        Ok((_, blocks)) => {
-            let blocks = blocks.into_iter().flatten().collect();
-            Ok(Document { blocks })
-        }
-        Err(nom::Err::Error(e)) => Err(nom::Err::Error(nom::error::Error {
-            input: e.input.to_string(),
-            code: e.code,
-        })),
-        Err(nom::Err::Failure(e)) => Err(nom::Err::Failure(nom::error::Error {
-            input: e.input.to_string(),
-            code: e.code,
-        })),
-        Err(nom::Err::Incomplete(needed)) => Err(nom::Err::Incomplete(needed)),
-    }
+/// Parse a Markdown string and compact the result into a [`BorrowedDocument`]
+///
+/// This runs [`parse_markdown`] and then [`Document::into_compact`]: text
+/// that appears verbatim in `input` is swapped for a slice of it and the
+/// owned copy is freed. Parsing itself allocates as much as
+/// [`parse_markdown`] plus the conversion, but the returned tree retains far
+/// fewer allocations. See [`crate::ast::BorrowedDocument`] for what is
+/// borrowed.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::parser::{parse_markdown, parse_markdown_compact, MarkdownParserState};
+///
+/// let input = "# Hello\n\nWorld!";
+/// let compact = parse_markdown_compact(MarkdownParserState::new(), input).unwrap();
+/// assert_eq!(compact.borrowed_count(), 2);
+/// assert_eq!(
+///     compact.into_owned(),
+///     parse_markdown(MarkdownParserState::new(), input).unwrap()
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`parse_markdown`].
+pub fn parse_markdown_compact<'a>(
+    state: MarkdownParserState,
+    input: &'a str,
+) -> Result<BorrowedDocument<'a>, nom::Err<nom::error::Error<String>>> {
+    Ok(parse_markdown(state, input)?.into_compact(input))
+}
+
+/// Parse a string as inline content only
+///
+/// Runs the inline parser over the whole input, without recognizing any
+/// block structure, and returns the inline elements as they would appear
+/// inside a paragraph. Useful for short single-line strings where only
+/// emphasis, links and the like matter.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::ast::Inline;
+/// use markdown_ppp::parser::{parse_inlines, MarkdownParserState};
+///
+/// let inlines = parse_inlines(MarkdownParserState::new(), "a *b* c").unwrap();
+/// assert_eq!(
+///     inlines,
+///     vec![
+///         Inline::Text("a ".to_string()),
+///         Inline::Emphasis(vec![Inline::Text("b".to_string())]),
+///         Inline::Text(" c".to_string()),
+///     ]
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns a parse error if the inline parser cannot consume the whole input.
+pub fn parse_inlines(
+    state: MarkdownParserState,
+    input: &str,
+) -> Result<Vec<Inline>, nom::Err<nom::error::Error<String>>> {
+    let mut parser = terminated(crate::parser::inline::inline_many0(Rc::new(state)), eof);
+    let (_, inlines) = parser.parse(input).map_err(to_owned_error)?;
+    Ok(inlines)
+}
+
+fn to_owned_error(err: nom::Err<nom::error::Error<&str>>) -> nom::Err<nom::error::Error<String>> {
+    err.map(|e| nom::error::Error {
+        input: e.input.to_string(),
+        code: e.code,
+    })
 }
@@ -0,0 +1,148 @@
+//! Incremental re-parsing for documents with element IDs
+//!
+//! [`reparse`] parses the new source from scratch and then carries element
+//! IDs over from the previous tree: every top-level block whose content is
+//! unchanged keeps its whole subtree of IDs, while new or edited blocks get
+//! fresh IDs above the highest one used so far. Renderers keyed on
+//! [`ElementId`] can then diff the two trees cheaply.
+
+use crate::ast::convert::{StripData, WithData};
+use crate::ast::map_data_visitor::{map_user_data, ClosureMapDataVisitor, MapDataVisitor};
+use crate::ast::{generic, Block};
+use crate::ast_specialized::element_id::IdGenerator;
+use crate::ast_specialized::{with_ids, ElementId};
+use crate::parser::{parse_markdown, MarkdownParserState};
+
+/// Re-parse `new_source`, reusing IDs from `old` for unchanged blocks.
+///
+/// Blocks are matched by content, so a block that only moved keeps its IDs
+/// as well. The document itself keeps its ID.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::ast_specialized::id_utils;
+/// use markdown_ppp::ast_specialized::with_ids::Block;
+/// use markdown_ppp::parser::{parse_markdown, reparse, MarkdownParserState};
+///
+/// let doc = parse_markdown(MarkdownParserState::new(), "# Title\n\nBody").unwrap();
+/// let old = id_utils::add_ids_to_document(doc);
+///
+/// let new = reparse(MarkdownParserState::new(), &old, "# Title\n\nEdited body").unwrap();
+/// let id = |block: &Block| match block {
+///     Block::Heading(h) => h.user_data.id(),
+///     Block::Paragraph { user_data, .. } => user_data.id(),
+///     _ => unreachable!(),
+/// };
+/// assert_eq!(id(&new.blocks[0]), id(&old.blocks[0]));
+/// assert_ne!(id(&new.blocks[1]), id(&old.blocks[1]));
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`parse_markdown`].
+pub fn reparse(
+    state: MarkdownParserState,
+    old: &with_ids::Document,
+    new_source: &str,
+) -> Result<with_ids::Document, nom::Err<nom::error::Error<String>>> {
+    let doc = parse_markdown(state, new_source)?;
+
+    let mut max_id = 0;
+    let old = map_user_data(old.clone(), |id: ElementId| {
+        max_id = max_id.max(id.id());
+        id
+    });
+    let mut ids = IdGenerator::starting_from(max_id + 1);
+
+    let mut candidates: Vec<Option<(Block, with_ids::Block)>> = old
+        .blocks
+        .into_iter()
+        .map(|block| Some((block.clone().strip_data(), block)))
+        .collect();
+
+    let blocks = doc
+        .blocks
+        .into_iter()
+        .map(|block| {
+            let reused = candidates
+                .iter_mut()
+                .find(|candidate| matches!(candidate, Some((content, _)) if *content == block))
+                .and_then(Option::take);
+            match reused {
+                Some((_, block)) => block,
+                None => {
+                    let block: generic::Block<()> = block.with_default_data();
+                    ClosureMapDataVisitor::new(|_| ids.generate()).visit_block(block)
+                }
+            }
+        })
+        .collect();
+
+    Ok(generic::Document {
+        blocks,
+        user_data: old.user_data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast_specialized::id_utils;
+
+    fn block_id(block: &with_ids::Block) -> u64 {
+        match block {
+            generic::Block::Paragraph { user_data, .. } => user_data.id(),
+            generic::Block::Heading(heading) => heading.user_data.id(),
+            generic::Block::ThematicBreak { user_data } => user_data.id(),
+            other => panic!("unexpected block {other:?}"),
+        }
+    }
+
+    fn parse_with_ids(source: &str) -> with_ids::Document {
+        let doc = parse_markdown(MarkdownParserState::new(), source).unwrap();
+        id_utils::add_ids_to_document(doc)
+    }
+
+    #[test]
+    fn editing_a_paragraph_keeps_other_ids() {
+        let old = parse_with_ids("# Title\n\nFirst paragraph.\n\nSecond paragraph.\n\n---\n");
+        let new = reparse(
+            MarkdownParserState::new(),
+            &old,
+            "# Title\n\nFirst paragraph, edited.\n\nSecond paragraph.\n\n---\n",
+        )
+        .unwrap();
+
+        assert_eq!(new.user_data, old.user_data);
+        assert_eq!(new.blocks.len(), 4);
+        assert_eq!(block_id(&new.blocks[0]), block_id(&old.blocks[0]));
+        assert_eq!(block_id(&new.blocks[2]), block_id(&old.blocks[2]));
+        assert_eq!(block_id(&new.blocks[3]), block_id(&old.blocks[3]));
+        // The edited paragraph is new and gets IDs nobody used before
+        let max_old = old.blocks.iter().map(block_id).max().unwrap();
+        assert!(block_id(&new.blocks[1]) > max_old);
+        // Inline children of an unchanged block keep their IDs too
+        assert_eq!(new.blocks[0], old.blocks[0]);
+    }
+
+    #[test]
+    fn inserted_and_moved_blocks() {
+        let old = parse_with_ids("Alpha\n\nBeta\n");
+        let new = reparse(MarkdownParserState::new(), &old, "Beta\n\nGamma\n\nAlpha\n").unwrap();
+
+        assert_eq!(block_id(&new.blocks[0]), block_id(&old.blocks[1]));
+        assert_eq!(block_id(&new.blocks[2]), block_id(&old.blocks[0]));
+        let fresh = block_id(&new.blocks[1]);
+        assert!(old.blocks.iter().all(|b| block_id(b) != fresh));
+    }
+
+    #[test]
+    fn duplicate_blocks_are_matched_once() {
+        let old = parse_with_ids("Same\n");
+        let new = reparse(MarkdownParserState::new(), &old, "Same\n\nSame\n").unwrap();
+
+        assert_eq!(block_id(&new.blocks[0]), block_id(&old.blocks[0]));
+        assert_ne!(block_id(&new.blocks[1]), block_id(&old.blocks[0]));
+    }
+}
@@ -20,24 +20,144 @@ impl<'a> ToDoc<'a> for Vec<&Block> {
         config: Rc<crate::printer::config::Config>,
         arena: &'a Arena<'a>,
     ) -> DocBuilder<'a, Arena<'a>, ()> {
-        let mut acc = arena.nil();
-        for (i, block) in self.iter().enumerate() {
-            if i > 0 {
-                // first block should not have an empty line before it
+        blocks_to_doc(self, 1, config, arena)
+    }
+}
+
+/// Render a sequence of blocks separated by `separation` blank lines
+///
+/// Blocks that would merge without a blank line between them are still
+/// separated by one, whatever `separation` is.
+pub(crate) fn blocks_to_doc<'a>(
+    blocks: &[&Block],
+    separation: usize,
+    config: Rc<crate::printer::config::Config>,
+    arena: &'a Arena<'a>,
+) -> DocBuilder<'a, Arena<'a>, ()> {
+    let mut acc = arena.nil();
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0 {
+            // first block should not have an empty line before it
+            acc = acc.append(arena.hardline());
+            if config.preserve_blank_lines && matches!(block, Block::Empty) {
+                // a preserved blank line adds exactly one line break
+                continue;
+            }
+            let blank_lines = if matches!(block, Block::List(_)) && !config.empty_line_before_list {
+                0
+            } else if separation == 0 && needs_blank_line(blocks[i - 1], block) {
+                1
+            } else {
+                separation
+            };
+            for _ in 0..blank_lines {
                 acc = acc.append(arena.hardline());
-                if matches!(block, Block::List(_)) {
-                    if config.empty_line_before_list {
-                        // empty line before list block
-                        acc = acc.append(arena.hardline());
-                    }
-                } else {
-                    acc = acc.append(arena.hardline());
-                }
             }
-            acc = acc.append(block.to_doc(config.clone(), arena))
         }
-        acc
+        acc = acc.append(block.to_doc(config.clone(), arena))
     }
+    acc
+}
+
+/// Link destination, in angle brackets if needed
+pub(crate) fn link_destination(destination: &str) -> String {
+    // Parentheses outside of backslash escapes must balance in a bare destination
+    let mut depth: i32 = 0;
+    let mut escaped = false;
+    let balanced = destination.chars().all(|c| {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        depth >= 0
+    }) && depth == 0;
+    if destination.is_empty()
+        || !balanced
+        || destination.contains(|c: char| c.is_whitespace() || c.is_ascii_control() || c == '<')
+    {
+        let escaped = destination
+            .replace('\\', "\\\\")
+            .replace('<', "\\<")
+            .replace('>', "\\>");
+        format!("<{escaped}>")
+    } else {
+        destination.to_owned()
+    }
+}
+
+/// Title of a link reference definition, quoted so that it parses back
+fn definition_title(title: &str) -> String {
+    if !title.contains('"') {
+        format!("\"{title}\"")
+    } else if !title.contains('\'') {
+        format!("'{title}'")
+    } else {
+        format!("\"{}\"", title.replace('"', "\\\""))
+    }
+}
+
+/// Whether `next` would be parsed as part of `prev` without a blank line
+fn needs_blank_line(prev: &Block, next: &Block) -> bool {
+    match prev {
+        // These blocks end on their own line
+        Block::Heading(_) | Block::ThematicBreak => false,
+        Block::CodeBlock(CodeBlock {
+            kind: CodeBlockKind::Fenced { .. },
+            ..
+        }) => false,
+        // Only constructs that can interrupt a paragraph; `---` would turn
+        // the paragraph into a setext heading
+        Block::Paragraph(_) => !matches!(
+            next,
+            Block::Heading(Heading {
+                kind: HeadingKind::Atx(_),
+                ..
+            }) | Block::BlockQuote(_)
+                | Block::CodeBlock(CodeBlock {
+                    kind: CodeBlockKind::Fenced { .. },
+                    ..
+                })
+        ),
+        _ => true,
+    }
+}
+
+/// Raw HTML, emitted byte for byte.
+///
+/// Lines are joined with hardlines so the indentation of an enclosing list
+/// or block quote applies to every line.
+pub(crate) fn raw_html_to_doc<'a>(
+    html: &str,
+    arena: &'a Arena<'a>,
+) -> DocBuilder<'a, Arena<'a>, ()> {
+    arena.intersperse(
+        html.split('\n').map(|line| arena.text(line.to_string())),
+        arena.hardline(),
+    )
+}
+
+/// How deeply containers are nested within `blocks`.
+fn container_nesting(blocks: &[Block]) -> usize {
+    blocks
+        .iter()
+        .map(|block| match block {
+            Block::Container(container) => 1 + container_nesting(&container.blocks),
+            Block::BlockQuote(blocks) => container_nesting(blocks),
+            Block::List(list) => list
+                .items
+                .iter()
+                .map(|item| container_nesting(&item.blocks))
+                .max()
+                .unwrap_or(0),
+            Block::FootnoteDefinition(def) => container_nesting(&def.blocks),
+            Block::GitHubAlert(alert) => container_nesting(&alert.blocks),
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0)
 }
 
 /// Block-level nodes
@@ -88,27 +208,38 @@ impl<'a> ToDoc<'a> for Block {
                         doc.append(arena.hardline()).append(arena.text("```"))
                     }
                     CodeBlockKind::Indented => {
-                        // Each line indented with 4 spaces
+                        // Each line indented with 4 spaces; blank lines stay empty
                         let indented = literal
                             .lines()
-                            .map(|l| format!("    {l}"))
+                            .map(|l| {
+                                if l.is_empty() {
+                                    String::new()
+                                } else {
+                                    format!("    {l}")
+                                }
+                            })
                             .collect::<Vec<_>>()
                             .join("\n");
                         arena.text(indented)
                     }
                 }
             }
-            Block::HtmlBlock(html) => arena.text(html.clone()),
+            Block::HtmlBlock(html) => {
+                // The parser keeps the line ending that terminated the block
+                let html = html.content.as_str();
+                let html = html.strip_suffix('\n').unwrap_or(html);
+                raw_html_to_doc(html.strip_suffix('\r').unwrap_or(html), arena)
+            }
             Block::Definition(def) => arena
                 .text("[")
                 .append(def.label.to_doc_inline(true, arena, config.clone()))
                 .append(arena.text("]: "))
                 .append(arena.text(format!(
                     "{}{}",
-                    def.destination,
+                    link_destination(&def.destination),
                     def.title
                         .as_ref()
-                        .map(|t| format!(" \"{t}\""))
+                        .map(|t| format!(" {}", definition_title(t)))
                         .unwrap_or_default()
                 ))),
 
@@ -122,13 +253,23 @@ impl<'a> ToDoc<'a> for Block {
             }
             Block::LatexBlock(latex) => arena.text(format!("$${}$$", latex)),
             Block::Container(container) => {
-                let mut doc = arena.text(format!(":::{}", container.kind));
+                if let Some(render) = config.container_renderers.get(&container.kind) {
+                    let rendered = render(container);
+                    return arena.intersperse(
+                        rendered.lines().map(|line| arena.text(line.to_string())),
+                        arena.hardline(),
+                    );
+                }
+                // Outer fences get more colons so nested closing fences
+                // don't end them early
+                let fence = ":".repeat(3 + container_nesting(&container.blocks));
+                let mut doc = arena.text(format!("{fence}{}", container.kind));
                 if !container.blocks.is_empty() {
                     doc = doc.append(arena.hardline());
                     doc = doc.append(container.blocks.to_doc(config, arena));
                     doc = doc.append(arena.hardline());
                 }
-                doc.append(arena.text(":::"))
+                doc.append(arena.text(fence))
             }
             Block::MacroBlock(content) => arena.text(format!("{{{{ {} }}}}", content)),
         }
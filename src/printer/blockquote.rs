@@ -10,7 +10,7 @@ pub(crate) fn blockquote_to_doc<'a>(
 ) -> DocBuilder<'a, Arena<'a>, ()> {
     let blocks = inner.to_owned();
     arena.column(move |current_column| {
-        let prefix = "> ";
+        let prefix = config.blockquote_marker.clone();
         let tmp_arena = Arena::new();
         let doc = blocks.to_doc(config.clone(), &tmp_arena);
 
@@ -19,11 +19,9 @@ pub(crate) fn blockquote_to_doc<'a>(
             .unwrap();
         let text = String::from_utf8(buf).unwrap();
 
-        let lines = text.lines().map(|d| {
-            arena
-                .as_string(prefix.to_string())
-                .append(arena.as_string(d))
-        });
+        let lines = text
+            .lines()
+            .map(|d| arena.as_string(prefix.clone()).append(arena.as_string(d)));
 
         arena.intersperse(lines, arena.hardline()).into_doc()
     })
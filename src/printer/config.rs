@@ -1,9 +1,110 @@
+use crate::ast::Container;
+pub use crate::render::{ContainerRenderFn, EscapeFn, LineEnding};
+use std::collections::HashMap;
+use std::sync::Arc;
+
 /// Configuration for Markdown pretty-printing output.
+#[derive(Clone)]
 pub struct Config {
     pub(crate) width: usize,
     pub(crate) spaces_before_list_item: usize,
     pub(crate) empty_line_before_list: bool,
     pub(crate) smart_wrapping: bool,
+    pub(crate) list_spacing: ListSpacing,
+    pub(crate) ordered_list_numbering: OrderedListNumbering,
+    pub(crate) list_indent: Option<usize>,
+    pub(crate) blockquote_marker: String,
+    pub(crate) escape_override: Option<EscapeFn>,
+    pub(crate) block_separation: usize,
+    pub(crate) preserve_blank_lines: bool,
+    pub(crate) align_ordered_list_markers: bool,
+    pub(crate) line_ending: LineEnding,
+    pub(crate) table_alignment: TableAlignment,
+    pub(crate) combined_emphasis: CombinedEmphasis,
+    pub(crate) soft_break: SoftBreakStyle,
+    pub(crate) non_breaking_space: NonBreakingSpaceStyle,
+    pub(crate) container_renderers: HashMap<String, ContainerRenderFn>,
+}
+
+/// How the blank lines between list items are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListSpacing {
+    /// Follow the `tight` flag of each list. A loose list gets no blank
+    /// lines between items when an item already has one between its blocks.
+    #[default]
+    Preserve,
+
+    /// Render every list tight, without blank lines between items.
+    Tight,
+
+    /// Render every list loose, with a blank line between items.
+    Loose,
+}
+
+/// How the markers of ordered list items are numbered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrderedListNumbering {
+    /// Start at the list's stored start number and count up (`5.`, `6.`, `7.`).
+    #[default]
+    Incrementing,
+
+    /// Use `1.` for every item.
+    AllOnes,
+}
+
+/// How table columns are laid out in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableAlignment {
+    /// Pad cells to the widest cell of their column so the pipes line up.
+    #[default]
+    Pretty,
+
+    /// Separate cells by single spaces without padding.
+    Compact,
+}
+
+/// How text that is both strong and emphasized is delimited.
+///
+/// Applies to a strong node whose only child is an emphasis node. The chosen
+/// style is used only if it parses back to that nesting; otherwise `***` is
+/// written. Emphasis around strong is always written as `_**text**_`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CombinedEmphasis {
+    /// `***text***`, parsed as strong around emphasis.
+    #[default]
+    Triple,
+
+    /// `**_text_**`, parsed as strong around emphasis.
+    StrongOutside,
+
+    /// `_**text**_`, parsed as emphasis around strong, so strong around
+    /// emphasis is written as `***text***`.
+    EmphasisOutside,
+}
+
+/// How a soft line break ([`crate::ast::Inline::SoftBreak`]) is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SoftBreakStyle {
+    /// Reflow it like any other space between words.
+    #[default]
+    Space,
+
+    /// Keep the line break, so paragraph lines end where they did.
+    Newline,
+
+    /// Write a hard line break instead.
+    LineBreak,
+}
+
+/// How a non-breaking space (U+00A0) in text is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NonBreakingSpaceStyle {
+    /// The character itself.
+    #[default]
+    Literal,
+
+    /// The `&nbsp;` entity, which makes the space visible in the source.
+    Entity,
 }
 
 impl Default for Config {
@@ -13,6 +114,20 @@ impl Default for Config {
             spaces_before_list_item: 1,
             empty_line_before_list: true,
             smart_wrapping: false,
+            list_spacing: ListSpacing::Preserve,
+            ordered_list_numbering: OrderedListNumbering::Incrementing,
+            list_indent: None,
+            blockquote_marker: "> ".to_string(),
+            escape_override: None,
+            block_separation: 1,
+            preserve_blank_lines: false,
+            align_ordered_list_markers: false,
+            line_ending: LineEnding::Lf,
+            table_alignment: TableAlignment::Pretty,
+            combined_emphasis: CombinedEmphasis::Triple,
+            soft_break: SoftBreakStyle::Space,
+            non_breaking_space: NonBreakingSpaceStyle::Literal,
+            container_renderers: HashMap::new(),
         }
     }
 }
@@ -64,4 +179,190 @@ impl Config {
             ..self
         }
     }
+
+    /// Sets how the spacing between list items is rendered.
+    ///
+    /// The default is [`ListSpacing::Preserve`], which keeps tight lists tight and
+    /// loose lists loose.
+    pub fn with_list_spacing(self, list_spacing: ListSpacing) -> Self {
+        Self {
+            list_spacing,
+            ..self
+        }
+    }
+
+    /// Sets how ordered list items are numbered.
+    ///
+    /// With [`OrderedListNumbering::AllOnes`] every marker is `1.`, so the
+    /// stored start number is not preserved.
+    pub fn with_ordered_list_numbering(self, ordered_list_numbering: OrderedListNumbering) -> Self {
+        Self {
+            ordered_list_numbering,
+            ..self
+        }
+    }
+
+    /// Sets whether ordered list markers are right-aligned.
+    ///
+    /// When enabled, shorter numbers are padded with spaces (`  9.`, ` 10.`)
+    /// so the item text of a list starts at the same column. The default is
+    /// `false`. The padding counts towards the at most 3 spaces allowed
+    /// before a list marker.
+    pub fn with_align_ordered_list_markers(self, align_ordered_list_markers: bool) -> Self {
+        Self {
+            align_ordered_list_markers,
+            ..self
+        }
+    }
+
+    /// Sets the indentation of continuation lines and nested blocks inside list items.
+    ///
+    /// By default the content is aligned with the text after the list marker
+    /// (e.g. 3 columns for ` - `). An indentation smaller than that may change
+    /// how the output is parsed back.
+    pub fn with_list_indent(self, indent: usize) -> Self {
+        Self {
+            list_indent: Some(indent),
+            ..self
+        }
+    }
+
+    /// Sets the prefix written before every line of a blockquote.
+    ///
+    /// The default is `"> "`.
+    pub fn with_blockquote_marker(self, marker: &str) -> Self {
+        Self {
+            blockquote_marker: marker.to_string(),
+            ..self
+        }
+    }
+
+    /// Sets a hook that escapes characters of plain text.
+    ///
+    /// Text is printed verbatim by default; the hook can escape characters
+    /// that are significant to a downstream system. Code, URLs and raw HTML
+    /// are not affected.
+    pub fn with_escape_override<F>(self, escape: F) -> Self
+    where
+        F: Fn(char) -> Option<String> + Send + Sync + 'static,
+    {
+        Self {
+            escape_override: Some(Arc::new(escape)),
+            ..self
+        }
+    }
+
+    /// Sets the number of blank lines between top-level blocks.
+    ///
+    /// The default is 1. With 0, blocks that would otherwise merge (such as
+    /// two consecutive paragraphs) are still separated by one blank line.
+    pub fn with_block_separation(self, block_separation: usize) -> Self {
+        Self {
+            block_separation,
+            ..self
+        }
+    }
+
+    /// Sets whether `Block::Empty` markers are rendered as blank lines.
+    ///
+    /// Pair this with the parser's `with_preserve_blank_lines` to keep runs of
+    /// blank lines between blocks. The default is `false`, which renders
+    /// `Block::Empty` like any other empty block, as left behind by blocks
+    /// the parser skips.
+    pub fn with_preserve_blank_lines(self, preserve_blank_lines: bool) -> Self {
+        Self {
+            preserve_blank_lines,
+            ..self
+        }
+    }
+
+    /// Sets the line terminator of the output.
+    ///
+    /// The default is [`LineEnding::Lf`]. Line breaks inside code blocks and
+    /// other literal content are converted as well.
+    pub fn with_line_ending(self, line_ending: LineEnding) -> Self {
+        Self {
+            line_ending,
+            ..self
+        }
+    }
+
+    /// Sets how table columns are laid out.
+    ///
+    /// The default is [`TableAlignment::Pretty`]. [`TableAlignment::Compact`]
+    /// keeps lines short when some cells are much wider than others.
+    pub fn with_table_alignment(self, table_alignment: TableAlignment) -> Self {
+        Self {
+            table_alignment,
+            ..self
+        }
+    }
+
+    /// Sets how text that is both strong and emphasized is delimited.
+    ///
+    /// The default is [`CombinedEmphasis::Triple`].
+    pub fn with_combined_emphasis(self, combined_emphasis: CombinedEmphasis) -> Self {
+        Self {
+            combined_emphasis,
+            ..self
+        }
+    }
+
+    /// Sets how soft line breaks are written.
+    ///
+    /// The default is [`SoftBreakStyle::Space`]. Soft breaks only appear in
+    /// the AST when the parser preserves them; otherwise the newline is part
+    /// of the text and always reflowed.
+    pub fn with_soft_break(self, soft_break: SoftBreakStyle) -> Self {
+        Self { soft_break, ..self }
+    }
+
+    /// Sets how non-breaking spaces in text are written.
+    ///
+    /// The default is [`NonBreakingSpaceStyle::Literal`]. Either way a
+    /// non-breaking space is never turned into a line break, and both forms
+    /// parse back to the same character.
+    pub fn with_non_breaking_space(self, non_breaking_space: NonBreakingSpaceStyle) -> Self {
+        Self {
+            non_breaking_space,
+            ..self
+        }
+    }
+
+    /// Registers a renderer for containers of the given `kind`.
+    ///
+    /// Containers of other kinds keep the default `:::kind` fences. The
+    /// returned text is indented like any other block when the container is
+    /// nested in a list or block quote.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::printer::{config::Config, render_markdown};
+    ///
+    /// let config = Config::default().with_container_renderer("aside", |container| {
+    ///     let body = render_markdown(
+    ///         &Document { blocks: container.blocks.clone() },
+    ///         Config::default(),
+    ///     );
+    ///     format!("<aside>\n\n{body}\n\n</aside>")
+    /// });
+    /// let doc = Document {
+    ///     blocks: vec![Block::Container(Container {
+    ///         kind: "aside".to_string(),
+    ///         params: vec![],
+    ///         blocks: vec![Block::Paragraph(vec![Inline::Text("Hi".to_string())])],
+    ///     })],
+    /// };
+    /// assert_eq!(render_markdown(&doc, config), "<aside>\n\nHi\n\n</aside>");
+    /// ```
+    pub fn with_container_renderer<F>(mut self, kind: impl Into<String>, render: F) -> Self
+    where
+        F: Fn(&Container) -> String + Send + Sync + 'static,
+    {
+        self.container_renderers
+            .insert(kind.into(), Arc::new(render));
+        self
+    }
 }
@@ -23,29 +23,35 @@ pub(crate) fn github_alert_to_doc<'a>(
     config: Rc<Config>,
     arena: &'a Arena<'a>,
 ) -> DocBuilder<'a, Arena<'a>, ()> {
-    // Create the alert marker line
-    let marker = format!("> [!{}]", alert.alert_type.as_markdown_str().to_uppercase());
-    let mut lines = vec![marker];
+    let alert = alert.clone();
+    arena.column(move |current_column| {
+        // Create the alert marker line
+        let marker = format!("> [!{}]", alert.alert_type.as_markdown_str().to_uppercase());
+        let mut lines = vec![marker];
 
-    // Convert alert blocks to blockquote format
-    if !alert.blocks.is_empty() {
-        let content_doc = alert.blocks.to_doc(config.clone(), arena);
-        let content_string = content_doc.pretty(80).to_string();
+        // Convert alert blocks to blockquote format, wrapping to the space
+        // left after the "> " prefix
+        if !alert.blocks.is_empty() {
+            let tmp_arena = Arena::new();
+            let content_doc = alert.blocks.to_doc(config.clone(), &tmp_arena);
+            let width = config.width.saturating_sub(current_column + 2);
+            let content_string = content_doc.pretty(width).to_string();
 
-        for line in content_string.lines() {
-            if line.is_empty() {
-                lines.push(">".to_string());
-            } else if line.chars().all(|c| c.is_whitespace()) {
-                // Line contains only whitespace - convert to empty blockquote line
-                lines.push(">".to_string());
-            } else {
-                lines.push(format!("> {line}"));
+            for line in content_string.lines() {
+                if line.chars().all(|c| c.is_whitespace()) {
+                    // Empty or whitespace-only line - convert to empty blockquote line
+                    lines.push(">".to_string());
+                } else {
+                    lines.push(format!("> {line}"));
+                }
             }
         }
-    }
 
-    arena.intersperse(
-        lines.into_iter().map(|line| arena.text(line)),
-        arena.hardline(),
-    )
+        arena
+            .intersperse(
+                lines.into_iter().map(|line| arena.text(line)),
+                arena.hardline(),
+            )
+            .into_doc()
+    })
 }
@@ -1,5 +1,6 @@
 use crate::ast::*;
-use crate::printer::config::Config;
+use crate::printer::block::link_destination;
+use crate::printer::config::{CombinedEmphasis, Config, NonBreakingSpaceStyle, SoftBreakStyle};
 use crate::printer::markdown_syntax_detector::is_safe_line_break_before;
 use pretty::{Arena, DocAllocator, DocBuilder};
 use std::rc::Rc;
@@ -20,6 +21,10 @@ impl<'a> ToDocInline<'a> for [Inline] {
         arena: &'a Arena<'a>,
         config: Rc<Config>,
     ) -> DocBuilder<'a, Arena<'a>, ()> {
+        if config.soft_break == SoftBreakStyle::Space && self.contains(&Inline::SoftBreak) {
+            // Wrapping only sees the break as a space once it is part of the text
+            return join_soft_breaks(self).to_doc_inline(allow_newlines, arena, config);
+        }
         arena.concat(
             self.iter()
                 .map(|inline| inline.to_doc_inline(allow_newlines, arena, config.clone()))
@@ -35,9 +40,30 @@ impl<'a> ToDocInline<'a> for Inline {
         arena: &'a Arena<'a>,
         config: Rc<Config>,
     ) -> DocBuilder<'a, Arena<'a>, ()> {
+        if let Some((strong_outside, children)) = combined_emphasis(self) {
+            // Only a style that spells out the same nesting is applied
+            let (open, close) = match (strong_outside, config.combined_emphasis) {
+                (true, CombinedEmphasis::StrongOutside) => ("**_", "_**"),
+                (true, _) => ("***", "***"),
+                (false, _) => ("_**", "**_"),
+            };
+            return arena
+                .text(open)
+                .append(children.to_doc_inline(allow_newlines, arena, config.clone()))
+                .append(arena.text(close));
+        }
         match self {
             Inline::Text(t) => {
-                let t = t.replace('\n', " ");
+                let mut t = t.replace('\n', " ");
+                if let Some(escape) = &config.escape_override {
+                    t = t
+                        .chars()
+                        .map(|c| escape(c).unwrap_or_else(|| c.to_string()))
+                        .collect();
+                }
+                if config.non_breaking_space == NonBreakingSpaceStyle::Entity {
+                    t = t.replace('\u{a0}', "&nbsp;");
+                }
                 let words_or_spaces: Vec<_> = split_with_spaces(&t);
 
                 if !allow_newlines {
@@ -53,10 +79,20 @@ impl<'a> ToDocInline<'a> for Inline {
                 }
             }
             // TODO parametrize format
-            Inline::LineBreak => arena.text("  \n"),
+            Inline::LineBreak if allow_newlines => arena.text("  \n"),
+            // Table cells and ATX headings must stay on one line
+            Inline::LineBreak => arena.text("<br>"),
+            Inline::SoftBreak => match config.soft_break {
+                SoftBreakStyle::Newline if allow_newlines => arena.hardline(),
+                SoftBreakStyle::LineBreak if allow_newlines => {
+                    Inline::LineBreak.to_doc_inline(allow_newlines, arena, config.clone())
+                }
+                _ if allow_newlines => arena.softline(),
+                _ => arena.space(),
+            },
             Inline::Code(code) => arena.text("`").append(code.clone()).append(arena.text("`")),
             Inline::Latex(latex) => arena.text(format!("${}$", latex)),
-            Inline::Html(html) => arena.text(html.clone()),
+            Inline::Html(html) => crate::printer::block::raw_html_to_doc(html, arena),
             Inline::Emphasis(children) => arena
                 .text("*")
                 .append(children.to_doc_inline(allow_newlines, arena, config.clone()))
@@ -69,6 +105,28 @@ impl<'a> ToDocInline<'a> for Inline {
                 .text("~~")
                 .append(children.to_doc_inline(allow_newlines, arena, config.clone()))
                 .append(arena.text("~~")),
+            Inline::Highlight(children) => arena
+                .text("==")
+                .append(children.to_doc_inline(allow_newlines, arena, config.clone()))
+                .append(arena.text("==")),
+            Inline::Subscript(children) => arena
+                .text("~")
+                .append(children.to_doc_inline(allow_newlines, arena, config.clone()))
+                .append(arena.text("~")),
+            Inline::Superscript(children) => arena
+                .text("^")
+                .append(children.to_doc_inline(allow_newlines, arena, config.clone()))
+                .append(arena.text("^")),
+            Inline::Delimited(delimited) => match delimited_children(delimited) {
+                Some(children) => arena
+                    .text(delimited.delimiter.clone())
+                    .append(children.to_doc_inline(allow_newlines, arena, config.clone()))
+                    .append(arena.text(delimited.delimiter.clone())),
+                // The node no longer matches its delimiters
+                None => delimited
+                    .inner
+                    .to_doc_inline(allow_newlines, arena, config.clone()),
+            },
             Inline::Link(Link {
                 destination,
                 title,
@@ -85,7 +143,7 @@ impl<'a> ToDocInline<'a> for Inline {
                     .text("[")
                     .append(children.to_doc_inline(allow_newlines, arena, config.clone()))
                     .append(arena.text("]("))
-                    .append(arena.text(destination.clone()))
+                    .append(arena.text(link_destination(destination)))
                     .append(title)
                     .append(")")
             }
@@ -103,18 +161,14 @@ impl<'a> ToDocInline<'a> for Inline {
                 let attr_part = attr
                     .as_ref()
                     .map(|a| {
-                        let mut attrs = Vec::new();
-                        if let Some(width) = &a.width {
-                            attrs.push(format!("width=\"{}\"", width));
-                        }
-                        if let Some(height) = &a.height {
-                            attrs.push(format!("height=\"{}\"", height));
-                        }
-                        if attrs.is_empty() {
-                            String::new()
-                        } else {
-                            format!("{{{}}}", attrs.join(" "))
-                        }
+                        let attrs: Vec<_> = [("width", &a.width), ("height", &a.height)]
+                            .into_iter()
+                            .filter_map(|(key, value)| {
+                                let value = value.as_deref()?;
+                                Some(format!("{key}={}", image_attr_value(value)))
+                            })
+                            .collect();
+                        format!("{{{}}}", attrs.join(" "))
                     })
                     .unwrap_or_default();
 
@@ -122,7 +176,7 @@ impl<'a> ToDocInline<'a> for Inline {
                     .text("![")
                     .append(arena.text(alt.clone()))
                     .append("](")
-                    .append(arena.text(destination.clone()))
+                    .append(arena.text(link_destination(destination)))
                     .append(arena.text(title_part))
                     .append(arena.text(")"))
                     .append(arena.text(attr_part))
@@ -148,13 +202,65 @@ impl<'a> ToDocInline<'a> for Inline {
     }
 }
 
+/// Content of a strong node wrapping only an emphasis node, or the reverse,
+/// and whether strong is the outer node
+fn combined_emphasis(inline: &Inline) -> Option<(bool, &Vec<Inline>)> {
+    match inline {
+        Inline::Strong(children) => match children.as_slice() {
+            [Inline::Emphasis(inner)] => Some((true, inner)),
+            _ => None,
+        },
+        Inline::Emphasis(children) => match children.as_slice() {
+            [Inline::Strong(inner)] => Some((false, inner)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Inlines with soft breaks turned into newlines of the adjacent text
+fn join_soft_breaks(inlines: &[Inline]) -> Vec<Inline> {
+    let mut result: Vec<Inline> = Vec::with_capacity(inlines.len());
+    for inline in inlines {
+        match (result.last_mut(), inline) {
+            (Some(Inline::Text(prev)), Inline::SoftBreak) => prev.push('\n'),
+            (Some(Inline::Text(prev)), Inline::Text(text)) => prev.push_str(text),
+            (_, Inline::SoftBreak) => result.push(Inline::Text("\n".to_string())),
+            (_, inline) => result.push(inline.clone()),
+        }
+    }
+    result
+}
+
+/// Image attribute value, quoted only if it would not parse bare
+fn image_attr_value(value: &str) -> String {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '}' || c == '"') {
+        format!("\"{value}\"")
+    } else {
+        value.to_owned()
+    }
+}
+
+/// Content between the delimiters, if the node still matches their length
+fn delimited_children(delimited: &Delimited) -> Option<&Vec<Inline>> {
+    match (delimited.delimiter.len(), delimited.inner.as_ref()) {
+        (1, Inline::Emphasis(children)) | (2, Inline::Strong(children)) => Some(children),
+        (3, Inline::Strong(children)) => match children.as_slice() {
+            [Inline::Emphasis(inner)] => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 /// Split string by spaces, but keep the spaces in the result.
 fn split_with_spaces(s: &str) -> Vec<Option<&str>> {
     let mut result = Vec::new();
     let mut word_start: Option<usize> = None;
 
     for (i, c) in s.char_indices() {
-        if c.is_whitespace() {
+        // Non-breaking spaces stay inside the word
+        if c.is_whitespace() && !matches!(c, '\u{a0}' | '\u{2007}' | '\u{202f}') {
             if let Some(start) = word_start {
                 result.push(Some(&s[start..i]));
                 word_start = None;
@@ -1,5 +1,8 @@
 use crate::ast::*;
-use crate::printer::ToDoc;
+use crate::printer::{
+    config::{ListSpacing, OrderedListNumbering},
+    ToDoc,
+};
 use pretty::{Arena, DocAllocator, DocBuilder};
 use std::rc::Rc;
 
@@ -14,23 +17,44 @@ impl<'a> ToDoc<'a> for List {
         } else {
             0
         };
-        let prefix_length = match &self.kind {
+        let marker_width = match &self.kind {
             ListKind::Bullet(ListBulletKind::Dash) => 2 + config.spaces_before_list_item, // <space>-<space>
             ListKind::Bullet(ListBulletKind::Star) => 2 + config.spaces_before_list_item, // <space>*<space>
             ListKind::Bullet(ListBulletKind::Plus) => 2 + config.spaces_before_list_item, // <space>+<space>
+            ListKind::Ordered(_)
+                if config.ordered_list_numbering == OrderedListNumbering::AllOnes =>
+            {
+                3 + config.spaces_before_list_item // <space>1.<space>
+            }
             ListKind::Ordered(v) => {
-                let last = v.start + self.items.len() as u64 - 1;
-                let digits = last.to_string().len();
+                // Roman numerals are not widest at the last item
+                let digits = (v.start..v.start + self.items.len() as u64)
+                    .map(|n| v.style.format(n).len())
+                    .max()
+                    .unwrap_or(1);
                 digits + 2 + config.spaces_before_list_item // <space>1.<space>
             }
         };
+        // Width of the widest ordered marker, for right-alignment
+        let number_width = match &self.kind {
+            ListKind::Ordered(_) if config.align_ordered_list_markers => {
+                marker_width - 1 - config.spaces_before_list_item
+            }
+            _ => 0,
+        };
+        let prefix_length = config.list_indent.unwrap_or(marker_width);
         let items = self.items.iter().map(|item| {
-            let marker = match self.kind {
+            let marker = match &self.kind {
                 ListKind::Bullet(ListBulletKind::Dash) => "-".to_owned(),
                 ListKind::Bullet(ListBulletKind::Star) => "*".to_owned(),
                 ListKind::Bullet(ListBulletKind::Plus) => "+".to_owned(),
-                ListKind::Ordered(_) => {
-                    let r = format!("{counter}.");
+                ListKind::Ordered(v)
+                    if config.ordered_list_numbering == OrderedListNumbering::AllOnes =>
+                {
+                    format!("{}.", v.style.format(1))
+                }
+                ListKind::Ordered(v) => {
+                    let r = format!("{:>number_width$}", format!("{}.", v.style.format(counter)));
                     counter += 1;
                     r
                 }
@@ -57,6 +81,25 @@ impl<'a> ToDoc<'a> for List {
                 )
         });
 
-        arena.intersperse(items, arena.hardline())
+        // A blank line between the children of one item already makes the
+        // list loose, so items only need blank lines between them without it
+        let children_spaced = self.items.iter().any(|item| {
+            item.blocks.iter().skip(1).any(|block| match block {
+                Block::List(_) => config.empty_line_before_list,
+                _ => true,
+            })
+        });
+        let loose = match config.list_spacing {
+            ListSpacing::Preserve => !self.tight && !children_spaced,
+            ListSpacing::Tight => false,
+            ListSpacing::Loose => true,
+        };
+        let separator = if loose {
+            arena.hardline().append(arena.hardline())
+        } else {
+            arena.hardline()
+        };
+
+        arena.intersperse(items, separator)
     }
 }
@@ -64,7 +64,10 @@ mod table;
 mod tests;
 
 use crate::ast::*;
+use crate::printer::inline::ToDocInline;
+use crate::render::LineEndingWriter;
 use pretty::{Arena, DocBuilder};
+use std::io::Write;
 use std::rc::Rc;
 
 /// Render a Markdown AST back to formatted Markdown text
@@ -121,15 +124,110 @@ use std::rc::Rc;
 /// ```
 /// Where ≈ means semantically equivalent AST structures.
 pub fn render_markdown(ast: &Document, config: crate::printer::config::Config) -> String {
-    let config = Rc::new(config);
-    let arena = Arena::new();
-    let doc = ast.to_doc(config.clone(), &arena);
+    render(config, |config, arena| ast.to_doc(config, arena))
+}
 
+/// Render a Markdown AST into a writer
+///
+/// Produces the same output as [`render_markdown`], but writes it to
+/// `writer` without building a `String` first.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::ast::*;
+/// use markdown_ppp::printer::{render_markdown_to, config::Config};
+///
+/// let doc = Document {
+///     blocks: vec![Block::Paragraph(vec![Inline::Text("Hello".to_string())])],
+/// };
+/// let mut out = Vec::new();
+/// render_markdown_to(&doc, Config::default(), &mut out).unwrap();
+/// assert_eq!(out, b"Hello");
+/// ```
+///
+/// # Errors
+///
+/// Returns any error from writing to `writer`.
+pub fn render_markdown_to<W: std::io::Write>(
+    ast: &Document,
+    config: crate::printer::config::Config,
+    writer: W,
+) -> std::io::Result<()> {
+    render_to(config, |config, arena| ast.to_doc(config, arena), writer)
+}
+
+/// Render a sequence of inline elements to Markdown
+///
+/// The elements are laid out as they would be inside a paragraph, without
+/// constructing a [`Document`].
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::ast::*;
+/// use markdown_ppp::printer::{render_inlines, config::Config};
+///
+/// let inlines = vec![Inline::Emphasis(vec![Inline::Text("caption".to_string())])];
+/// assert_eq!(render_inlines(&inlines, Config::default()), "*caption*");
+/// ```
+pub fn render_inlines(inlines: &[Inline], config: crate::printer::config::Config) -> String {
+    render(config, |config, arena| {
+        inlines.to_doc_inline(true, arena, config)
+    })
+}
+
+/// Render a single block to Markdown
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::ast::*;
+/// use markdown_ppp::printer::{render_block, config::Config};
+///
+/// let block = Block::Heading(Heading {
+///     kind: HeadingKind::Atx(2),
+///     content: vec![Inline::Text("Usage".to_string())],
+/// });
+/// assert_eq!(render_block(&block, Config::default()), "## Usage");
+/// ```
+pub fn render_block(block: &Block, config: crate::printer::config::Config) -> String {
+    render(config, |config, arena| block.to_doc(config, arena))
+}
+
+fn render<F>(config: crate::printer::config::Config, to_doc: F) -> String
+where
+    F: for<'a> FnOnce(
+        Rc<crate::printer::config::Config>,
+        &'a Arena<'a>,
+    ) -> DocBuilder<'a, Arena<'a>, ()>,
+{
     let mut buf = Vec::new();
-    doc.render(config.width, &mut buf).unwrap();
+    render_to(config, to_doc, &mut buf).unwrap();
     String::from_utf8(buf).unwrap()
 }
 
+fn render_to<F, W>(
+    config: crate::printer::config::Config,
+    to_doc: F,
+    writer: W,
+) -> std::io::Result<()>
+where
+    F: for<'a> FnOnce(
+        Rc<crate::printer::config::Config>,
+        &'a Arena<'a>,
+    ) -> DocBuilder<'a, Arena<'a>, ()>,
+    W: std::io::Write,
+{
+    let config = Rc::new(config);
+    let arena = Arena::new();
+    let doc = to_doc(config.clone(), &arena);
+
+    let mut writer = LineEndingWriter::new(writer, config.line_ending);
+    doc.render(config.width, &mut writer)?;
+    writer.flush()
+}
+
 trait ToDoc<'a> {
     fn to_doc(
         &self,
@@ -144,6 +242,8 @@ impl<'a> ToDoc<'a> for Document {
         config: Rc<crate::printer::config::Config>,
         arena: &'a Arena<'a>,
     ) -> DocBuilder<'a, Arena<'a>, ()> {
-        self.blocks.to_doc(config, arena)
+        let blocks: Vec<_> = self.blocks.iter().collect();
+        let separation = config.block_separation;
+        block::blocks_to_doc(&blocks, separation, config, arena)
     }
 }
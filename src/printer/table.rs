@@ -1,4 +1,5 @@
 use crate::ast::*;
+use crate::printer::config::TableAlignment;
 use crate::printer::{inline::ToDocInline, ToDoc};
 use core::iter::Iterator;
 use pretty::{Arena, DocAllocator, DocBuilder};
@@ -7,7 +8,7 @@ use std::rc::Rc;
 impl<'a> ToDoc<'a> for Table {
     fn to_doc(
         &self,
-        _config: Rc<crate::printer::config::Config>,
+        config: Rc<crate::printer::config::Config>,
         arena: &'a Arena<'a>,
     ) -> DocBuilder<'a, Arena<'a>, ()> {
         if self.rows.is_empty() {
@@ -15,9 +16,16 @@ impl<'a> ToDoc<'a> for Table {
         }
 
         let content = table_content(self);
-        let columns_width = columns_width(&content, &self.alignments);
+        let (columns_width, separator_width) = match config.table_alignment {
+            TableAlignment::Pretty => {
+                let widths = columns_width(&content, &self.alignments);
+                (widths.clone(), widths)
+            }
+            // No padding, and the shortest valid delimiter row
+            TableAlignment::Compact => (vec![0; content[0].len()], vec![3; content[0].len()]),
+        };
         let header = row_to_doc(&content[0], &columns_width, &self.alignments, arena);
-        let separator = alignments_row_to_doc(&self.alignments, &columns_width, arena);
+        let separator = alignments_row_to_doc(&self.alignments, &separator_width, arena);
 
         let body = content
             .iter()
@@ -115,11 +123,11 @@ fn cell_to_doc<'a>(
             format!(
                 "{}{}",
                 cell,
-                " ".repeat(column_width - cell.chars().count())
+                " ".repeat(column_width.saturating_sub(cell.chars().count()))
             )
         }
         Alignment::Center => {
-            let padding = column_width - cell.chars().count();
+            let padding = column_width.saturating_sub(cell.chars().count());
             let left_padding = padding / 2;
             let right_padding = padding - left_padding;
             format!(
@@ -131,7 +139,7 @@ fn cell_to_doc<'a>(
         }
         Alignment::Right => format!(
             "{}{}",
-            " ".repeat(column_width - cell.chars().count()),
+            " ".repeat(column_width.saturating_sub(cell.chars().count())),
             cell
         ),
     };
@@ -197,5 +205,6 @@ fn render_cell(doc: &[Inline]) -> String {
 
     let mut buf = Vec::new();
     doc.render(usize::MAX, &mut buf).unwrap();
-    String::from_utf8(buf).unwrap()
+    // A pipe ends the cell unless escaped, even inside code spans
+    String::from_utf8(buf).unwrap().replace('|', "\\|")
 }
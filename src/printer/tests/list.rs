@@ -237,3 +237,231 @@ fn fenced_code_block_in_blockquote_idempotent(input: &str) {
         input, pass1, pass2
     );
 }
+
+#[rstest(
+    input,
+    case(
+        r#" - item1
+
+ - item2"#
+    ),
+    case(
+        r#" 1. item1
+
+ 2. item2"#
+    ),
+    case(
+        r#" - item1
+ - item2"#
+    ),
+    case(" - item1\n   \n   more item1\n - item2")
+)]
+fn symmetric_round_trip_list_spacing(input: &str) {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(input, result);
+}
+
+#[rstest(
+    input,
+    spacing,
+    expected,
+    case(
+        " - a\n\n - b",
+        crate::printer::config::ListSpacing::Tight,
+        " - a\n - b"
+    ),
+    case(
+        " - a\n - b",
+        crate::printer::config::ListSpacing::Loose,
+        " - a\n\n - b"
+    )
+)]
+fn list_spacing_normalization(
+    input: &str,
+    spacing: crate::printer::config::ListSpacing,
+    expected: &str,
+) {
+    let config = crate::printer::config::Config::default().with_list_spacing(spacing);
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let result = crate::printer::render_markdown(&doc, config);
+    assert_eq!(expected, result);
+}
+
+#[rstest(
+    indent,
+    expected,
+    case(
+        2,
+        r#"- item1
+  - item1 1
+  - item1 2
+- item2"#
+    ),
+    case(
+        4,
+        r#"- item1
+    - item1 1
+    - item1 2
+- item2"#
+    )
+)]
+fn list_indent(indent: usize, expected: &str) {
+    let input = "- item1\n  - item1 1\n  - item1 2\n- item2";
+    let config = crate::printer::config::Config::default()
+        .with_spaces_before_list_item(0)
+        .with_empty_line_before_list(false)
+        .with_list_indent(indent);
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let result = crate::printer::render_markdown(&doc, config);
+    assert_eq!(expected, result);
+
+    // The rendered output keeps the nesting
+    let reparsed =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), &result)
+            .unwrap();
+    assert_eq!(doc, reparsed);
+}
+
+#[test]
+fn blockquote_marker() {
+    let config = crate::printer::config::Config::default().with_blockquote_marker(">  ");
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::default(),
+        "> line1\n>\n> line2",
+    )
+    .unwrap();
+    let result = crate::printer::render_markdown(&doc, config);
+    assert_eq!(">  line1\n>  \n>  line2", result);
+}
+
+#[rstest(
+    kind,
+    expected,
+    case(
+        crate::ast::ListKind::Bullet(crate::ast::ListBulletKind::Dash),
+        " - [X] done\n - [ ] todo\n - plain"
+    ),
+    case(
+        crate::ast::ListKind::Ordered(crate::ast::ListOrderedKindOptions {
+    start: 1,
+    style: crate::ast::OrderedListStyle::Decimal,
+}),
+        " 1. [X] done\n 2. [ ] todo\n 3. plain"
+    )
+)]
+fn task_list_items(kind: crate::ast::ListKind, expected: &str) {
+    use crate::ast::*;
+
+    let item = |task: Option<TaskState>, text: &str| ListItem {
+        task,
+        blocks: vec![Block::Paragraph(vec![Inline::Text(text.to_string())])],
+    };
+    let doc = Document {
+        blocks: vec![Block::List(List {
+            kind,
+            items: vec![
+                item(Some(TaskState::Complete), "done"),
+                item(Some(TaskState::Incomplete), "todo"),
+                item(None, "plain"),
+            ],
+            tight: true,
+        })],
+    };
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(expected, result);
+
+    let reparsed =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), &result)
+            .unwrap();
+    assert_eq!(doc, reparsed);
+}
+
+#[rstest(
+    numbering,
+    expected,
+    case(
+        crate::printer::config::OrderedListNumbering::Incrementing,
+        " 5. five\n 6. six\n 7. seven"
+    ),
+    case(
+        crate::printer::config::OrderedListNumbering::AllOnes,
+        " 1. five\n 1. six\n 1. seven"
+    )
+)]
+fn ordered_list_numbering(numbering: crate::printer::config::OrderedListNumbering, expected: &str) {
+    let input = "5. five\n6. six\n7. seven";
+    let config = crate::printer::config::Config::default().with_ordered_list_numbering(numbering);
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let result = crate::printer::render_markdown(&doc, config);
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn aligned_ordered_list_markers() {
+    let input = (1..=12)
+        .map(|i| format!("{i}. item"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), &input)
+        .unwrap();
+
+    let config = crate::printer::config::Config::default().with_align_ordered_list_markers(true);
+    let result = crate::printer::render_markdown(&doc, config);
+    let expected = (1..=12)
+        .map(|i| format!(" {:>3} item", format!("{i}.")))
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert_eq!(expected, result);
+    assert!(result.lines().all(|line| line.find("item") == Some(5)));
+
+    let reparsed =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), &result)
+            .unwrap();
+    assert_eq!(doc, reparsed);
+
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert!(result.starts_with(" 1. item\n 2. item"));
+}
+
+#[rstest(
+    input,
+    case(" a. one\n b. two\n c. three"),
+    case(" C. one\n D. two"),
+    case(" viii. one\n ix. two\n x. three"),
+    case(" I. one\n II. two")
+)]
+fn alphabetic_and_roman_list_round_trip(input: &str) {
+    let state = || {
+        crate::parser::MarkdownParserState::with_config(
+            crate::parser::config::MarkdownParserConfig::default().with_alphabetic_list_markers(),
+        )
+    };
+    let doc = crate::parser::parse_markdown(state(), input).unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(input, result);
+    assert_eq!(
+        doc,
+        crate::parser::parse_markdown(state(), &result).unwrap()
+    );
+}
+
+#[test]
+fn alphabetic_list_round_trip_past_z() {
+    let state = || {
+        crate::parser::MarkdownParserState::with_config(
+            crate::parser::config::MarkdownParserConfig::default().with_alphabetic_list_markers(),
+        )
+    };
+    let doc = crate::parser::parse_markdown(state(), "y. one\nz. two\nz. three").unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(" y. one\n z. two\n aa. three", result);
+    assert_eq!(
+        doc,
+        crate::parser::parse_markdown(state(), &result).unwrap()
+    );
+}
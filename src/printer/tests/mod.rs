@@ -311,6 +311,12 @@ let s = "hello\n";
 | Data 1   | Data 2   | Data 3   | Data 4   | Data 5   | Data 6   | Data 7   | Data 8   |
 | More 1   | More 2   | More 3   | More 4   | More 5   | More 6   | More 7   | More 8   |"#),
 
+        // Trailing whitespace and a trailing blank line in code, no final newline
+        case("```\nlet x = 1;  \n\n```"),
+
+        // Blank lines inside an indented code block
+        case("    a\n\n    b"),
+
 )]
 fn symmetric_round_trip(input: &str) {
     let config = crate::printer::config::Config::default();
@@ -320,3 +326,232 @@ fn symmetric_round_trip(input: &str) {
     let result = crate::printer::render_markdown(&doc, config);
     assert_eq!(input, result);
 }
+
+#[test]
+fn preserved_blank_lines_round_trip() {
+    let input = "a\n\n\n\nb\n\nc";
+    let config = crate::parser::config::MarkdownParserConfig::default().with_preserve_blank_lines();
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(config),
+        input,
+    )
+    .unwrap();
+    let printer_config = crate::printer::config::Config::default().with_preserve_blank_lines(true);
+    let result = crate::printer::render_markdown(&doc, printer_config);
+    assert_eq!(input, result);
+
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!("a\n\nb\n\nc", result);
+
+    // A skipped block is not a preserved blank line
+    let config = crate::parser::config::MarkdownParserConfig::default()
+        .with_block_html_block_behavior(crate::parser::config::ElementBehavior::Skip);
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(config),
+        "a\n\n<div>\n</div>\n\nb",
+    )
+    .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!("a\n\n\n\nb", result);
+}
+
+#[rstest(input, separation, expected,
+    // Paragraphs would merge, so they keep one blank line
+    case("a\n\nb", 0, "a\n\nb"),
+    case("a\n\nb", 1, "a\n\nb"),
+    case("a\n\nb", 2, "a\n\n\nb"),
+    case("# Title\n\nText\n\n```\ncode\n```\n\n---", 0, "# Title\nText\n```\ncode\n```\n---"),
+    case("# Title\n\nText", 1, "# Title\n\nText"),
+    case("> quote\n\ntext", 0, "> quote\n\ntext"),
+)]
+fn block_separation(input: &str, separation: usize, expected: &str) {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let config = crate::printer::config::Config::default().with_block_separation(separation);
+    let result = crate::printer::render_markdown(&doc, config);
+    assert_eq!(expected, result);
+    let reparsed =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), &result)
+            .unwrap();
+    assert_eq!(doc, reparsed);
+}
+
+#[rstest(
+    input,
+    case("<div  class='x'  data-y=\"1\">"),
+    case("<div  class='x'  data-y=\"1\">\n\n</div>"),
+    case("<div  class='x'  data-y=\"1\">\n  text   here\n</div>\n\nAfter"),
+    case("<!--  spaced   comment\n\n  still  -->"),
+    case("<pre  lang='x'>\n  a   b\n\n</pre>"),
+    case(" - <div  data-y='1'>\n   text  here\n   </div>")
+)]
+fn html_block_preserved(input: &str) {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(input, result);
+}
+
+#[test]
+fn inline_html_preserved() {
+    use crate::ast::*;
+
+    let html = "<span  class='x'\n   data-y=\"1\">";
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![
+            Inline::Text("a".to_string()),
+            Inline::Html(html.to_string()),
+            Inline::Text("b".to_string()),
+        ])],
+    };
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(format!("a{html}b"), result);
+}
+
+#[test]
+fn nested_container_fences() {
+    let input = "::::note\n:::details\nHidden\n:::\n::::\n";
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!("::::note\n:::details\nHidden\n:::\n::::", result);
+    let reparsed =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), &result)
+            .unwrap();
+    assert_eq!(doc, reparsed);
+}
+
+#[test]
+fn container_renderer() {
+    use crate::ast::*;
+
+    let container = |kind: &str, text: &str| {
+        Block::Container(Container {
+            kind: kind.to_string(),
+            params: vec![],
+            blocks: vec![Block::Paragraph(vec![Inline::Text(text.to_string())])],
+        })
+    };
+    let doc = Document {
+        blocks: vec![
+            container("warning", "Mind the gap"),
+            Block::List(List {
+                kind: ListKind::Bullet(ListBulletKind::Dash),
+                tight: true,
+                items: vec![ListItem {
+                    task: None,
+                    blocks: vec![container("warning", "Nested")],
+                }],
+            }),
+            container("aside", "Kept"),
+        ],
+    };
+    let config =
+        crate::printer::config::Config::default().with_container_renderer("warning", |container| {
+            let body = crate::printer::render_markdown(
+                &Document {
+                    blocks: container.blocks.clone(),
+                },
+                crate::printer::config::Config::default(),
+            );
+            format!("> [!WARNING]\n> {body}")
+        });
+    let result = crate::printer::render_markdown(&doc, config);
+    assert_eq!(
+        "> [!WARNING]\n> Mind the gap\n\n - > [!WARNING]\n   > Nested\n\n:::aside\nKept\n:::",
+        result
+    );
+}
+
+#[rstest(
+    input,
+    expected,
+    case("[ref]: https://example.com", "[ref]: https://example.com"),
+    case(
+        "[ref]: https://example.com 'Title'",
+        "[ref]: https://example.com \"Title\""
+    ),
+    case("[ref]: /url (Say \"hi\")", "[ref]: /url 'Say \"hi\"'"),
+    case(
+        "[ref]: /url (Say \"hi\" isn't it)",
+        "[ref]: /url \"Say \\\"hi\\\" isn't it\""
+    ),
+    case("[My *Ref*]: <my file.md>", "[My *Ref*]: <my file.md>"),
+    case("[ref]: <>", "[ref]: <>")
+)]
+fn link_definition(input: &str, expected: &str) {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(expected, result);
+    let reparsed =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), &result)
+            .unwrap();
+    assert_eq!(doc, reparsed);
+}
+
+#[rstest(
+    input,
+    case("[x](<a b>)"),
+    case("[x](/a%20b)"),
+    case("![x](<my image.png> \"Title\")"),
+    case("[x](<a)b>)"),
+    case("[x](a(b))"),
+    case("[x](a\\)b)"),
+    case("[x](<a\\<b\\\\c d>)"),
+    case("[x](<>)")
+)]
+fn link_destination_round_trip(input: &str) {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(input, result);
+}
+
+#[rstest(
+    input,
+    case("![x](/u){width=100pt height=50pt}"),
+    case("![x](/u \"Title\"){width=50%}"),
+    case("![x](/u){height=\"1 em\"}"),
+    case("![x](/u){}")
+)]
+fn image_attributes_round_trip(input: &str) {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(input, result);
+}
+
+#[test]
+fn render_fragments() {
+    use crate::ast::*;
+    use crate::printer::{config::Config, render_block, render_inlines};
+
+    let inlines = vec![Inline::Emphasis(vec![Inline::Text("caption".to_string())])];
+    assert_eq!("*caption*", render_inlines(&inlines, Config::default()));
+
+    let block = Block::BlockQuote(vec![Block::Paragraph(inlines)]);
+    assert_eq!("> *caption*", render_block(&block, Config::default()));
+}
+
+#[test]
+fn render_to_writer() {
+    use crate::printer::config::{Config, LineEnding};
+
+    let input = "# Title\n\nSome *text*.\n\n - a\n - b\n\n```\ncode\n```";
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    for config in [
+        Config::default(),
+        Config::default().with_line_ending(LineEnding::CrLf),
+    ] {
+        let mut out = Vec::new();
+        crate::printer::render_markdown_to(&doc, config.clone(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            crate::printer::render_markdown(&doc, config)
+        );
+    }
+}
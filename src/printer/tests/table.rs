@@ -161,3 +161,78 @@ fn table_with_user_example_structure() {
     );
     assert!(first_data_line.len() > 100, "Line should be long"); // Much longer than typical width
 }
+
+#[test]
+fn table_alignment_modes() {
+    use crate::printer::config::TableAlignment;
+
+    let input =
+        "| Name | Qty | Note |\n|:-:|--:|---|\n| Apple | 3 | x |\n| Kiwi | 12 | long note |";
+    let doc = parse_markdown(MarkdownParserState::default(), input).unwrap();
+
+    let pretty = render_markdown(
+        &doc,
+        Config::default().with_table_alignment(TableAlignment::Pretty),
+    );
+    assert_eq!(
+        pretty,
+        "| Name  | Qty | Note      |\n| :---: | --: | --------- |\n| Apple |   3 | x         |\n| Kiwi  |  12 | long note |"
+    );
+    assert_eq!(pretty, render_markdown(&doc, Config::default()));
+
+    let compact = render_markdown(
+        &doc,
+        Config::default().with_table_alignment(TableAlignment::Compact),
+    );
+    assert_eq!(
+        compact,
+        "| Name | Qty | Note |\n| :-: | --: | --- |\n| Apple | 3 | x |\n| Kiwi | 12 | long note |"
+    );
+
+    for output in [pretty, compact] {
+        assert_eq!(
+            parse_markdown(MarkdownParserState::default(), &output).unwrap(),
+            doc
+        );
+    }
+}
+
+#[test]
+fn table_cell_line_breaks_and_pipes() {
+    let cell = |content| TableCell {
+        content,
+        colspan: None,
+        rowspan: None,
+        removed_by_extended_table: false,
+    };
+    let doc = Document {
+        blocks: vec![Block::Table(Table {
+            rows: vec![
+                vec![cell(vec![Inline::Text("Name".to_string())])],
+                vec![cell(vec![
+                    Inline::Text("one".to_string()),
+                    Inline::LineBreak,
+                    Inline::Code("a | b".to_string()),
+                ])],
+            ],
+            alignments: vec![Alignment::None],
+        })],
+    };
+
+    let result = render_markdown(&doc, Config::default());
+    assert_eq!(
+        result,
+        "| Name            |\n| --------------- |\n| one<br>`a \\| b` |"
+    );
+
+    // The pipe survives a round trip; the break is raw HTML to the parser
+    let reparsed = parse_markdown(MarkdownParserState::default(), &result).unwrap();
+    let Block::Table(table) = &reparsed.blocks[0] else {
+        panic!("expected a table, got {:?}", reparsed.blocks);
+    };
+    assert_eq!(table.rows.len(), 2);
+    assert_eq!(
+        table.rows[1][0].content.last(),
+        Some(&Inline::Code("a | b".to_string()))
+    );
+}
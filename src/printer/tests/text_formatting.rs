@@ -55,3 +55,177 @@ fn text_newlines_normalize_to_spaces(input: &str, expected: &str) {
     let result = crate::printer::render_markdown(&doc, config);
     assert_eq!(expected, result);
 }
+
+#[test]
+fn text_escape_override() {
+    use crate::ast::*;
+
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![
+            Inline::Text("Issue #12 costs 5$ and ".to_string()),
+            Inline::Code("#code".to_string()),
+        ])],
+    };
+    let config = crate::printer::config::Config::default().with_escape_override(|c| match c {
+        '#' | '$' => Some(format!("\\{c}")),
+        _ => None,
+    });
+    let result = crate::printer::render_markdown(&doc, config);
+    assert_eq!("Issue \\#12 costs 5\\$ and `#code`", result);
+}
+
+#[rstest(
+    input,
+    style,
+    expected,
+    case(
+        "***text***",
+        crate::printer::config::CombinedEmphasis::Triple,
+        "***text***"
+    ),
+    case(
+        "**_text_**",
+        crate::printer::config::CombinedEmphasis::Triple,
+        "***text***"
+    ),
+    case(
+        "***text***",
+        crate::printer::config::CombinedEmphasis::StrongOutside,
+        "**_text_**"
+    ),
+    case(
+        "a **_two words_** b",
+        crate::printer::config::CombinedEmphasis::StrongOutside,
+        "a **_two words_** b"
+    ),
+    case(
+        "_**text**_",
+        crate::printer::config::CombinedEmphasis::EmphasisOutside,
+        "_**text**_"
+    ),
+    case(
+        "***text***",
+        crate::printer::config::CombinedEmphasis::EmphasisOutside,
+        "***text***"
+    ),
+    case(
+        "_**text**_",
+        crate::printer::config::CombinedEmphasis::Triple,
+        "_**text**_"
+    ),
+    case(
+        "_**text**_",
+        crate::printer::config::CombinedEmphasis::StrongOutside,
+        "_**text**_"
+    ),
+    case(
+        "**bold *and* more**",
+        crate::printer::config::CombinedEmphasis::StrongOutside,
+        "**bold *and* more**"
+    )
+)]
+fn combined_emphasis(input: &str, style: crate::printer::config::CombinedEmphasis, expected: &str) {
+    let config = crate::printer::config::Config::default().with_combined_emphasis(style);
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let result = crate::printer::render_markdown(&doc, config.clone());
+    assert_eq!(expected, result);
+
+    let reparsed =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), &result)
+            .unwrap();
+    assert_eq!(result, crate::printer::render_markdown(&reparsed, config));
+    assert_eq!(doc, reparsed);
+}
+
+#[rstest(
+    input,
+    case("_a_ *b* __c__ **d**"),
+    case("___a___ and ***b***"),
+    case("__outer _inner_ text__"),
+    case("**_mixed_**")
+)]
+fn preserved_emphasis_delimiters(input: &str) {
+    let state = crate::parser::MarkdownParserState::with_config(
+        crate::parser::config::MarkdownParserConfig::default().with_preserve_emphasis_delimiters(),
+    );
+    let doc = crate::parser::parse_markdown(state, input).unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(input, result);
+}
+
+#[rstest(
+    input,
+    style,
+    expected,
+    case(
+        "one two\nthree four",
+        crate::printer::config::SoftBreakStyle::Space,
+        "one two three four"
+    ),
+    case(
+        "one two\nthree four",
+        crate::printer::config::SoftBreakStyle::Newline,
+        "one two\nthree four"
+    ),
+    case(
+        "one two\nthree four",
+        crate::printer::config::SoftBreakStyle::LineBreak,
+        "one two  \nthree four"
+    ),
+    case(
+        "- one *two\n  three*",
+        crate::printer::config::SoftBreakStyle::Newline,
+        " - one *two\n   three*"
+    )
+)]
+fn soft_break(input: &str, style: crate::printer::config::SoftBreakStyle, expected: &str) {
+    let state = || {
+        crate::parser::MarkdownParserState::with_config(
+            crate::parser::config::MarkdownParserConfig::default().with_preserve_soft_breaks(),
+        )
+    };
+    let config = crate::printer::config::Config::default().with_soft_break(style);
+    let doc = crate::parser::parse_markdown(state(), input).unwrap();
+    let result = crate::printer::render_markdown(&doc, config.clone());
+    assert_eq!(expected, result);
+
+    let reparsed = crate::parser::parse_markdown(state(), &result).unwrap();
+    assert_eq!(result, crate::printer::render_markdown(&reparsed, config));
+}
+
+#[test]
+fn non_breaking_space_and_soft_hyphen() {
+    use crate::printer::config::{Config, NonBreakingSpaceStyle};
+
+    let input =
+        "Call 555\u{a0}0100 for in\u{ad}com\u{ad}pre\u{ad}hen\u{ad}si\u{ad}ble help, at 10\u{a0}am";
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    assert_eq!(
+        doc.blocks,
+        vec![crate::ast::Block::Paragraph(vec![
+            crate::ast::Inline::Text(input.to_string())
+        ])]
+    );
+
+    // Never broken at a non-breaking space, even when the line is too long
+    let result = crate::printer::render_markdown(&doc, Config::default().with_width(10));
+    assert!(result.contains("555\u{a0}0100"));
+    assert!(result.contains("10\u{a0}am"));
+    assert!(result.contains("in\u{ad}com\u{ad}pre\u{ad}hen\u{ad}si\u{ad}ble"));
+
+    let result = crate::printer::render_markdown(&doc, Config::default());
+    assert_eq!(result, input);
+
+    let config = Config::default().with_non_breaking_space(NonBreakingSpaceStyle::Entity);
+    let result = crate::printer::render_markdown(&doc, config);
+    assert_eq!(
+        result,
+        "Call 555&nbsp;0100 for in\u{ad}com\u{ad}pre\u{ad}hen\u{ad}si\u{ad}ble help, at 10&nbsp;am"
+    );
+    let reparsed =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), &result)
+            .unwrap();
+    assert_eq!(reparsed, doc);
+}
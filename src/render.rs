@@ -0,0 +1,284 @@
+//! Render one document to several output formats at once
+//!
+//! [`render_all`] runs every printer selected in [`RenderOptions`] over the
+//! same AST and collects the results in a [`RenderedBundle`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[cfg(feature = "printer")] {
+//! use markdown_ppp::ast::*;
+//! use markdown_ppp::render::{render_all, RenderOptions};
+//!
+//! let doc = Document {
+//!     blocks: vec![Block::Paragraph(vec![Inline::Text("Hello".to_string())])],
+//! };
+//! let options = RenderOptions::default()
+//!     .with_markdown(markdown_ppp::printer::config::Config::default());
+//! let bundle = render_all(&doc, &options);
+//! assert_eq!(bundle.markdown.as_deref(), Some("Hello"));
+//! # }
+//! ```
+
+use crate::ast::{Container, Document};
+use std::sync::Arc;
+
+/// Output formats to render, each with its printer configuration.
+///
+/// A format is rendered only if its configuration is set.
+#[derive(Clone, Default)]
+pub struct RenderOptions {
+    /// Configuration for the Markdown printer.
+    #[cfg(feature = "printer")]
+    pub markdown: Option<crate::printer::config::Config>,
+
+    /// Configuration for the Typst printer.
+    #[cfg(feature = "typst-printer")]
+    pub typst: Option<crate::typst_printer::config::Config>,
+}
+
+impl RenderOptions {
+    /// Render Markdown with the given configuration.
+    #[cfg(feature = "printer")]
+    #[allow(clippy::needless_update)]
+    pub fn with_markdown(self, config: crate::printer::config::Config) -> Self {
+        Self {
+            markdown: Some(config),
+            ..self
+        }
+    }
+
+    /// Render Typst with the given configuration.
+    #[cfg(feature = "typst-printer")]
+    #[allow(clippy::needless_update)]
+    pub fn with_typst(self, config: crate::typst_printer::config::Config) -> Self {
+        Self {
+            typst: Some(config),
+            ..self
+        }
+    }
+}
+
+/// Output of [`render_all`], one field per format.
+///
+/// Formats that were not requested are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderedBundle {
+    /// Rendered Markdown.
+    #[cfg(feature = "printer")]
+    pub markdown: Option<String>,
+
+    /// Rendered Typst source.
+    #[cfg(feature = "typst-printer")]
+    pub typst: Option<String>,
+}
+
+/// Line terminator used in rendered output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+
+    /// `\r\n`
+    CrLf,
+}
+
+/// Per-character escaping hook of a printer configuration, such as
+/// `printer::config::Config::with_escape_override`.
+///
+/// Returning `Some(s)` emits `s` for the character; `None` leaves the
+/// character to the printer's default handling.
+pub type EscapeFn = Arc<dyn Fn(char) -> Option<String> + Send + Sync>;
+
+/// Renderer for one container kind of a printer configuration, such as
+/// `printer::config::Config::with_container_renderer`.
+///
+/// Returns the output emitted in place of the container.
+pub type ContainerRenderFn = Arc<dyn Fn(&Container) -> String + Send + Sync>;
+
+/// Convert every line break in `text` to `ending`.
+///
+/// Both `\n` and `\r\n` are recognized as line breaks, so the function
+/// can be applied to text that already mixes them.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "printer")] {
+/// use markdown_ppp::render::{normalize_line_endings, LineEnding};
+///
+/// assert_eq!(normalize_line_endings("a\nb\r\nc", LineEnding::CrLf), "a\r\nb\r\nc");
+/// assert_eq!(normalize_line_endings("a\r\nb", LineEnding::Lf), "a\nb");
+/// # }
+/// ```
+pub fn normalize_line_endings(text: &str, ending: LineEnding) -> String {
+    let lf = text.replace("\r\n", "\n");
+    match ending {
+        LineEnding::Lf => lf,
+        LineEnding::CrLf => lf.replace('\n', "\r\n"),
+    }
+}
+
+/// Writer that converts line breaks to `ending` on the fly, like
+/// [`normalize_line_endings`] does for strings.
+///
+/// Text written with [`LineEnding::Lf`] is passed through as is.
+#[cfg(any(feature = "printer", feature = "typst-printer"))]
+pub(crate) struct LineEndingWriter<W> {
+    inner: W,
+    ending: LineEnding,
+    after_cr: bool,
+}
+
+#[cfg(any(feature = "printer", feature = "typst-printer"))]
+impl<W: std::io::Write> LineEndingWriter<W> {
+    pub(crate) fn new(inner: W, ending: LineEnding) -> Self {
+        Self {
+            inner,
+            ending,
+            after_cr: false,
+        }
+    }
+}
+
+#[cfg(any(feature = "printer", feature = "typst-printer"))]
+impl<W: std::io::Write> std::io::Write for LineEndingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.ending == LineEnding::Lf {
+            return self.inner.write(buf);
+        }
+        let mut start = 0;
+        for (i, &byte) in buf.iter().enumerate() {
+            if byte == b'\n' && !self.after_cr {
+                self.inner.write_all(&buf[start..i])?;
+                self.inner.write_all(b"\r")?;
+                start = i;
+            }
+            self.after_cr = byte == b'\r';
+        }
+        self.inner.write_all(&buf[start..])?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Render `doc` to every format enabled in `options`.
+pub fn render_all(doc: &Document, options: &RenderOptions) -> RenderedBundle {
+    RenderedBundle {
+        #[cfg(feature = "printer")]
+        markdown: options
+            .markdown
+            .clone()
+            .map(|config| crate::printer::render_markdown(doc, config)),
+        #[cfg(feature = "typst-printer")]
+        typst: options
+            .typst
+            .clone()
+            .map(|config| crate::typst_printer::render_typst(doc, config)),
+    }
+}
+
+#[cfg(all(test, feature = "printer", feature = "typst-printer"))]
+mod tests {
+    use super::*;
+    use crate::ast::*;
+
+    #[test]
+    fn renders_selected_formats() {
+        let doc = Document {
+            blocks: vec![
+                Block::Heading(Heading {
+                    kind: HeadingKind::Atx(1),
+                    content: vec![Inline::Text("Title".to_string())],
+                }),
+                Block::Paragraph(vec![Inline::Strong(vec![Inline::Text("bold".to_string())])]),
+            ],
+        };
+
+        let options = RenderOptions::default()
+            .with_markdown(crate::printer::config::Config::default())
+            .with_typst(crate::typst_printer::config::Config::default());
+        let bundle = render_all(&doc, &options);
+        assert_eq!(
+            bundle.markdown,
+            Some(crate::printer::render_markdown(
+                &doc,
+                crate::printer::config::Config::default()
+            ))
+        );
+        assert_eq!(
+            bundle.typst,
+            Some(crate::typst_printer::render_typst(
+                &doc,
+                crate::typst_printer::config::Config::default()
+            ))
+        );
+
+        let options =
+            RenderOptions::default().with_typst(crate::typst_printer::config::Config::default());
+        let bundle = render_all(&doc, &options);
+        assert_eq!(bundle.markdown, None);
+        assert!(bundle.typst.unwrap().contains("#heading(level: 1"));
+    }
+
+    #[test]
+    fn crlf_line_endings() {
+        let doc = Document {
+            blocks: vec![
+                Block::Heading(Heading {
+                    kind: HeadingKind::Atx(1),
+                    content: vec![Inline::Text("Title".to_string())],
+                }),
+                Block::CodeBlock(CodeBlock {
+                    kind: CodeBlockKind::Fenced {
+                        info: Some("rust".to_string()),
+                    },
+                    literal: "fn main() {\n}".to_string(),
+                }),
+                Block::Paragraph(vec![Inline::Text("End".to_string())]),
+            ],
+        };
+
+        let options = RenderOptions::default()
+            .with_markdown(
+                crate::printer::config::Config::default().with_line_ending(LineEnding::CrLf),
+            )
+            .with_typst(
+                crate::typst_printer::config::Config::default().with_line_ending(LineEnding::CrLf),
+            );
+        let bundle = render_all(&doc, &options);
+
+        let markdown = bundle.markdown.unwrap();
+        assert_eq!(
+            markdown,
+            "# Title\r\n\r\n```rust\r\nfn main() {\r\n}\r\n```\r\n\r\nEnd"
+        );
+
+        let typst = bundle.typst.unwrap();
+        assert!(typst.contains("fn main() {\r\n}"));
+        assert!(!typst.replace("\r\n", "").contains('\n'));
+        assert_eq!(
+            normalize_line_endings(&typst, LineEnding::Lf),
+            crate::typst_printer::render_typst(
+                &doc,
+                crate::typst_printer::config::Config::default()
+            )
+        );
+    }
+
+    #[test]
+    fn line_ending_writer_across_writes() {
+        use std::io::Write;
+
+        let mut out = Vec::new();
+        let mut writer = LineEndingWriter::new(&mut out, LineEnding::CrLf);
+        for chunk in ["a\r", "\nb\n", "\n", "c"] {
+            writer.write_all(chunk.as_bytes()).unwrap();
+        }
+        assert_eq!(out, b"a\r\nb\r\n\r\nc");
+    }
+}
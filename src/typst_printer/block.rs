@@ -1,4 +1,5 @@
 use crate::ast::*;
+use crate::typst_printer::config::BlockQuoteStyle;
 use crate::typst_printer::util::{body, escape_typst};
 use crate::typst_printer::ToDoc;
 use pretty::{Arena, DocAllocator, DocBuilder};
@@ -31,11 +32,7 @@ impl<'a> ToDoc<'a> for Block {
                 .append(inlines.to_doc(state))
                 .append("]"), //TODO: #par[]
             Block::Heading(heading) => {
-                let level = match heading.kind {
-                    HeadingKind::Atx(level) => level,
-                    HeadingKind::Setext(SetextHeading::Level1) => 1,
-                    HeadingKind::Setext(SetextHeading::Level2) => 2,
-                };
+                let level = heading.level();
                 state
                     .arena
                     .text("#heading(level: ")
@@ -44,10 +41,26 @@ impl<'a> ToDoc<'a> for Block {
                     // .append(state.arena.space())
                     .append(heading.content.to_doc(state))
                     .append("])")
+                    .append(
+                        match state
+                            .config
+                            .heading_labels
+                            .then(|| state.heading_label(&heading.content))
+                            .flatten()
+                        {
+                            Some(label) => state.arena.text(format!(" <{label}>")),
+                            None => state.arena.nil(),
+                        },
+                    )
             }
 
             Block::ThematicBreak => state.arena.text("#thematic-break"),
 
+            Block::BlockQuote(blocks)
+                if state.config.blockquote_style == BlockQuoteStyle::LinePrefix =>
+            {
+                prefixed_blockquote(blocks, state)
+            }
             Block::BlockQuote(blocks) => {
                 if blocks.is_empty() {
                     state.arena.text("#quote(block: true)[]")
@@ -64,12 +77,12 @@ impl<'a> ToDoc<'a> for Block {
 
             Block::CodeBlock(code_block) => {
                 let lang = match &code_block.kind {
-                    CodeBlockKind::Fenced { info: Some(lang) } => lang.as_str(),
-                    _ => "",
+                    CodeBlockKind::Fenced { info: Some(info) } => code_block_lang(info),
+                    _ => None,
                 };
 
                 let mut args = vec![state.arena.text("block: true")];
-                if !lang.is_empty() {
+                if let Some(lang) = lang {
                     args.push(state.arena.text(format!(r#", lang: "{}""#, lang)));
                 }
                 let escaped_code = code_block
@@ -85,7 +98,7 @@ impl<'a> ToDoc<'a> for Block {
                 &state.arena,
                 "raw",
                 None,
-                vec![state.arena.text(escape_typst(html))],
+                vec![state.arena.text(state.escape(&html.content))],
             ),
 
             Block::Definition(_) => state.arena.nil(),
@@ -120,7 +133,9 @@ impl<'a> ToDoc<'a> for Block {
                 .append(state.arena.text(escape_typst(&latex.clone())))
                 .append(state.arena.text("\")")),
             Block::Container(container) => {
-                if container.kind == "figure" {
+                if let Some(render) = state.config.container_renderers.get(&container.kind) {
+                    state.arena.text(render(container))
+                } else if container.kind == "figure" {
                     let mut doc = state.arena.text("#figure");
                     let mut args = Vec::new();
                     if let Some((_, caption)) =
@@ -129,7 +144,7 @@ impl<'a> ToDoc<'a> for Block {
                         args.push(
                             state
                                 .arena
-                                .text(format!("caption: [{}]", escape_typst(caption))),
+                                .text(format!("caption: [{}]", state.escape(caption))),
                         );
                     }
                     if !args.is_empty() {
@@ -170,8 +185,14 @@ impl<'a> ToDoc<'a> for List {
         if state.render_with_hash {
             doc = doc.append(state.arena.text("#"));
         }
-        let prefix = match self.kind {
-            ListKind::Ordered(_) => "enum(\n  [",
+        let prefix = match &self.kind {
+            ListKind::Ordered(options) => match options.style {
+                OrderedListStyle::Decimal => "enum(\n  [",
+                OrderedListStyle::LowerAlpha => "enum(numbering: \"a.\",\n  [",
+                OrderedListStyle::UpperAlpha => "enum(numbering: \"A.\",\n  [",
+                OrderedListStyle::LowerRoman => "enum(numbering: \"i.\",\n  [",
+                OrderedListStyle::UpperRoman => "enum(numbering: \"I.\",\n  [",
+            },
             ListKind::Bullet(_) => "list(\n  [",
         };
         doc = doc.append(prefix);
@@ -220,3 +241,41 @@ impl ListItem {
         }
     }
 }
+
+/// Extract a Typst language tag from a fenced code block info string.
+///
+/// Only the first whitespace- or comma-delimited token is used, so `rust,ignore`
+/// becomes `rust`. Tokens that cannot be a language name (e.g. `{.class}`) are
+/// dropped.
+fn code_block_lang(info: &str) -> Option<&str> {
+    let token = info
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .find(|token| !token.is_empty())?;
+    token
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '+' | '-' | '#' | '_' | '.'))
+        .then_some(token)
+}
+
+/// Quoted blocks with every line prefixed by `> `
+fn prefixed_blockquote<'a>(
+    blocks: &[Block],
+    state: &'a crate::typst_printer::State<'a>,
+) -> DocBuilder<'a, Arena<'a>, ()> {
+    let arena = Arena::new();
+    let inner = crate::typst_printer::State {
+        arena: &arena,
+        ..state.clone()
+    };
+    let doc = blocks.iter().collect::<Vec<_>>().to_doc(&inner);
+    let mut buf = Vec::new();
+    doc.render(state.config.width.saturating_sub(2), &mut buf)
+        .unwrap();
+    let text = String::from_utf8(buf).unwrap();
+
+    let lines = text.split('\n').map(|line| match line {
+        "" => state.arena.text(">"),
+        line => state.arena.text(format!("> {line}")),
+    });
+    state.arena.intersperse(lines, state.arena.hardline())
+}
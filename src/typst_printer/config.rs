@@ -3,6 +3,11 @@
 //! This module provides configuration options to customize the Typst output
 //! style and format.
 
+use crate::ast::Container;
+pub use crate::render::{ContainerRenderFn, EscapeFn, LineEnding};
+use std::collections::HashMap;
+use std::sync::Arc;
+
 /// Configuration for Typst rendering
 ///
 /// This struct controls various aspects of how the Markdown AST is converted
@@ -20,8 +25,60 @@
 /// let config = Config::default()
 ///     .with_width(120);
 /// ```
+#[derive(Clone)]
 pub struct Config {
     pub(crate) width: usize,
+    pub(crate) escape_override: Option<EscapeFn>,
+    pub(crate) preamble: bool,
+    pub(crate) line_ending: LineEnding,
+    pub(crate) container_renderers: HashMap<String, ContainerRenderFn>,
+    pub(crate) heading_labels: bool,
+    pub(crate) footnote_labels: bool,
+    pub(crate) unresolved_references: Option<UnresolvedReferences>,
+    pub(crate) blockquote_style: BlockQuoteStyle,
+    pub(crate) soft_break: SoftBreakStyle,
+}
+
+/// How references without a matching definition are rendered, see
+/// [`Config::with_unresolved_references`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnresolvedReferences {
+    /// Render the visible text: the link text, or the footnote label.
+    Text,
+
+    /// Render the original Markdown syntax, such as `[text][label]` or
+    /// `[^label]`.
+    Literal,
+
+    /// Make [`try_render_typst`](crate::typst_printer::try_render_typst)
+    /// fail. [`render_typst`](crate::typst_printer::render_typst) falls back
+    /// to [`UnresolvedReferences::Text`].
+    Error,
+}
+
+/// How block quotes are rendered, see [`Config::with_blockquote_style`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockQuoteStyle {
+    /// `#quote(block: true)[...]`
+    #[default]
+    Function,
+
+    /// Every line of the quoted content prefixed with `> `.
+    LinePrefix,
+}
+
+/// How soft line breaks are rendered, see [`Config::with_soft_break`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SoftBreakStyle {
+    /// A space, or a newline where the line gets too long.
+    #[default]
+    Space,
+
+    /// Always a newline in the output, which Typst still shows as a space.
+    Newline,
+
+    /// `#linebreak()`
+    LineBreak,
 }
 
 impl Default for Config {
@@ -30,7 +87,18 @@ impl Default for Config {
     /// Default settings:
     /// - Width: 80 characters
     fn default() -> Self {
-        Self { width: 80 }
+        Self {
+            width: 80,
+            escape_override: None,
+            preamble: false,
+            line_ending: LineEnding::Lf,
+            container_renderers: HashMap::new(),
+            heading_labels: false,
+            footnote_labels: false,
+            unresolved_references: None,
+            blockquote_style: BlockQuoteStyle::Function,
+            soft_break: SoftBreakStyle::Space,
+        }
     }
 }
 
@@ -54,4 +122,241 @@ impl Config {
     pub fn with_width(self, width: usize) -> Self {
         Self { width, ..self }
     }
+
+    /// Prepend definitions of the helper functions used by the output
+    ///
+    /// The printer emits `#thematic-break` and `#mi(...)`, which are not
+    /// built into Typst. With the preamble enabled the output starts with
+    /// their definitions (`mi` comes from the `mitex` package), so it
+    /// compiles on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::typst_printer::config::Config;
+    ///
+    /// let config = Config::default().with_preamble(true);
+    /// ```
+    pub fn with_preamble(self, preamble: bool) -> Self {
+        Self { preamble, ..self }
+    }
+
+    /// Customize how characters of text content are escaped
+    ///
+    /// The hook is consulted for every character of text, link titles, image
+    /// alt text and raw HTML before [`escape_typst`](crate::typst_printer::util::escape_typst).
+    /// URLs and LaTeX are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::typst_printer::config::Config;
+    ///
+    /// // Render tabs as spaces instead of `\t`
+    /// let config = Config::default().with_escape_override(|c| (c == '\t').then(|| " ".to_string()));
+    /// ```
+    pub fn with_escape_override<F>(self, escape: F) -> Self
+    where
+        F: Fn(char) -> Option<String> + Send + Sync + 'static,
+    {
+        Self {
+            escape_override: Some(Arc::new(escape)),
+            ..self
+        }
+    }
+
+    /// Set the line terminator of the output
+    ///
+    /// Defaults to [`LineEnding::Lf`]. Line breaks inside raw blocks and
+    /// other literal content are converted as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::typst_printer::config::{Config, LineEnding};
+    ///
+    /// let config = Config::default().with_line_ending(LineEnding::CrLf);
+    /// ```
+    pub fn with_line_ending(self, line_ending: LineEnding) -> Self {
+        Self {
+            line_ending,
+            ..self
+        }
+    }
+
+    /// Register a renderer for containers of the given `kind`
+    ///
+    /// The returned markup is emitted verbatim in place of the container.
+    /// Unregistered kinds keep the built-in rendering, which turns `figure`
+    /// containers into `#figure` and renders the content of any other
+    /// container as is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::typst_printer::config::Config;
+    ///
+    /// let config = Config::default().with_container_renderer("theorem", |container| {
+    ///     format!("#theorem[{} blocks]", container.blocks.len())
+    /// });
+    /// ```
+    pub fn with_container_renderer<F>(mut self, kind: impl Into<String>, render: F) -> Self
+    where
+        F: Fn(&Container) -> String + Send + Sync + 'static,
+    {
+        self.container_renderers
+            .insert(kind.into(), Arc::new(render));
+        self
+    }
+
+    /// Attach a label to every heading for cross-referencing
+    ///
+    /// Each heading is followed by a `<slug>` label built with
+    /// [`slugify`](crate::ast::slugify), so `@slug` refers to it. Repeated
+    /// slugs get a `-1`, `-2`, ... suffix to stay unique. Disabled by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::typst_printer::{config::Config, render_typst};
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Heading(Heading {
+    ///         kind: HeadingKind::Atx(1),
+    ///         content: vec![Inline::Text("Getting Started".to_string())],
+    ///     })],
+    /// };
+    /// let typst = render_typst(&doc, Config::default().with_heading_labels(true));
+    /// assert!(typst.contains("<getting-started>"));
+    /// ```
+    pub fn with_heading_labels(self, heading_labels: bool) -> Self {
+        Self {
+            heading_labels,
+            ..self
+        }
+    }
+
+    /// Render each footnote once and refer back to it on later references
+    ///
+    /// By default every reference to a footnote renders its whole content
+    /// in a `#footnote[...]`, so a footnote referenced twice shows up twice.
+    /// When enabled, the first reference renders `#footnote[...]` with a
+    /// `<footnote-label>` label and later ones render
+    /// `#footnote(<footnote-label>)`, which Typst shows as the same number.
+    /// Labels are made unique the same way as heading labels.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::typst_printer::{config::Config, render_typst};
+    ///
+    /// let reference = || Inline::FootnoteReference("note".to_string());
+    /// let doc = Document {
+    ///     blocks: vec![
+    ///         Block::Paragraph(vec![reference(), reference()]),
+    ///         Block::FootnoteDefinition(FootnoteDefinition {
+    ///             label: "note".to_string(),
+    ///             blocks: vec![Block::Paragraph(vec![Inline::Text("Body".to_string())])],
+    ///         }),
+    ///     ],
+    /// };
+    /// let typst = render_typst(&doc, Config::default().with_footnote_labels(true));
+    /// assert_eq!(typst.matches("Body").count(), 1);
+    /// assert!(typst.contains("#footnote(<footnote-note>)"));
+    /// ```
+    pub fn with_footnote_labels(self, footnote_labels: bool) -> Self {
+        Self {
+            footnote_labels,
+            ..self
+        }
+    }
+
+    /// Set how references without a matching definition are rendered
+    ///
+    /// By default an unresolved link reference renders its text and an
+    /// unresolved footnote reference its `[^label]` syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::typst_printer::config::{Config, UnresolvedReferences};
+    /// use markdown_ppp::typst_printer::try_render_typst;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![Inline::FootnoteReference(
+    ///         "missing".to_string(),
+    ///     )])],
+    /// };
+    /// let config = Config::default().with_unresolved_references(UnresolvedReferences::Error);
+    /// assert!(try_render_typst(&doc, config).is_err());
+    /// ```
+    pub fn with_unresolved_references(self, policy: UnresolvedReferences) -> Self {
+        Self {
+            unresolved_references: Some(policy),
+            ..self
+        }
+    }
+
+    /// Set how block quotes are rendered
+    ///
+    /// The default wraps them in the `#quote` function. With
+    /// [`BlockQuoteStyle::LinePrefix`] the quoted blocks are rendered as usual
+    /// and each of their lines is prefixed with `> `; nested quotes get one
+    /// prefix per level. Typst gives `>` no meaning, so the prefix shows up
+    /// as literal text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::typst_printer::config::{BlockQuoteStyle, Config};
+    /// use markdown_ppp::typst_printer::render_typst;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::BlockQuote(vec![Block::Paragraph(vec![
+    ///         Inline::Text("Quoted".to_string()),
+    ///     ])])],
+    /// };
+    /// let config = Config::default().with_blockquote_style(BlockQuoteStyle::LinePrefix);
+    /// assert_eq!(render_typst(&doc, config), r##"> #par[#"Quoted"]"##);
+    /// ```
+    pub fn with_blockquote_style(self, blockquote_style: BlockQuoteStyle) -> Self {
+        Self {
+            blockquote_style,
+            ..self
+        }
+    }
+
+    /// Set how soft line breaks are rendered
+    ///
+    /// Soft breaks only appear in the AST when the parser preserves them.
+    /// The default renders them as spaces, as Typst would.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdown_ppp::ast::*;
+    /// use markdown_ppp::typst_printer::config::{Config, SoftBreakStyle};
+    /// use markdown_ppp::typst_printer::render_typst;
+    ///
+    /// let doc = Document {
+    ///     blocks: vec![Block::Paragraph(vec![
+    ///         Inline::Text("one".to_string()),
+    ///         Inline::SoftBreak,
+    ///         Inline::Text("two".to_string()),
+    ///     ])],
+    /// };
+    /// let config = Config::default().with_soft_break(SoftBreakStyle::LineBreak);
+    /// assert_eq!(
+    ///     render_typst(&doc, config),
+    ///     r##"#par[#"one"#linebreak()#"two"]"##
+    /// );
+    /// ```
+    pub fn with_soft_break(self, soft_break: SoftBreakStyle) -> Self {
+        Self { soft_break, ..self }
+    }
 }
@@ -1,6 +1,7 @@
 use crate::ast::*;
+use crate::typst_printer::config::{SoftBreakStyle, UnresolvedReferences};
 use crate::typst_printer::util::{body, escape_typst};
-use crate::typst_printer::ToDoc;
+use crate::typst_printer::{ToDoc, UnresolvedReference};
 use once_cell::sync::Lazy;
 use pretty::{Arena, DocAllocator, DocBuilder};
 use regex::Regex;
@@ -9,7 +10,7 @@ static TYPST_RELATIVE_VALUE_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^(?: *[+-]? *(?:\d+(?:\.\d+)?|\.\d+)(?:pt|mm|cm|in|em|%))(?: *[+-] *(?:\d+(?:\.\d+)?|\.\d+)(?:pt|mm|cm|in|em|%))* *$").unwrap()
 });
 
-impl<'a> ToDoc<'a> for Vec<Inline> {
+impl<'a> ToDoc<'a> for [Inline] {
     fn to_doc(&self, state: &'a crate::typst_printer::State<'a>) -> DocBuilder<'a, Arena<'a>, ()> {
         state
             .arena
@@ -31,12 +32,19 @@ impl<'a> ToDoc<'a> for Inline {
                 //     None => state.arena.softline(),
                 // });
                 // state.arena.concat(words_or_spaces)
-                let escaped = escape_typst(&text);
+                let escaped = state.escape(text);
                 let formatted = format!("#\"{}\"", escaped);
                 state.arena.text(formatted)
             }
 
-            Inline::LineBreak => state.arena.hardline(),
+            // A newline in markup is only a space
+            Inline::LineBreak => state.arena.text("#linebreak()"),
+
+            Inline::SoftBreak => match state.config.soft_break {
+                SoftBreakStyle::Space => state.arena.softline(),
+                SoftBreakStyle::Newline => state.arena.hardline(),
+                SoftBreakStyle::LineBreak => state.arena.text("#linebreak()"),
+            },
 
             Inline::Code(code) => {
                 let escaped_code = code.replace('\\', r"\\").replace('"', r#"\""#);
@@ -52,7 +60,7 @@ impl<'a> ToDoc<'a> for Inline {
                 &state.arena,
                 "raw",
                 None,
-                vec![state.arena.text(escape_typst(html))],
+                vec![state.arena.text(state.escape(html))],
             ),
 
             Inline::Link(link) => {
@@ -63,7 +71,7 @@ impl<'a> ToDoc<'a> for Inline {
                     args.push(
                         state
                             .arena
-                            .text(format!(r#", title: "{}""#, escape_typst(title))),
+                            .text(format!(r#", title: "{}""#, state.escape(title))),
                     );
                 }
                 body(
@@ -83,7 +91,7 @@ impl<'a> ToDoc<'a> for Inline {
                         args.push(
                             state
                                 .arena
-                                .text(format!(r#", title: "{}""#, escape_typst(title))),
+                                .text(format!(r#", title: "{}""#, state.escape(title))),
                         );
                     }
                     body(
@@ -93,13 +101,31 @@ impl<'a> ToDoc<'a> for Inline {
                         vec![text],
                     )
                 } else {
-                    link_ref.text.to_doc(state)
+                    state
+                        .report_unresolved(UnresolvedReference::Link(inline_text(&link_ref.label)));
+                    if state.config.unresolved_references == Some(UnresolvedReferences::Literal) {
+                        let mut doc = state
+                            .arena
+                            .text("[")
+                            .append(link_ref.text.to_doc(state))
+                            .append(state.arena.text("]"));
+                        // Shortcut references only repeat the text
+                        if link_ref.label != link_ref.text {
+                            doc = doc
+                                .append(state.arena.text("["))
+                                .append(link_ref.label.to_doc(state))
+                                .append(state.arena.text("]"));
+                        }
+                        doc
+                    } else {
+                        link_ref.text.to_doc(state)
+                    }
                 }
             }
 
             Inline::Image(image) => {
                 let url = escape_typst(&image.destination);
-                let alt = escape_typst(&image.alt);
+                let alt = state.escape(&image.alt);
                 let mut res = format!("#box(image(\"{url}\", alt: \"{alt}\"");
                 if let Some(attr) = &image.attr {
                     if let Some(width) = &attr.width {
@@ -135,6 +161,26 @@ impl<'a> ToDoc<'a> for Inline {
                 .append(content.to_doc(state))
                 .append(state.arena.text("]")),
 
+            Inline::Highlight(content) => state
+                .arena
+                .text("#highlight[")
+                .append(content.to_doc(state))
+                .append(state.arena.text("]")),
+
+            Inline::Subscript(content) => state
+                .arena
+                .text("#sub[")
+                .append(content.to_doc(state))
+                .append(state.arena.text("]")),
+
+            Inline::Superscript(content) => state
+                .arena
+                .text("#super[")
+                .append(content.to_doc(state))
+                .append(state.arena.text("]")),
+
+            Inline::Delimited(delimited) => delimited.inner.to_doc(state),
+
             Inline::Autolink(url) => {
                 let escaped_url = escape_typst(url);
                 body(
@@ -147,6 +193,13 @@ impl<'a> ToDoc<'a> for Inline {
 
             Inline::FootnoteReference(label) => {
                 if let Some(def) = state.get_footnote_definition(label) {
+                    let typst_label = state
+                        .config
+                        .footnote_labels
+                        .then(|| state.footnote_label(label));
+                    if let Some((typst_label, false)) = typst_label {
+                        return state.arena.text(format!("#footnote(<{typst_label}>)"));
+                    }
                     let content = def
                         .blocks
                         .iter()
@@ -157,12 +210,22 @@ impl<'a> ToDoc<'a> for Inline {
                         .text("#footnote[")
                         .append(state.arena.concat(content))
                         .append(state.arena.text("]"))
+                        .append(match typst_label {
+                            Some((typst_label, _)) => state.arena.text(format!(" <{typst_label}>")),
+                            None => state.arena.nil(),
+                        })
                 } else {
-                    state
-                        .arena
-                        .text("[^")
-                        .append(state.arena.text(escape_typst(label)))
-                        .append(state.arena.text("]"))
+                    state.report_unresolved(UnresolvedReference::Footnote(label.clone()));
+                    match state.config.unresolved_references {
+                        Some(UnresolvedReferences::Text | UnresolvedReferences::Error) => {
+                            state.arena.text(format!("#\"{}\"", state.escape(label)))
+                        }
+                        None | Some(UnresolvedReferences::Literal) => state
+                            .arena
+                            .text("[^")
+                            .append(state.arena.text(state.escape(label)))
+                            .append(state.arena.text("]")),
+                    }
                 }
             }
 
@@ -67,8 +67,11 @@ pub mod util;
 mod tests;
 
 use crate::ast::*;
+use crate::render::LineEndingWriter;
 use pretty::{Arena, DocBuilder};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::io::Write;
 
 /// Internal state for Typst rendering
 ///
@@ -77,12 +80,17 @@ use std::collections::HashMap;
 #[derive(Clone)]
 pub(crate) struct State<'a> {
     arena: &'a Arena<'a>,
-    #[allow(unused)]
     config: &'a crate::typst_printer::config::Config,
     /// Mapping of footnote labels to their definitions.
     footnote_definitions: &'a HashMap<String, FootnoteDefinition>,
     /// Mapping of link labels to their definitions.
     link_definitions: &'a HashMap<Vec<Inline>, LinkDefinition>,
+    /// How many times each heading or footnote label slug has been used so far.
+    heading_slugs: &'a RefCell<HashMap<String, usize>>,
+    /// Typst labels of the footnotes rendered so far, by footnote label.
+    footnote_labels: &'a RefCell<HashMap<String, String>>,
+    /// References without a definition, in rendering order.
+    unresolved: &'a RefCell<Vec<UnresolvedReference>>,
     render_with_hash: bool,
 }
 
@@ -96,16 +104,73 @@ impl<'a> State<'a> {
         config: &'a crate::typst_printer::config::Config,
         footnote_definitions: &'a HashMap<String, FootnoteDefinition>,
         link_definitions: &'a HashMap<Vec<Inline>, LinkDefinition>,
+        heading_slugs: &'a RefCell<HashMap<String, usize>>,
+        footnote_labels: &'a RefCell<HashMap<String, String>>,
+        unresolved: &'a RefCell<Vec<UnresolvedReference>>,
     ) -> Self {
         Self {
             arena,
             config,
             footnote_definitions,
             link_definitions,
+            heading_slugs,
+            footnote_labels,
+            unresolved,
             render_with_hash: true,
         }
     }
 
+    /// Escape text content, honoring the configured escape override
+    pub fn escape(&self, text: &str) -> String {
+        util::escape_typst_with(text, self.config.escape_override.as_ref())
+    }
+
+    /// Build a unique label for a heading with the given content
+    ///
+    /// Returns `None` if the heading has no text to build a slug from.
+    pub fn heading_label(&self, content: &[Inline]) -> Option<String> {
+        let slug = slugify(&inline_text(content), SlugOptions::default());
+        if slug.is_empty() {
+            return None;
+        }
+        Some(self.unique_label(slug))
+    }
+
+    /// Typst label of a footnote, and whether this is its first reference
+    ///
+    /// Labels share the heading label namespace so the two never clash.
+    pub fn footnote_label(&self, label: &str) -> (String, bool) {
+        if let Some(existing) = self.footnote_labels.borrow().get(label) {
+            return (existing.clone(), false);
+        }
+        let slug = match slugify(label, SlugOptions::default()) {
+            slug if slug.is_empty() => "footnote".to_string(),
+            slug => format!("footnote-{slug}"),
+        };
+        let typst_label = self.unique_label(slug);
+        self.footnote_labels
+            .borrow_mut()
+            .insert(label.to_string(), typst_label.clone());
+        (typst_label, true)
+    }
+
+    /// `slug`, suffixed with `-1`, `-2`, ... if it was used before
+    fn unique_label(&self, slug: String) -> String {
+        let mut slugs = self.heading_slugs.borrow_mut();
+        let count = slugs.entry(slug.clone()).or_insert(0);
+        let label = match *count {
+            0 => slug,
+            n => format!("{slug}-{n}"),
+        };
+        *count += 1;
+        label
+    }
+
+    /// Record a reference without a matching definition
+    pub fn report_unresolved(&self, reference: UnresolvedReference) {
+        self.unresolved.borrow_mut().push(reference);
+    }
+
     /// Get the footnote definition for a label
     ///
     /// Returns `None` if the footnote is not defined in the document.
@@ -161,6 +226,7 @@ impl<'a> State<'a> {
 ///                     Inline::Text(" item.".to_string()),
 ///                 ])],
 ///             }],
+///             tight: true,
 ///         }),
 ///     ],
 /// };
@@ -172,16 +238,168 @@ impl<'a> State<'a> {
 /// // - [*Bold*] item
 /// ```
 pub fn render_typst(ast: &Document, config: crate::typst_printer::config::Config) -> String {
-    let (footnote_definitions, link_definitions) = get_indices(ast);
-    let arena = Arena::new();
-    let state = State::new(&arena, &config, &footnote_definitions, &link_definitions);
-    let doc = ast.to_doc(&state);
+    render(&ast.blocks, &config, config.preamble, |state| {
+        ast.to_doc(state)
+    })
+    .0
+}
 
+/// Render the given Markdown AST to Typst into a writer
+///
+/// Produces the same output as [`render_typst`], but writes it to `writer`
+/// without building a `String` first.
+///
+/// # Errors
+///
+/// Returns any error from writing to `writer`.
+pub fn render_typst_to<W: std::io::Write>(
+    ast: &Document,
+    config: crate::typst_printer::config::Config,
+    writer: W,
+) -> std::io::Result<()> {
+    render_to(
+        &ast.blocks,
+        &config,
+        config.preamble,
+        |state| ast.to_doc(state),
+        writer,
+    )?;
+    Ok(())
+}
+
+/// Render a sequence of inline elements to Typst
+///
+/// Renders a fragment the way it would appear inside a paragraph, without
+/// the preamble. Link and footnote references have no definitions to
+/// resolve against, so they follow the unresolved reference policy.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdown_ppp::ast::*;
+/// use markdown_ppp::typst_printer::{render_inlines, config::Config};
+///
+/// let inlines = vec![Inline::Emphasis(vec![Inline::Text("caption".to_string())])];
+/// assert_eq!(
+///     render_inlines(&inlines, Config::default()),
+///     r##"#emph[#"caption"]"##
+/// );
+/// ```
+pub fn render_inlines(inlines: &[Inline], config: crate::typst_printer::config::Config) -> String {
+    render(&[], &config, false, |state| inlines.to_doc(state)).0
+}
+
+/// Render a single block to Typst
+///
+/// Like [`render_inlines`], but for a block. Definitions nested in the block
+/// are used to resolve its references.
+pub fn render_block(block: &Block, config: crate::typst_printer::config::Config) -> String {
+    render(std::slice::from_ref(block), &config, false, |state| {
+        block.to_doc(state)
+    })
+    .0
+}
+
+/// Render the given Markdown AST to Typst, rejecting unresolved references
+///
+/// Behaves like [`render_typst`], but with
+/// [`UnresolvedReferences::Error`](config::UnresolvedReferences::Error) set
+/// it returns the first link or footnote reference that has no definition.
+/// With any other policy it always succeeds.
+pub fn try_render_typst(
+    ast: &Document,
+    config: crate::typst_printer::config::Config,
+) -> Result<String, UnresolvedReference> {
+    let (output, unresolved) = render(&ast.blocks, &config, config.preamble, |state| {
+        ast.to_doc(state)
+    });
+    match (config.unresolved_references, unresolved.into_iter().next()) {
+        (Some(config::UnresolvedReferences::Error), Some(reference)) => Err(reference),
+        _ => Ok(output),
+    }
+}
+
+/// A link or footnote reference without a matching definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnresolvedReference {
+    /// Link reference, with the label as plain text.
+    Link(String),
+
+    /// Footnote reference, with its label.
+    Footnote(String),
+}
+
+impl std::fmt::Display for UnresolvedReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnresolvedReference::Link(label) => write!(f, "undefined link reference [{label}]"),
+            UnresolvedReference::Footnote(label) => {
+                write!(f, "undefined footnote reference [^{label}]")
+            }
+        }
+    }
+}
+
+impl std::error::Error for UnresolvedReference {}
+
+/// Render the document built by `to_doc`, resolving references against the
+/// definitions found in `blocks`
+fn render<F>(
+    blocks: &[Block],
+    config: &crate::typst_printer::config::Config,
+    preamble: bool,
+    to_doc: F,
+) -> (String, Vec<UnresolvedReference>)
+where
+    F: for<'a> FnOnce(&'a State<'a>) -> DocBuilder<'a, Arena<'a>, ()>,
+{
     let mut buf = Vec::new();
-    doc.render(config.width, &mut buf).unwrap();
-    String::from_utf8(buf).unwrap()
+    let unresolved = render_to(blocks, config, preamble, to_doc, &mut buf).unwrap();
+    (String::from_utf8(buf).unwrap(), unresolved)
 }
 
+fn render_to<F, W>(
+    blocks: &[Block],
+    config: &crate::typst_printer::config::Config,
+    preamble: bool,
+    to_doc: F,
+    writer: W,
+) -> std::io::Result<Vec<UnresolvedReference>>
+where
+    F: for<'a> FnOnce(&'a State<'a>) -> DocBuilder<'a, Arena<'a>, ()>,
+    W: std::io::Write,
+{
+    let (footnote_definitions, link_definitions) = get_indices(blocks);
+    let arena = Arena::new();
+    let heading_slugs = RefCell::new(HashMap::new());
+    let footnote_labels = RefCell::new(HashMap::new());
+    let unresolved = RefCell::new(Vec::new());
+    let state = State::new(
+        &arena,
+        config,
+        &footnote_definitions,
+        &link_definitions,
+        &heading_slugs,
+        &footnote_labels,
+        &unresolved,
+    );
+    let doc = to_doc(&state);
+
+    let mut writer = LineEndingWriter::new(writer, config.line_ending);
+    if preamble {
+        writer.write_all(PREAMBLE.as_bytes())?;
+    }
+    doc.render(config.width, &mut writer)?;
+    writer.flush()?;
+    Ok(unresolved.into_inner())
+}
+
+/// Definitions of the non-builtin functions the printer emits
+const PREAMBLE: &str = r#"#import "@preview/mitex:0.2.5": mi
+#let thematic-break = align(center, line(length: 100%))
+
+"#;
+
 /// Internal trait for converting AST nodes to pretty-printer documents
 ///
 /// This trait is implemented by all AST node types and provides the core
@@ -197,7 +415,7 @@ impl<'a> ToDoc<'a> for Document {
     }
 }
 
-/// Extract footnote and link definition indices from the document
+/// Extract footnote and link definition indices from the blocks
 ///
 /// This function performs a pre-processing pass over the AST to:
 /// 1. Assign numeric indices to footnote definitions (1, 2, 3, ...)
@@ -207,7 +425,7 @@ impl<'a> ToDoc<'a> for Document {
 /// - footnote_index maps footnote labels to their numeric indices
 /// - link_definitions maps link labels to their full definitions
 fn get_indices(
-    ast: &Document,
+    blocks: &[Block],
 ) -> (
     HashMap<String, FootnoteDefinition>,
     HashMap<Vec<Inline>, LinkDefinition>,
@@ -244,11 +462,7 @@ fn get_indices(
         }
     }
 
-    process_blocks(
-        &ast.blocks,
-        &mut footnote_definitions,
-        &mut link_definitions,
-    );
+    process_blocks(blocks, &mut footnote_definitions, &mut link_definitions);
 
     (footnote_definitions, link_definitions)
 }
@@ -14,7 +14,10 @@ fn test_thematic_break() {
 #[test]
 fn test_html_block() {
     let doc = Document {
-        blocks: vec![Block::HtmlBlock("<div>Raw HTML</div>".to_string())],
+        blocks: vec![Block::HtmlBlock(HtmlBlock {
+            kind: None,
+            content: "<div>Raw HTML</div>".to_string(),
+        })],
     };
 
     let result = render_typst(&doc, Config::default());
@@ -254,6 +257,35 @@ fn test_strikethrough() {
     assert!(result.contains(r#"#strike[#"crossed out"]"#));
 }
 
+#[test]
+fn test_highlight() {
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![
+            Inline::Text("This is ".to_string()),
+            Inline::Highlight(vec![Inline::Text("important".to_string())]),
+        ])],
+    };
+
+    let result = render_typst(&doc, Config::default());
+    assert!(result.contains(r#"#highlight[#"important"]"#));
+}
+
+#[test]
+fn test_subscript_superscript() {
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![
+            Inline::Text("H".to_string()),
+            Inline::Subscript(vec![Inline::Text("2".to_string())]),
+            Inline::Text("O x".to_string()),
+            Inline::Superscript(vec![Inline::Text("2".to_string())]),
+        ])],
+    };
+
+    let result = render_typst(&doc, Config::default());
+    assert!(result.contains(r#"#sub[#"2"]"#));
+    assert!(result.contains(r#"#super[#"2"]"#));
+}
+
 #[test]
 fn test_autolink() {
     let doc = Document {
@@ -320,10 +352,14 @@ fn test_nested_elements() {
                             Inline::Text(" text".to_string()),
                         ])],
                     }],
+                    tight: true,
                 }),
             ]),
             Block::List(List {
-                kind: ListKind::Ordered(ListOrderedKindOptions { start: 5 }),
+                kind: ListKind::Ordered(ListOrderedKindOptions {
+                    start: 5,
+                    style: OrderedListStyle::Decimal,
+                }),
                 items: vec![ListItem {
                     task: Some(TaskState::Incomplete),
                     blocks: vec![
@@ -336,6 +372,7 @@ fn test_nested_elements() {
                         }),
                     ],
                 }],
+                tight: true,
             }),
         ],
     };
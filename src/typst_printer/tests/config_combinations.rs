@@ -1,5 +1,5 @@
 use crate::ast::*;
-use crate::typst_printer::{config::*, render_typst};
+use crate::typst_printer::{config::*, render_typst, try_render_typst, UnresolvedReference};
 
 #[test]
 fn test_width_configuration() {
@@ -61,3 +61,292 @@ fn test_default_config() {
     assert!(result.contains("#figure(table"));
     assert!(result.contains(r#"#raw(block: true, lang: "rust", ""#));
 }
+
+#[test]
+fn test_escape_override() {
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![
+            Inline::Text("#tag\t\"quoted\"".to_string()),
+            Inline::Image(Image {
+                destination: "a\tb.png".to_string(),
+                title: None,
+                alt: "x\ty".to_string(),
+                attr: None,
+            }),
+        ])],
+    };
+
+    let result = render_typst(&doc, Config::default());
+    assert_eq!(
+        result.trim(),
+        r##"#par[#"#tag\t\"quoted\""#box(image("a\tb.png", alt: "x\ty"))]"##
+    );
+
+    let config = Config::default().with_escape_override(|c| match c {
+        '\t' => Some(" ".to_string()),
+        '"' => Some("'".to_string()),
+        _ => None,
+    });
+    let result = render_typst(&doc, config);
+    assert_eq!(
+        result.trim(),
+        r##"#par[#"#tag 'quoted'"#box(image("a\tb.png", alt: "x y"))]"##
+    );
+}
+
+#[test]
+fn test_preamble() {
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![Inline::Text("Before".to_string())]),
+            Block::ThematicBreak,
+            Block::Paragraph(vec![Inline::Latex("x^2".to_string())]),
+        ],
+    };
+
+    let without = render_typst(&doc, Config::default());
+    assert!(without.starts_with("#par["));
+    assert!(!without.contains("#let"));
+
+    let with = render_typst(&doc, Config::default().with_preamble(true));
+    assert!(with.starts_with("#import \"@preview/mitex:"));
+    assert!(with.contains("#let thematic-break = "));
+    assert!(with.ends_with(&without));
+}
+
+#[test]
+fn test_container_renderer() {
+    let container = |kind: &str| {
+        Block::Container(Container {
+            kind: kind.to_string(),
+            params: vec![("title".to_string(), "Careful".to_string())],
+            blocks: vec![Block::Paragraph(vec![Inline::Text("Body".to_string())])],
+        })
+    };
+    let doc = Document {
+        blocks: vec![container("warning"), container("aside")],
+    };
+
+    let config = Config::default().with_container_renderer("warning", |container| {
+        let title = container
+            .params
+            .iter()
+            .find(|(key, _)| key == "title")
+            .map_or("", |(_, value)| value.as_str());
+        format!("#warning(title: \"{title}\")")
+    });
+    let result = render_typst(&doc, config);
+    // Unregistered kinds keep the default rendering
+    assert_eq!(
+        result.trim(),
+        "#warning(title: \"Careful\")\n\n#par[#\"Body\"]"
+    );
+}
+
+#[test]
+fn test_heading_labels() {
+    let heading = |level, content: Vec<Inline>| {
+        Block::Heading(Heading {
+            kind: HeadingKind::Atx(level),
+            content,
+        })
+    };
+    let doc = Document {
+        blocks: vec![
+            heading(1, vec![Inline::Text("Title".to_string())]),
+            heading(
+                2,
+                vec![
+                    Inline::Text("Using ".to_string()),
+                    Inline::Code("render_typst".to_string()),
+                ],
+            ),
+            heading(2, vec![Inline::Text("Title".to_string())]),
+            heading(3, vec![Inline::Text("!!".to_string())]),
+        ],
+    };
+
+    let result = render_typst(&doc, Config::default());
+    assert!(!result.contains('<'));
+
+    let result = render_typst(&doc, Config::default().with_heading_labels(true));
+    let headings: Vec<_> = result.lines().filter(|line| !line.is_empty()).collect();
+    assert_eq!(
+        headings,
+        vec![
+            r##"#heading(level: 1, [#"Title"]) <title>"##,
+            r##"#heading(level: 2, [#"Using "#raw("render_typst")]) <using-render_typst>"##,
+            r##"#heading(level: 2, [#"Title"]) <title-1>"##,
+            r##"#heading(level: 3, [#"!!"])"##,
+        ]
+    );
+}
+
+#[test]
+fn test_footnote_labels() {
+    let reference = |label: &str| Inline::FootnoteReference(label.to_string());
+    let doc = Document {
+        blocks: vec![
+            Block::Heading(Heading {
+                kind: HeadingKind::Atx(1),
+                content: vec![Inline::Text("Footnote note".to_string())],
+            }),
+            Block::Paragraph(vec![
+                Inline::Text("One".to_string()),
+                reference("note"),
+                reference("note"),
+            ]),
+            Block::Paragraph(vec![Inline::Text("Two".to_string()), reference("note")]),
+            Block::FootnoteDefinition(FootnoteDefinition {
+                label: "note".to_string(),
+                blocks: vec![Block::Paragraph(vec![Inline::Text(
+                    "A long footnote body".to_string(),
+                )])],
+            }),
+        ],
+    };
+
+    let result = render_typst(&doc, Config::default());
+    assert_eq!(result.matches("A long footnote body").count(), 3);
+
+    let config = Config::default()
+        .with_heading_labels(true)
+        .with_footnote_labels(true);
+    let result = render_typst(&doc, config);
+    let blocks: Vec<_> = result.lines().filter(|line| !line.is_empty()).collect();
+    assert_eq!(
+        blocks,
+        vec![
+            r##"#heading(level: 1, [#"Footnote note"]) <footnote-note>"##,
+            r##"#par[#"One"#footnote[#par[#"A long footnote body"]] <footnote-note-1>#footnote(<footnote-note-1>)]"##,
+            r##"#par[#"Two"#footnote(<footnote-note-1>)]"##,
+        ]
+    );
+}
+
+#[test]
+fn test_unresolved_references() {
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![
+            Inline::LinkReference(LinkReference {
+                label: vec![Inline::Text("ref".to_string())],
+                text: vec![Inline::Text("docs".to_string())],
+            }),
+            Inline::FootnoteReference("note".to_string()),
+        ])],
+    };
+    let render = |policy| render_typst(&doc, Config::default().with_unresolved_references(policy));
+
+    assert_eq!(
+        render_typst(&doc, Config::default()).trim(),
+        r##"#par[#"docs"[^note]]"##
+    );
+    assert_eq!(
+        render(UnresolvedReferences::Text).trim(),
+        r##"#par[#"docs"#"note"]"##
+    );
+    assert_eq!(
+        render(UnresolvedReferences::Literal).trim(),
+        r##"#par[[#"docs"][#"ref"][^note]]"##
+    );
+    assert_eq!(
+        render(UnresolvedReferences::Error),
+        render(UnresolvedReferences::Text)
+    );
+
+    let config = Config::default().with_unresolved_references(UnresolvedReferences::Error);
+    assert_eq!(
+        try_render_typst(&doc, config.clone()),
+        Err(UnresolvedReference::Link("ref".to_string()))
+    );
+    let resolved = Document {
+        blocks: vec![Block::Paragraph(vec![Inline::Text("fine".to_string())])],
+    };
+    assert_eq!(
+        try_render_typst(&resolved, config),
+        Ok(render_typst(&resolved, Config::default()))
+    );
+    assert!(try_render_typst(
+        &doc,
+        Config::default().with_unresolved_references(UnresolvedReferences::Literal)
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_blockquote_style() {
+    let paragraph = |text: &str| Block::Paragraph(vec![Inline::Text(text.to_string())]);
+    let two_paragraphs = Document {
+        blocks: vec![Block::BlockQuote(vec![paragraph("one"), paragraph("two")])],
+    };
+    let nested = Document {
+        blocks: vec![Block::BlockQuote(vec![
+            paragraph("outer"),
+            Block::BlockQuote(vec![paragraph("inner")]),
+        ])],
+    };
+
+    assert_eq!(
+        render_typst(&two_paragraphs, Config::default()),
+        "#quote(block: true)[#par[#\"one\"]\n\n#par[#\"two\"]]"
+    );
+    assert_eq!(
+        render_typst(&nested, Config::default()),
+        "#quote(block: true)[#par[#\"outer\"]\n\n#quote(block: true)[#par[#\"inner\"]]]"
+    );
+
+    let config = Config::default().with_blockquote_style(BlockQuoteStyle::LinePrefix);
+    assert_eq!(
+        render_typst(&two_paragraphs, config.clone()),
+        "> #par[#\"one\"]\n>\n> #par[#\"two\"]"
+    );
+    assert_eq!(
+        render_typst(&nested, config),
+        "> #par[#\"outer\"]\n>\n> > #par[#\"inner\"]"
+    );
+}
+
+#[test]
+fn test_render_to_writer() {
+    let doc = Document {
+        blocks: vec![
+            Block::Heading(Heading {
+                kind: HeadingKind::Atx(1),
+                content: vec![Inline::Text("Title".to_string())],
+            }),
+            Block::CodeBlock(CodeBlock {
+                kind: CodeBlockKind::Fenced { info: None },
+                literal: "a\r\nb\nc".to_string(),
+            }),
+        ],
+    };
+    for config in [
+        Config::default(),
+        Config::default()
+            .with_preamble(true)
+            .with_line_ending(LineEnding::CrLf),
+    ] {
+        let mut out = Vec::new();
+        crate::typst_printer::render_typst_to(&doc, config.clone(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), render_typst(&doc, config));
+    }
+}
+
+#[test]
+fn test_soft_break_style() {
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![
+            Inline::Text("one".to_string()),
+            Inline::SoftBreak,
+            Inline::Text("two".to_string()),
+        ])],
+    };
+    let render = |style| render_typst(&doc, Config::default().with_soft_break(style));
+
+    assert_eq!(render(SoftBreakStyle::Space), r##"#par[#"one" #"two"]"##);
+    assert_eq!(render(SoftBreakStyle::Newline), "#par[#\"one\"\n#\"two\"]");
+    assert_eq!(
+        render(SoftBreakStyle::LineBreak),
+        r##"#par[#"one"#linebreak()#"two"]"##
+    );
+}
@@ -55,6 +55,7 @@ fn test_empty_list() {
         blocks: vec![Block::List(List {
             kind: ListKind::Bullet(ListBulletKind::Star),
             items: vec![],
+            tight: true,
         })],
     };
 
@@ -71,6 +72,7 @@ fn test_empty_list_item() {
                 task: None,
                 blocks: vec![],
             }],
+            tight: true,
         })],
     };
 
@@ -179,6 +181,7 @@ fn test_deeply_nested_lists() {
                     task: None,
                     blocks: create_nested_list(depth - 1),
                 }],
+                tight: true,
             })]
         }
     }
@@ -113,13 +113,18 @@ fn test_lists() {
                         )])],
                     },
                 ],
+                tight: true,
             }),
             Block::List(List {
-                kind: ListKind::Ordered(ListOrderedKindOptions { start: 1 }),
+                kind: ListKind::Ordered(ListOrderedKindOptions {
+                    start: 1,
+                    style: OrderedListStyle::Decimal,
+                }),
                 items: vec![ListItem {
                     task: None,
                     blocks: vec![Block::Paragraph(vec![Inline::Text("Numbered".to_string())])],
                 }],
+                tight: true,
             }),
         ],
     };
@@ -133,6 +138,34 @@ fn test_lists() {
     assert_eq!(result.trim(), expected);
 }
 
+#[test]
+fn test_list_numbering_style() {
+    let list = |style| {
+        Block::List(List {
+            kind: ListKind::Ordered(ListOrderedKindOptions { start: 1, style }),
+            items: vec![ListItem {
+                task: None,
+                blocks: vec![Block::Paragraph(vec![Inline::Text("Item".to_string())])],
+            }],
+            tight: true,
+        })
+    };
+    let doc = Document {
+        blocks: vec![
+            list(OrderedListStyle::LowerAlpha),
+            list(OrderedListStyle::UpperRoman),
+        ],
+    };
+
+    let result = render_typst(&doc, Config::default());
+    let expected = [
+        "#enum(numbering: \"a.\",\n  [#\"Item\"],\n)",
+        "#enum(numbering: \"I.\",\n  [#\"Item\"],\n)",
+    ]
+    .join("\n\n");
+    assert_eq!(result.trim(), expected);
+}
+
 #[test]
 fn test_table() {
     let doc = Document {
@@ -184,6 +217,32 @@ fn test_table() {
     assert_eq!(result.trim(), expected);
 }
 
+#[test]
+fn test_table_cell_line_break() {
+    let cell = |content| TableCell {
+        content,
+        colspan: None,
+        rowspan: None,
+        removed_by_extended_table: false,
+    };
+    let doc = Document {
+        blocks: vec![Block::Table(Table {
+            rows: vec![
+                vec![cell(vec![Inline::Text("Name".to_string())])],
+                vec![cell(vec![
+                    Inline::Text("one".to_string()),
+                    Inline::LineBreak,
+                    Inline::Code("two".to_string()),
+                ])],
+            ],
+            alignments: vec![Alignment::None],
+        })],
+    };
+
+    let result = render_typst(&doc, Config::default());
+    assert!(result.contains(r##"  [#"one"#linebreak()#raw("two")],"##));
+}
+
 #[test]
 fn test_blockquote() {
     let doc = Document {
@@ -233,3 +292,50 @@ fn test_autolink() {
     let expected = r##"#par[#"Visit "#link("https://example.com")#"."]"##;
     assert_eq!(result.trim(), expected);
 }
+
+#[test]
+fn test_code_block_info_string() {
+    let render = |info: &str| {
+        let doc = Document {
+            blocks: vec![Block::CodeBlock(CodeBlock {
+                kind: CodeBlockKind::Fenced {
+                    info: Some(info.to_string()),
+                },
+                literal: "x".to_string(),
+            })],
+        };
+        render_typst(&doc, Config::default()).trim().to_string()
+    };
+
+    assert_eq!(
+        render("rust,ignore"),
+        r#"#raw(block: true, lang: "rust", "x")"#
+    );
+    assert_eq!(
+        render("python {.line-numbers}"),
+        r#"#raw(block: true, lang: "python", "x")"#
+    );
+    assert_eq!(render("c++"), r#"#raw(block: true, lang: "c++", "x")"#);
+    assert_eq!(render("{.attrs}"), r#"#raw(block: true, "x")"#);
+    assert_eq!(render(""), r#"#raw(block: true, "x")"#);
+}
+
+#[test]
+fn test_render_fragments() {
+    use crate::typst_printer::{render_block, render_inlines};
+
+    let inlines = vec![Inline::Emphasis(vec![Inline::Text("caption".to_string())])];
+    assert_eq!(
+        render_inlines(&inlines, Config::default()),
+        r##"#emph[#"caption"]"##
+    );
+
+    let block = Block::Paragraph(inlines);
+    let doc = Document {
+        blocks: vec![block.clone()],
+    };
+    assert_eq!(
+        render_block(&block, Config::default().with_preamble(true)),
+        render_typst(&doc, Config::default())
+    );
+}
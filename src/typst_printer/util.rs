@@ -3,6 +3,7 @@
 //! This module provides helper functions for Typst generation including
 //! character escaping and Typst function generation.
 
+use crate::typst_printer::config::EscapeFn;
 use pretty::{Arena, DocAllocator, DocBuilder};
 
 /// Escape Typst special characters in text
@@ -26,18 +27,33 @@ use pretty::{Arena, DocAllocator, DocBuilder};
 /// assert_eq!(escape_typst("\"Quoted\""), "\\\"Quoted\\\"");
 /// ```
 pub fn escape_typst(text: &str) -> String {
+    escape_typst_with(text, None)
+}
+
+/// Escape Typst special characters, consulting `escape_override` first
+///
+/// Characters for which the hook returns `None` are escaped as in
+/// [`escape_typst`].
+pub fn escape_typst_with(text: &str, escape_override: Option<&EscapeFn>) -> String {
     text.chars()
-        .map(|c| match c {
-            '\\' => r"\\".to_string(),
-            '"' => "\\\"".to_string(),
-            '\t' => r"\t".to_string(),
-            '\n' => r"\n".to_string(),
-            '\r' => r"\r".to_string(),
-            _ => c.to_string(),
+        .map(|c| match escape_override.and_then(|f| f(c)) {
+            Some(escaped) => escaped,
+            None => escape_char(c),
         })
         .collect()
 }
 
+fn escape_char(c: char) -> String {
+    match c {
+        '\\' => r"\\".to_string(),
+        '"' => "\\\"".to_string(),
+        '\t' => r"\t".to_string(),
+        '\n' => r"\n".to_string(),
+        '\r' => r"\r".to_string(),
+        _ => c.to_string(),
+    }
+}
+
 /// Create a Typst function call with content.
 /// e.g. `#name[content]` or `#name(..args)[content]`
 pub fn body<'a>(